@@ -57,7 +57,7 @@ const BettingInterface = () => {
       setBetPools(pools.map(pool => ({
         publicKey: pool.publicKey,
         totalBets: pool.account.totalBets.toString(),
-        odds: pool.account.odds,
+        odds: pool.account.oddsBps.toNumber() / 10_000,
         outcome: pool.account.outcome,
         bets: pool.account.bets,
       })));
@@ -0,0 +1,261 @@
+use std::error::Error;
+
+use clap::{Parser, Subcommand, ValueEnum};
+use client::betting::BetStatus;
+use client::vesting::UserType;
+use solana_client::{rpc_client::RpcClient, rpc_config::RpcProgramAccountsConfig};
+use solana_sdk::{
+    commitment_config::CommitmentConfig,
+    instruction::Instruction,
+    pubkey::Pubkey,
+    signature::{read_keypair_file, Keypair, Signer},
+    transaction::Transaction,
+};
+
+// Admin tool for the on-chain programs; RPC URL and keypair default to the Solana CLI config
+#[derive(Parser)]
+#[command(version, about)]
+struct Cli {
+    /// Solana CLI config file to read defaults from
+    #[arg(long, global = true)]
+    config: Option<String>,
+    /// RPC URL, overrides the config file
+    #[arg(long, global = true)]
+    url: Option<String>,
+    /// Fee payer / authority keypair, overrides the config file
+    #[arg(long, global = true)]
+    keypair: Option<String>,
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    #[command(subcommand)]
+    Vesting(VestingCommand),
+    #[command(subcommand)]
+    Betting(BettingCommand),
+    #[command(subcommand)]
+    Guardian(GuardianCommand),
+}
+
+#[derive(Subcommand)]
+enum VestingCommand {
+    /// Create the vesting state, mint and treasury
+    Init {
+        #[arg(long)]
+        total_supply: u64,
+        /// Cliff in seconds
+        #[arg(long)]
+        cliff: i64,
+        /// Vesting duration in seconds
+        #[arg(long)]
+        duration: i64,
+    },
+    AddBeneficiary {
+        #[arg(long)]
+        user: Pubkey,
+        /// The vesting state's main treasury
+        #[arg(long)]
+        treasury: Pubkey,
+        #[arg(long)]
+        allocation: u64,
+        #[arg(long, value_enum)]
+        user_type: UserTypeArg,
+        /// Minimum seconds between releases
+        #[arg(long)]
+        min_claim_interval: Option<i64>,
+    },
+    Release {
+        #[arg(long)]
+        mint: Pubkey,
+        #[arg(long)]
+        treasury: Pubkey,
+        #[arg(long)]
+        user: Pubkey,
+    },
+}
+
+#[derive(Subcommand)]
+enum BettingCommand {
+    CreatePool {
+        #[arg(long)]
+        outcome: String,
+    },
+    Resolve {
+        #[arg(long)]
+        bet_pool: Pubkey,
+        #[arg(long)]
+        bet_pool_token_account: Pubkey,
+        /// Token of the pool; winners are paid to their associated token accounts for it
+        #[arg(long)]
+        mint: Pubkey,
+        /// Bankroll backing the pool, if any
+        #[arg(long)]
+        bankroll: Option<Pubkey>,
+        /// Price feed of the pool, if it values bets in USD
+        #[arg(long)]
+        price_feed: Option<Pubkey>,
+        #[arg(long)]
+        winning_outcome: String,
+    },
+}
+
+#[derive(Subcommand)]
+enum GuardianCommand {
+    /// Create the pause registry with the configured keypair, the program's upgrade authority, as guardian
+    Init,
+    /// Pause every token-moving instruction in all non-exempt programs
+    Pause,
+    Unpause,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum UserTypeArg {
+    Founder,
+    Advisor,
+    Team,
+}
+
+impl From<UserTypeArg> for UserType {
+    fn from(arg: UserTypeArg) -> Self {
+        match arg {
+            UserTypeArg::Founder => UserType::Founder,
+            UserTypeArg::Advisor => UserType::Advisor,
+            UserTypeArg::Team => UserType::Team,
+        }
+    }
+}
+
+fn main() -> Result<(), Box<dyn Error>> {
+    let cli = Cli::parse();
+
+    let config_file = cli
+        .config
+        .clone()
+        .or_else(|| solana_cli_config::CONFIG_FILE.clone())
+        .ok_or("no Solana CLI config file found")?;
+    let config = solana_cli_config::Config::load(&config_file).unwrap_or_default();
+
+    let rpc = RpcClient::new_with_commitment(
+        cli.url.unwrap_or(config.json_rpc_url),
+        CommitmentConfig::confirmed(),
+    );
+    let payer = read_keypair_file(cli.keypair.unwrap_or(config.keypair_path))?;
+
+    match cli.command {
+        Command::Vesting(command) => run_vesting(&rpc, &payer, command),
+        Command::Betting(command) => run_betting(&rpc, &payer, command),
+        Command::Guardian(command) => run_guardian(&rpc, &payer, command),
+    }
+}
+
+fn run_vesting(rpc: &RpcClient, payer: &Keypair, command: VestingCommand) -> Result<(), Box<dyn Error>> {
+    match command {
+        VestingCommand::Init { total_supply, cliff, duration } => {
+            let mint = Keypair::new();
+            let treasury = Keypair::new();
+            let ix = client::vesting::initialize(
+                payer.pubkey(),
+                mint.pubkey(),
+                treasury.pubkey(),
+                total_supply,
+                cliff,
+                duration,
+            );
+            send(rpc, payer, ix, &[&mint, &treasury])?;
+            println!("mint: {}", mint.pubkey());
+            println!("treasury: {}", treasury.pubkey());
+        }
+        VestingCommand::AddBeneficiary { user, treasury, allocation, user_type, min_claim_interval } => {
+            let ix = client::vesting::add_beneficiary(
+                payer.pubkey(),
+                payer.pubkey(),
+                user,
+                treasury,
+                allocation,
+                user_type.into(),
+                min_claim_interval,
+            );
+            send(rpc, payer, ix, &[])?;
+        }
+        VestingCommand::Release { mint, treasury, user } => {
+            send(rpc, payer, client::vesting::release(mint, treasury, user), &[])?;
+        }
+    }
+    Ok(())
+}
+
+fn run_betting(rpc: &RpcClient, payer: &Keypair, command: BettingCommand) -> Result<(), Box<dyn Error>> {
+    match command {
+        BettingCommand::CreatePool { outcome } => {
+            let bet_pool = Keypair::new();
+            let ix = client::betting::create_betting_pool(bet_pool.pubkey(), payer.pubkey(), outcome);
+            send(rpc, payer, ix, &[&bet_pool])?;
+            println!("bet pool: {}", bet_pool.pubkey());
+        }
+        BettingCommand::Resolve {
+            bet_pool,
+            bet_pool_token_account,
+            mint,
+            bankroll,
+            price_feed,
+            winning_outcome,
+        } => {
+            // Settle every open receipt of the pool in the same transaction
+            let receipts: Vec<Pubkey> = rpc
+                .get_program_accounts_with_config(
+                    &client::betting::PROGRAM_ID,
+                    RpcProgramAccountsConfig {
+                        filters: Some(client::betting::bet_receipt_filters(
+                            None,
+                            Some(bet_pool),
+                            Some(BetStatus::Open),
+                        )),
+                        ..Default::default()
+                    },
+                )?
+                .into_iter()
+                .map(|(address, _)| address)
+                .collect();
+
+            let pool = client::betting::decode_bet_pool(&rpc.get_account_data(&bet_pool)?)
+                .ok_or("not a current-version bet pool")?;
+            let winners = client::betting::winner_token_accounts(&pool, &winning_outcome, &mint);
+
+            let ix = client::betting::resolve_bets(
+                payer.pubkey(),
+                bet_pool,
+                bet_pool_token_account,
+                bankroll,
+                price_feed,
+                &winners,
+                &receipts,
+                winning_outcome,
+            );
+            send(rpc, payer, ix, &[])?;
+        }
+    }
+    Ok(())
+}
+
+fn run_guardian(rpc: &RpcClient, payer: &Keypair, command: GuardianCommand) -> Result<(), Box<dyn Error>> {
+    let ix = match command {
+        GuardianCommand::Init => client::pause_registry::initialize(payer.pubkey()),
+        GuardianCommand::Pause => client::pause_registry::set_global_pause(payer.pubkey(), true),
+        GuardianCommand::Unpause => client::pause_registry::set_global_pause(payer.pubkey(), false),
+    };
+    send(rpc, payer, ix, &[])
+}
+
+fn send(rpc: &RpcClient, payer: &Keypair, ix: Instruction, extra_signers: &[&Keypair]) -> Result<(), Box<dyn Error>> {
+    let mut signers: Vec<&Keypair> = vec![payer];
+    signers.extend_from_slice(extra_signers);
+
+    let blockhash = rpc.get_latest_blockhash()?;
+    let tx = Transaction::new_signed_with_payer(&[ix], Some(&payer.pubkey()), &signers, blockhash);
+    let signature = rpc.send_and_confirm_transaction(&tx)?;
+
+    println!("signature: {}", signature);
+    Ok(())
+}
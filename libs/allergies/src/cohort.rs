@@ -0,0 +1,109 @@
+use crate::Allergen;
+
+pub type PatientId = u64;
+
+// Patients whose score includes `allergen`, in input order; works on raw scores, no `Allergies` needed
+pub fn screen(patients: &[(PatientId, u32)], allergen: Allergen) -> Vec<PatientId> {
+    let bit = allergen.score();
+    patients.iter().filter(|&&(_, score)| score & bit != 0).map(|&(id, _)| id).collect()
+}
+
+// Per-allergen counts over a cohort, gathered in a single pass over the scores
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CohortStats {
+    patients: usize,
+    counts: [usize; Allergen::ALL.len()],
+}
+
+impl CohortStats {
+    pub fn new(patients: &[(PatientId, u32)]) -> Self {
+        patients.iter().map(|&(_, score)| score).collect()
+    }
+
+    pub fn add(&mut self, score: u32) {
+        self.patients += 1;
+        for (i, count) in self.counts.iter_mut().enumerate() {
+            *count += (score >> i & 1) as usize;
+        }
+    }
+
+    pub fn patients(&self) -> usize {
+        self.patients
+    }
+
+    pub fn count(&self, allergen: Allergen) -> usize {
+        self.counts[allergen as usize]
+    }
+
+    // Share of patients allergic to `allergen`, 0.0 for an empty cohort
+    pub fn prevalence(&self, allergen: Allergen) -> f64 {
+        if self.patients == 0 {
+            return 0.0;
+        }
+        self.count(allergen) as f64 / self.patients as f64
+    }
+}
+
+impl FromIterator<u32> for CohortStats {
+    fn from_iter<I: IntoIterator<Item = u32>>(scores: I) -> Self {
+        let mut stats = CohortStats::default();
+        for score in scores {
+            stats.add(score);
+        }
+        stats
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Eggs = 1, Peanuts = 2, Shellfish = 4, ..., Cats = 128
+    const PATIENTS: [(PatientId, u32); 4] = [(10, 1 | 2), (11, 0), (12, 2 | 128), (13, 2 | 256)];
+
+    #[test]
+    fn screen_keeps_input_order() {
+        assert_eq!(screen(&PATIENTS, Allergen::Peanuts), vec![10, 12, 13]);
+        assert_eq!(screen(&PATIENTS, Allergen::Eggs), vec![10]);
+        assert!(screen(&PATIENTS, Allergen::Pollen).is_empty());
+        assert!(screen(&[], Allergen::Eggs).is_empty());
+    }
+
+    #[test]
+    fn stats_totals() {
+        let stats = CohortStats::new(&PATIENTS);
+        assert_eq!(stats.patients(), 4);
+        assert_eq!(stats.count(Allergen::Peanuts), 3);
+        assert_eq!(stats.count(Allergen::Eggs), 1);
+        assert_eq!(stats.count(Allergen::Cats), 1);
+        assert_eq!(stats.count(Allergen::Pollen), 0);
+        assert_eq!(stats.prevalence(Allergen::Peanuts), 0.75);
+        // Bits past the known allergens are ignored
+        let total: usize = Allergen::ALL.iter().map(|&a| stats.count(a)).sum();
+        assert_eq!(total, 5);
+    }
+
+    #[test]
+    fn stats_agree_with_screen() {
+        let stats = CohortStats::new(&PATIENTS);
+        for allergen in Allergen::ALL {
+            assert_eq!(stats.count(allergen), screen(&PATIENTS, allergen).len());
+        }
+    }
+
+    #[test]
+    fn empty_cohort() {
+        let stats = CohortStats::new(&[]);
+        assert_eq!(stats, CohortStats::default());
+        assert_eq!(stats.prevalence(Allergen::Eggs), 0.0);
+    }
+
+    #[test]
+    fn add_matches_collect() {
+        let mut stats = CohortStats::default();
+        for &(_, score) in &PATIENTS {
+            stats.add(score);
+        }
+        assert_eq!(stats, PATIENTS.iter().map(|&(_, score)| score).collect());
+    }
+}
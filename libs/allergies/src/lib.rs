@@ -0,0 +1,216 @@
+pub mod cohort;
+
+use std::collections::HashMap;
+
+pub use cohort::{screen, CohortStats, PatientId};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Allergen {
+    Eggs,
+    Peanuts,
+    Shellfish,
+    Strawberries,
+    Tomatoes,
+    Chocolate,
+    Pollen,
+    Cats,
+}
+
+impl Allergen {
+    pub const ALL: [Allergen; 8] = [
+        Allergen::Eggs,
+        Allergen::Peanuts,
+        Allergen::Shellfish,
+        Allergen::Strawberries,
+        Allergen::Tomatoes,
+        Allergen::Chocolate,
+        Allergen::Pollen,
+        Allergen::Cats,
+    ];
+
+    // Case-insensitive English name or alias ("egg", "Eggs"); use a `NameTable` for other languages
+    pub fn from_name(name: &str) -> Option<Allergen> {
+        NameTable::english().lookup(name)
+    }
+
+    // Bit this allergen sets in a score
+    pub fn score(self) -> u32 {
+        1 << self as u32
+    }
+}
+
+// Maps free-text labels to allergens, ignoring case and surrounding whitespace
+#[derive(Debug, Clone, Default)]
+pub struct NameTable {
+    names: HashMap<String, Allergen>,
+}
+
+impl NameTable {
+    pub fn new() -> Self {
+        NameTable::default()
+    }
+
+    // Singular and plural English names
+    pub fn english() -> Self {
+        NameTable::new()
+            .with("egg", Allergen::Eggs)
+            .with("eggs", Allergen::Eggs)
+            .with("peanut", Allergen::Peanuts)
+            .with("peanuts", Allergen::Peanuts)
+            .with("shellfish", Allergen::Shellfish)
+            .with("strawberry", Allergen::Strawberries)
+            .with("strawberries", Allergen::Strawberries)
+            .with("tomato", Allergen::Tomatoes)
+            .with("tomatoes", Allergen::Tomatoes)
+            .with("chocolate", Allergen::Chocolate)
+            .with("pollen", Allergen::Pollen)
+            .with("cat", Allergen::Cats)
+            .with("cats", Allergen::Cats)
+    }
+
+    pub fn with(mut self, label: &str, allergen: Allergen) -> Self {
+        self.insert(label, allergen);
+        self
+    }
+
+    pub fn insert(&mut self, label: &str, allergen: Allergen) {
+        self.names.insert(normalize(label), allergen);
+    }
+
+    pub fn lookup(&self, name: &str) -> Option<Allergen> {
+        self.names.get(&normalize(name)).copied()
+    }
+
+    // Combined score for a list of answers, or the first answer that isn't in the table
+    pub fn score<'a>(&self, answers: impl IntoIterator<Item = &'a str>) -> Result<u32, &'a str> {
+        answers.into_iter().try_fold(0, |score, answer| {
+            self.lookup(answer).map(|allergen| score | allergen.score()).ok_or(answer)
+        })
+    }
+}
+
+fn normalize(label: &str) -> String {
+    label.trim().to_lowercase()
+}
+
+pub struct Allergies {
+    score: u32,
+}
+
+impl Allergies {
+    // Constructor that accepts a score and returns a new Allergies instance
+    pub fn new(score: u32) -> Self {
+        Allergies { score }
+    }
+
+    // Method to determine if the patient is allergic to a specific allergen
+    pub fn is_allergic_to(&self, allergen: &Allergen) -> bool {
+        // Check if the bit corresponding to the allergen is set in the score
+        (self.score & allergen.score()) != 0
+    }
+
+    // Method to return a list of allergens the patient is allergic to
+    pub fn allergies(&self) -> Vec<Allergen> {
+        let mut allergens = Vec::new();
+        
+        if self.is_allergic_to(&Allergen::Eggs) {
+            allergens.push(Allergen::Eggs);
+        }
+        if self.is_allergic_to(&Allergen::Peanuts) {
+            allergens.push(Allergen::Peanuts);
+        }
+        if self.is_allergic_to(&Allergen::Shellfish) {
+            allergens.push(Allergen::Shellfish);
+        }
+        if self.is_allergic_to(&Allergen::Strawberries) {
+            allergens.push(Allergen::Strawberries);
+        }
+        if self.is_allergic_to(&Allergen::Tomatoes) {
+            allergens.push(Allergen::Tomatoes);
+        }
+        if self.is_allergic_to(&Allergen::Chocolate) {
+            allergens.push(Allergen::Chocolate);
+        }
+        if self.is_allergic_to(&Allergen::Pollen) {
+            allergens.push(Allergen::Pollen);
+        }
+        if self.is_allergic_to(&Allergen::Cats) {
+            allergens.push(Allergen::Cats);
+        }
+        
+        allergens
+    }
+
+    // Allergens both patients react to, in `Allergen::ALL` order
+    pub fn shared_allergies(&self, other: &Allergies) -> Vec<Allergen> {
+        Allergies::new(self.score & other.score).allergies()
+    }
+
+    // True if the patient is allergic to none of a meal's `required` allergens
+    pub fn is_safe_meal(&self, required: &[Allergen]) -> bool {
+        !required.iter().any(|allergen| self.is_allergic_to(allergen))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn from_name_accepts_aliases_in_any_case() {
+        assert_eq!(Allergen::from_name("egg"), Some(Allergen::Eggs));
+        assert_eq!(Allergen::from_name("  Eggs "), Some(Allergen::Eggs));
+        assert_eq!(Allergen::from_name("STRAWBERRY"), Some(Allergen::Strawberries));
+        assert_eq!(Allergen::from_name("cat"), Some(Allergen::Cats));
+    }
+
+    #[test]
+    fn from_name_rejects_unknown_names() {
+        assert_eq!(Allergen::from_name(""), None);
+        assert_eq!(Allergen::from_name("gluten"), None);
+        assert_eq!(Allergen::from_name("peanut butter"), None);
+    }
+
+    #[test]
+    fn every_allergen_has_an_english_name() {
+        let table = NameTable::english();
+        for allergen in Allergen::ALL {
+            assert!(table.names.values().any(|&a| a == allergen), "{allergen:?}");
+        }
+    }
+
+    #[test]
+    fn custom_table_lookups() {
+        let table = NameTable::new()
+            .with("Œufs", Allergen::Eggs)
+            .with("arachides", Allergen::Peanuts);
+        assert_eq!(table.lookup("œufs"), Some(Allergen::Eggs));
+        assert_eq!(table.lookup("Arachides"), Some(Allergen::Peanuts));
+        assert_eq!(table.lookup("eggs"), None);
+    }
+
+    #[test]
+    fn score_combines_answers_or_reports_the_unknown_one() {
+        let table = NameTable::english();
+        assert_eq!(table.score(["eggs", "Cats"]), Ok(1 | 128));
+        assert_eq!(table.score(["egg", "eggs"]), Ok(1));
+        assert_eq!(table.score([]), Ok(0));
+        assert_eq!(table.score(["eggs", "gluten", "soy"]), Err("gluten"));
+    }
+    #[test]
+    fn shared_allergies_in_canonical_order() {
+        let a = Allergies::new(1 | 4 | 64 | 128);
+        let b = Allergies::new(128 | 4 | 2);
+        assert_eq!(a.shared_allergies(&b), vec![Allergen::Shellfish, Allergen::Cats]);
+        assert_eq!(b.shared_allergies(&a), a.shared_allergies(&b));
+        assert!(a.shared_allergies(&Allergies::new(0)).is_empty());
+    }
+
+    #[test]
+    fn safe_meal_avoids_every_allergy() {
+        let patient = Allergies::new(2 | 32); // peanuts, chocolate
+        assert!(patient.is_safe_meal(&[Allergen::Eggs, Allergen::Tomatoes]));
+        assert!(!patient.is_safe_meal(&[Allergen::Eggs, Allergen::Chocolate]));
+        assert!(patient.is_safe_meal(&[]));
+    }
+}
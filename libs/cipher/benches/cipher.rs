@@ -0,0 +1,37 @@
+use cipher::Cipher;
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+
+const KEY: &str = "thequickbrownfoxjumpsoverthelazydog";
+
+fn payload(len: usize) -> String {
+    ('a'..='z').cycle().take(len).collect()
+}
+
+fn bench_encode_decode(c: &mut Criterion) {
+    let cipher = Cipher::new(Some(KEY));
+    let mut group = c.benchmark_group("cipher");
+
+    for len in [1 << 10, 1 << 20, 16 << 20] {
+        let plaintext = payload(len);
+        let ciphertext = cipher.encode(&plaintext);
+        group.throughput(Throughput::Bytes(len as u64));
+
+        group.bench_with_input(BenchmarkId::new("encode_chars", len), &plaintext, |b, pt| {
+            b.iter(|| cipher.encode(black_box(pt)))
+        });
+        group.bench_with_input(BenchmarkId::new("encode_bytes", len), &plaintext, |b, pt| {
+            b.iter(|| cipher.encode_bytes(black_box(pt.as_bytes())))
+        });
+        group.bench_with_input(BenchmarkId::new("decode_chars", len), &ciphertext, |b, ct| {
+            b.iter(|| cipher.decode(black_box(ct)))
+        });
+        group.bench_with_input(BenchmarkId::new("decode_bytes", len), &ciphertext, |b, ct| {
+            b.iter(|| cipher.decode_bytes(black_box(ct.as_bytes())))
+        });
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, bench_encode_decode);
+criterion_main!(benches);
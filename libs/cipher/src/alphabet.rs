@@ -0,0 +1,180 @@
+use std::error::Error;
+use std::fmt;
+
+// Marks ASCII characters that aren't in the alphabet
+const ABSENT: u16 = u16::MAX;
+
+// Ordered set of characters the cipher shifts within; a character's shift is its index
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Alphabet {
+    chars: Vec<char>,
+    ascii: [u16; 128],          // index of each ASCII member, the common case
+    others: Vec<(char, usize)>, // non-ASCII members sorted by char, for binary search
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AlphabetError {
+    Empty,
+    Duplicate(char),
+    TooLarge, // more than u16::MAX - 1 characters
+}
+
+impl fmt::Display for AlphabetError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AlphabetError::Empty => write!(f, "alphabet is empty"),
+            AlphabetError::Duplicate(c) => write!(f, "{c:?} appears twice in the alphabet"),
+            AlphabetError::TooLarge => write!(f, "alphabet has too many characters"),
+        }
+    }
+}
+
+impl Error for AlphabetError {}
+
+impl Alphabet {
+    // Characters in shift order, e.g. "abcdefghijklmnopqrstuvwxyz0123456789"
+    pub fn new(chars: &str) -> Result<Self, AlphabetError> {
+        let chars: Vec<char> = chars.chars().collect();
+        if chars.is_empty() {
+            return Err(AlphabetError::Empty);
+        }
+        if chars.len() >= ABSENT as usize {
+            return Err(AlphabetError::TooLarge);
+        }
+
+        let mut ascii = [ABSENT; 128];
+        let mut others = Vec::new();
+        for (i, &c) in chars.iter().enumerate() {
+            if c.is_ascii() {
+                if ascii[c as usize] != ABSENT {
+                    return Err(AlphabetError::Duplicate(c));
+                }
+                ascii[c as usize] = i as u16;
+            } else {
+                others.push((c, i));
+            }
+        }
+        others.sort_unstable();
+        if let Some(pair) = others.windows(2).find(|pair| pair[0].0 == pair[1].0) {
+            return Err(AlphabetError::Duplicate(pair[0].0));
+        }
+        Ok(Alphabet { chars, ascii, others })
+    }
+
+    // a–z, what `Cipher::new` uses
+    pub fn lowercase() -> Self {
+        Alphabet::new("abcdefghijklmnopqrstuvwxyz").unwrap()
+    }
+
+    pub fn len(&self) -> usize {
+        self.chars.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.chars.is_empty()
+    }
+
+    pub fn chars(&self) -> &[char] {
+        &self.chars
+    }
+
+    pub fn index_of(&self, c: char) -> Option<usize> {
+        if c.is_ascii() {
+            let i = self.ascii[c as usize];
+            return (i != ABSENT).then_some(i as usize);
+        }
+        self.others.binary_search_by_key(&c, |&(o, _)| o).ok().map(|i| self.others[i].1)
+    }
+
+    pub fn contains(&self, c: char) -> bool {
+        self.index_of(c).is_some()
+    }
+
+    // True if every character of `text` is in the alphabet
+    pub fn accepts(&self, text: &str) -> bool {
+        text.chars().all(|c| self.contains(c))
+    }
+
+    pub fn is_ascii(&self) -> bool {
+        self.others.is_empty()
+    }
+
+    // Index of an ASCII byte, for the byte-oriented paths
+    pub(crate) fn index_of_byte(&self, b: u8) -> Option<usize> {
+        let i = *self.ascii.get(b as usize)?;
+        (i != ABSENT).then_some(i as usize)
+    }
+
+    // The character `shift` places after (or before, backwards) `index`, wrapping around
+    pub(crate) fn shifted(&self, index: usize, shift: usize, forward: bool) -> char {
+        let n = self.chars.len();
+        let i = if forward { (index + shift) % n } else { (index + n - shift % n) % n };
+        self.chars[i]
+    }
+}
+
+impl Default for Alphabet {
+    fn default() -> Self {
+        Alphabet::lowercase()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{decode_with, encode_with, Cipher};
+
+    #[test]
+    fn rejects_invalid_alphabets() {
+        assert_eq!(Alphabet::new(""), Err(AlphabetError::Empty));
+        assert_eq!(Alphabet::new("abca"), Err(AlphabetError::Duplicate('a')));
+        assert_eq!(Alphabet::new("aébé"), Err(AlphabetError::Duplicate('é')));
+    }
+
+    #[test]
+    fn indexes_ascii_and_non_ascii_members() {
+        let alphabet = Alphabet::new("abc0123αβγ").unwrap();
+        assert_eq!(alphabet.len(), 10);
+        assert!(!alphabet.is_ascii());
+        assert_eq!(alphabet.index_of('c'), Some(2));
+        assert_eq!(alphabet.index_of('β'), Some(8));
+        assert_eq!(alphabet.index_of('z'), None);
+        assert_eq!(alphabet.index_of('δ'), None);
+        assert!(alphabet.accepts("a0γ"));
+        assert!(!alphabet.accepts("a0z"));
+    }
+
+    #[test]
+    fn custom_alphabet_round_trip() {
+        let alphabet = Alphabet::new("abcdefghijklmnopqrstuvwxyz0123456789").unwrap();
+        // '9' is the last character, so shifting it wraps back to 'a'
+        assert_eq!(encode_with(&alphabet, "b", "az9").as_deref(), Some("b0a"));
+        let encoded = encode_with(&alphabet, "k3y", "agent007").unwrap();
+        assert_eq!(decode_with(&alphabet, "k3y", &encoded).as_deref(), Some("agent007"));
+    }
+
+    #[test]
+    fn custom_alphabet_validates_key_and_text() {
+        let alphabet = Alphabet::new("0123456789").unwrap();
+        assert_eq!(encode_with(&alphabet, "", "123"), None);
+        assert_eq!(encode_with(&alphabet, "abc", "123"), None);
+        assert_eq!(encode_with(&alphabet, "7", "12a"), None);
+        assert_eq!(decode_with(&alphabet, "7", "12a"), None);
+
+        // A key outside the alphabet is replaced with a random one drawn from it
+        let cipher = Cipher::with_alphabet(Some("abc"), alphabet.clone());
+        assert!(alphabet.accepts(cipher.key()));
+    }
+
+    #[test]
+    fn byte_paths_match_char_paths() {
+        let plaintext = "the-quick-brown-fox-jumps-over-the-lazy-dog-".repeat(3);
+        for chars in ["abcdefghijklmnopqrstuvwxyz-", "abcdefghijklmnopqrstuvwxyz-αβγ"] {
+            let cipher = Cipher::with_alphabet(Some("key-"), Alphabet::new(chars).unwrap());
+            let encoded = cipher.encode(&plaintext);
+            assert_ne!(encoded, plaintext);
+            assert_eq!(cipher.encode_bytes(plaintext.as_bytes()), encoded.as_bytes());
+            assert_eq!(cipher.decode_bytes(encoded.as_bytes()), plaintext.as_bytes());
+        }
+    }
+}
@@ -0,0 +1,158 @@
+use crate::{decode, encode};
+
+// Line length of armored output, as in MIME, so payloads survive mail and config files
+pub const LINE_WIDTH: usize = 76;
+
+const BASE64: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+const HEX: &[u8; 16] = b"0123456789abcdef";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Armor {
+    Base64, // standard alphabet, padded
+    Hex,    // lowercase
+}
+
+// `encode`, with the ciphertext armored and wrapped at `LINE_WIDTH` columns
+pub fn encode_armored(key: &str, plaintext: &str, armor: Armor) -> Option<String> {
+    let ciphertext = encode(key, plaintext)?;
+    let armored = match armor {
+        Armor::Base64 => to_base64(ciphertext.as_bytes()),
+        Armor::Hex => to_hex(ciphertext.as_bytes()),
+    };
+    Some(wrap(&armored))
+}
+
+// Whitespace is ignored, so re-indented or re-wrapped payloads still decode
+pub fn decode_armored(key: &str, armored: &str, armor: Armor) -> Option<String> {
+    let armored: Vec<u8> = armored.bytes().filter(|b| !b.is_ascii_whitespace()).collect();
+    let ciphertext = match armor {
+        Armor::Base64 => from_base64(&armored)?,
+        Armor::Hex => from_hex(&armored)?,
+    };
+    decode(key, std::str::from_utf8(&ciphertext).ok()?)
+}
+
+fn wrap(armored: &str) -> String {
+    // Armored text is ASCII, so every chunk is valid UTF-8
+    armored
+        .as_bytes()
+        .chunks(LINE_WIDTH)
+        .map(|line| std::str::from_utf8(line).unwrap())
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn to_base64(bytes: &[u8]) -> String {
+    let mut out = String::with_capacity(bytes.len().div_ceil(3) * 4);
+    for chunk in bytes.chunks(3) {
+        let group = chunk.iter().enumerate().fold(0u32, |group, (i, &b)| group | (b as u32) << (16 - 8 * i));
+        for i in 0..4 {
+            if i <= chunk.len() {
+                out.push(BASE64[(group >> (18 - 6 * i) & 0x3f) as usize] as char);
+            } else {
+                out.push('=');
+            }
+        }
+    }
+    out
+}
+
+fn from_base64(armored: &[u8]) -> Option<Vec<u8>> {
+    if !armored.len().is_multiple_of(4) {
+        return None;
+    }
+    let mut out = Vec::with_capacity(armored.len() / 4 * 3);
+    let groups = armored.chunks(4).count();
+    for (n, chunk) in armored.chunks(4).enumerate() {
+        // Padding only at the very end, and at most two characters of it
+        let padding = chunk.iter().rev().take_while(|&&c| c == b'=').count();
+        if padding > 2 || (padding > 0 && n + 1 != groups) {
+            return None;
+        }
+        let mut group = 0u32;
+        for (i, &c) in chunk[..4 - padding].iter().enumerate() {
+            let value = BASE64.iter().position(|&b| b == c)? as u32;
+            group |= value << (18 - 6 * i);
+        }
+        out.extend_from_slice(&group.to_be_bytes()[1..4 - padding]);
+    }
+    Some(out)
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    bytes
+        .iter()
+        .flat_map(|&b| [HEX[(b >> 4) as usize] as char, HEX[(b & 0xf) as usize] as char])
+        .collect()
+}
+
+// Either case is accepted
+fn from_hex(armored: &[u8]) -> Option<Vec<u8>> {
+    if !armored.len().is_multiple_of(2) {
+        return None;
+    }
+    armored
+        .chunks(2)
+        .map(|pair| {
+            let digit = |c: u8| (c as char).to_digit(16);
+            Some((digit(pair[0])? << 4 | digit(pair[1])?) as u8)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn base64_padding() {
+        // Input lengths 3, 2 and 1 leave 0, 1 and 2 padding characters
+        assert_eq!(to_base64(b"abc"), "YWJj");
+        assert_eq!(to_base64(b"ab"), "YWI=");
+        assert_eq!(to_base64(b"a"), "YQ==");
+        assert_eq!(to_base64(b""), "");
+        for input in [&b"abc"[..], b"ab", b"a", b"", b"\x00\xff\x10\x80"] {
+            assert_eq!(from_base64(to_base64(input).as_bytes()).unwrap(), input);
+        }
+    }
+
+    #[test]
+    fn base64_rejects_invalid_input() {
+        assert_eq!(from_base64(b"YWJ"), None); // not a multiple of 4
+        assert_eq!(from_base64(b"Y==="), None); // too much padding
+        assert_eq!(from_base64(b"YQ==YWJj"), None); // padding before the end
+        assert_eq!(from_base64(b"YW!j"), None); // outside the alphabet
+    }
+
+    #[test]
+    fn hex_round_trip() {
+        assert_eq!(to_hex(b"\x00\xab\xff"), "00abff");
+        assert_eq!(from_hex(b"00abff").unwrap(), b"\x00\xab\xff");
+        assert_eq!(from_hex(b"00ABFF").unwrap(), b"\x00\xab\xff");
+        assert_eq!(from_hex(b"abc"), None);
+        assert_eq!(from_hex(b"zz"), None);
+    }
+
+    #[test]
+    fn armored_round_trip() {
+        let plaintext = "meetmebythebridgeatmidnight".repeat(4);
+        for armor in [Armor::Base64, Armor::Hex] {
+            let armored = encode_armored("lemon", &plaintext, armor).unwrap();
+            assert!(armored.lines().all(|line| line.len() <= LINE_WIDTH));
+            assert!(armored.lines().count() > 1);
+            assert_eq!(decode_armored("lemon", &armored, armor).unwrap(), plaintext);
+            // Re-indented payloads still decode
+            let reindented = armored.replace('\n', "\n    ");
+            assert_eq!(decode_armored("lemon", &reindented, armor).unwrap(), plaintext);
+        }
+    }
+
+    #[test]
+    fn armored_rejects_invalid_input() {
+        assert_eq!(encode_armored("lemon", "Hello", Armor::Base64), None);
+        assert_eq!(decode_armored("lemon", "not base64!", Armor::Base64), None);
+        assert_eq!(decode_armored("lemon", "0g", Armor::Hex), None);
+        // Decodes as armor, but to bytes outside the alphabet
+        assert_eq!(decode_armored("lemon", "QUJD", Armor::Base64), None);
+    }
+}
@@ -0,0 +1,134 @@
+use std::error::Error;
+use std::fmt;
+
+use crate::Cipher;
+
+// Envelope layout: "vc<version>.<key length>.<checksum as 8 hex digits>.<ciphertext>"
+const MAGIC: &str = "vc";
+pub const VERSION: u8 = 1;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EnvelopeError {
+    InvalidPlaintext, // only characters of the cipher's alphabet can be encoded
+    Malformed,
+    UnsupportedVersion(u8),
+    KeyLengthMismatch { expected: usize, actual: usize },
+    ChecksumMismatch, // almost always a wrong key of the right length
+}
+
+impl fmt::Display for EnvelopeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            EnvelopeError::InvalidPlaintext => write!(f, "plaintext is outside the alphabet"),
+            EnvelopeError::Malformed => write!(f, "not a cipher envelope"),
+            EnvelopeError::UnsupportedVersion(v) => write!(f, "unsupported envelope version {v}"),
+            EnvelopeError::KeyLengthMismatch { expected, actual } => {
+                write!(f, "envelope was sealed with a {expected}-letter key, got {actual}")
+            }
+            EnvelopeError::ChecksumMismatch => write!(f, "checksum mismatch, wrong key?"),
+        }
+    }
+}
+
+impl Error for EnvelopeError {}
+
+// Encodes `plaintext` behind a header that lets `open` reject the wrong key
+pub fn seal(cipher: &Cipher, plaintext: &str) -> Result<String, EnvelopeError> {
+    if !cipher.alphabet().accepts(plaintext) {
+        return Err(EnvelopeError::InvalidPlaintext);
+    }
+    let ciphertext = cipher.encode_bytes(plaintext.as_bytes());
+    // Encoding characters of the alphabet yields characters of the alphabet
+    let ciphertext = String::from_utf8(ciphertext).unwrap();
+    Ok(format!(
+        "{MAGIC}{VERSION}.{}.{:08x}.{ciphertext}",
+        key_len(cipher),
+        checksum(plaintext.as_bytes())
+    ))
+}
+
+pub fn open(cipher: &Cipher, envelope: &str) -> Result<String, EnvelopeError> {
+    let rest = envelope.strip_prefix(MAGIC).ok_or(EnvelopeError::Malformed)?;
+    let mut fields = rest.splitn(4, '.');
+    let (Some(version), Some(key_len), Some(sum), Some(ciphertext)) =
+        (fields.next(), fields.next(), fields.next(), fields.next())
+    else {
+        return Err(EnvelopeError::Malformed);
+    };
+
+    let version: u8 = version.parse().map_err(|_| EnvelopeError::Malformed)?;
+    if version != VERSION {
+        return Err(EnvelopeError::UnsupportedVersion(version));
+    }
+    let expected: usize = key_len.parse().map_err(|_| EnvelopeError::Malformed)?;
+    let actual = self::key_len(cipher);
+    if expected != actual {
+        return Err(EnvelopeError::KeyLengthMismatch { expected, actual });
+    }
+    let sum = u32::from_str_radix(sum, 16).map_err(|_| EnvelopeError::Malformed)?;
+    if !cipher.alphabet().accepts(ciphertext) {
+        return Err(EnvelopeError::Malformed);
+    }
+
+    let plaintext = cipher.decode_bytes(ciphertext.as_bytes());
+    if checksum(&plaintext) != sum {
+        return Err(EnvelopeError::ChecksumMismatch);
+    }
+    Ok(String::from_utf8(plaintext).unwrap())
+}
+
+// In characters, which differs from bytes for non-ASCII alphabets
+fn key_len(cipher: &Cipher) -> usize {
+    cipher.key().chars().count()
+}
+
+// 32-bit FNV-1a; catches wrong keys, not tampering
+fn checksum(bytes: &[u8]) -> u32 {
+    bytes.iter().fold(0x811c_9dc5, |hash, &b| (hash ^ b as u32).wrapping_mul(0x0100_0193))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trip() {
+        let cipher = Cipher::new(Some("secret"));
+        let envelope = seal(&cipher, "attackatdawn").unwrap();
+        assert!(envelope.starts_with("vc1.6."));
+        assert_eq!(open(&cipher, &envelope).unwrap(), "attackatdawn");
+    }
+
+    #[test]
+    fn wrong_key_of_the_same_length_fails_the_checksum() {
+        let envelope = seal(&Cipher::new(Some("secret")), "attackatdawn").unwrap();
+        assert_eq!(
+            open(&Cipher::new(Some("sesame")), &envelope),
+            Err(EnvelopeError::ChecksumMismatch)
+        );
+    }
+
+    #[test]
+    fn tampered_checksum_is_rejected() {
+        let cipher = Cipher::new(Some("secret"));
+        let envelope = seal(&cipher, "attackatdawn").unwrap();
+        let mut fields: Vec<&str> = envelope.splitn(4, '.').collect();
+        let sum = format!("{:08x}", u32::from_str_radix(fields[2], 16).unwrap() ^ 1);
+        fields[2] = &sum;
+        assert_eq!(open(&cipher, &fields.join(".")), Err(EnvelopeError::ChecksumMismatch));
+    }
+
+    #[test]
+    fn header_errors() {
+        let cipher = Cipher::new(Some("secret"));
+        assert_eq!(seal(&cipher, "Attack"), Err(EnvelopeError::InvalidPlaintext));
+        assert_eq!(open(&cipher, "attack"), Err(EnvelopeError::Malformed));
+        assert_eq!(open(&cipher, "vc1.6.0000"), Err(EnvelopeError::Malformed));
+        assert_eq!(open(&cipher, "vc2.6.00000000.abc"), Err(EnvelopeError::UnsupportedVersion(2)));
+        assert_eq!(
+            open(&cipher, "vc1.4.00000000.abc"),
+            Err(EnvelopeError::KeyLengthMismatch { expected: 4, actual: 6 })
+        );
+        assert_eq!(open(&cipher, "vc1.6.00000000.ABC"), Err(EnvelopeError::Malformed));
+    }
+}
@@ -0,0 +1,208 @@
+pub mod alphabet;
+pub mod armor;
+pub mod envelope;
+
+pub use alphabet::{Alphabet, AlphabetError};
+
+use rand::rng;
+use rand::seq::IndexedRandom;
+
+// Bytes handled per pass of the fast path; sized so the inner loop vectorizes
+const BLOCK: usize = 64;
+
+pub struct Cipher {
+    key: String,
+    alphabet: Alphabet,
+    lowercase: bool, // alphabet is a–z, so the byte paths can use plain arithmetic
+}
+
+impl Cipher {
+    fn random_key(alphabet: &Alphabet) -> String {
+        let mut rng = rng();
+        (0..100)
+            .map(|_| *alphabet.chars().choose(&mut rng).unwrap())
+            .collect()
+    }
+
+    fn is_valid_key(key: &str, alphabet: &Alphabet) -> bool {
+        !key.is_empty() && alphabet.accepts(key)
+    }
+
+    pub fn new(key: Option<&str>) -> Self {
+        Self::with_alphabet(key, Alphabet::lowercase())
+    }
+
+    // As `new`, but shifting within `alphabet`; the key must be drawn from it too
+    pub fn with_alphabet(key: Option<&str>, alphabet: Alphabet) -> Self {
+        let key = match key {
+            Some(k) if Self::is_valid_key(k, &alphabet) => k.to_string(),
+            _ => Self::random_key(&alphabet),
+        };
+        let lowercase = alphabet == Alphabet::lowercase();
+        Self { key, alphabet, lowercase }
+    }
+
+    // Characters outside the alphabet pass through unchanged
+    pub fn encode(&self, plaintext: &str) -> String {
+        self.shift_chars(plaintext, true)
+    }
+
+    pub fn decode(&self, ciphertext: &str) -> String {
+        self.shift_chars(ciphertext, false)
+    }
+
+    fn shift_chars(&self, text: &str, forward: bool) -> String {
+        text.chars()
+            .zip(self.key.chars().cycle())
+            .map(|(c, k)| match (self.alphabet.index_of(c), self.alphabet.index_of(k)) {
+                (Some(index), Some(shift)) => self.alphabet.shifted(index, shift, forward),
+                _ => c,
+            })
+            .collect()
+    }
+
+    pub fn key(&self) -> &str {
+        &self.key
+    }
+
+    pub fn alphabet(&self) -> &Alphabet {
+        &self.alphabet
+    }
+
+    // Byte-oriented fast path for large payloads. With the a–z alphabet, input must be
+    // lowercase ASCII; other bytes don't panic but come out garbled. Other ASCII alphabets
+    // pass non-members through, and non-ASCII alphabets fall back to `encode` on UTF-8 input.
+    pub fn encode_bytes(&self, plaintext: &[u8]) -> Vec<u8> {
+        if self.lowercase {
+            self.transform(plaintext, |pt, shift| {
+                let c = pt.wrapping_add(shift);
+                if c > b'z' { c - 26 } else { c }
+            })
+        } else if self.alphabet.is_ascii() {
+            self.transform(plaintext, |pt, shift| self.shift_byte(pt, shift, true))
+        } else {
+            self.encode(&String::from_utf8_lossy(plaintext)).into_bytes()
+        }
+    }
+
+    pub fn decode_bytes(&self, ciphertext: &[u8]) -> Vec<u8> {
+        if self.lowercase {
+            self.transform(ciphertext, |ct, shift| {
+                let c = ct.wrapping_sub(shift);
+                if c < b'a' { c.wrapping_add(26) } else { c }
+            })
+        } else if self.alphabet.is_ascii() {
+            self.transform(ciphertext, |ct, shift| self.shift_byte(ct, shift, false))
+        } else {
+            self.decode(&String::from_utf8_lossy(ciphertext)).into_bytes()
+        }
+    }
+
+    // Only called for ASCII alphabets, so the shifted character is a single byte
+    fn shift_byte(&self, b: u8, shift: u8, forward: bool) -> u8 {
+        match self.alphabet.index_of_byte(b) {
+            Some(index) => self.alphabet.shifted(index, shift as usize, forward) as u8,
+            None => b,
+        }
+    }
+
+    // Applies `f(byte, shift)` a block at a time against the key repeated to cover any
+    // block offset, so the inner loop is a plain zip over equal-length slices. Only used
+    // with ASCII alphabets, which have at most 128 characters, so every shift fits a byte.
+    fn transform(&self, input: &[u8], f: impl Fn(u8, u8) -> u8) -> Vec<u8> {
+        let shifts: Vec<u8> = self
+            .key
+            .bytes()
+            .map(|k| self.alphabet.index_of_byte(k).unwrap() as u8)
+            .collect();
+        let expanded: Vec<u8> = shifts.iter().copied().cycle().take(shifts.len() + BLOCK).collect();
+
+        let mut output = vec![0; input.len()];
+        let mut offset = 0;
+        for (src, dst) in input.chunks(BLOCK).zip(output.chunks_mut(BLOCK)) {
+            let key = &expanded[offset..offset + src.len()];
+            for ((d, &s), &k) in dst.iter_mut().zip(src).zip(key) {
+                *d = f(s, k);
+            }
+            offset = (offset + BLOCK) % shifts.len();
+        }
+        output
+    }
+}
+
+// ✅ Add free functions to match test expectations
+pub fn encode(key: &str, plaintext: &str) -> Option<String> {
+    encode_with(&Alphabet::lowercase(), key, plaintext)
+}
+
+pub fn decode(key: &str, ciphertext: &str) -> Option<String> {
+    decode_with(&Alphabet::lowercase(), key, ciphertext)
+}
+
+// `encode` over a custom alphabet; `None` unless the key and plaintext are both drawn from it
+pub fn encode_with(alphabet: &Alphabet, key: &str, plaintext: &str) -> Option<String> {
+    if Cipher::is_valid_key(key, alphabet) && alphabet.accepts(plaintext) {
+        let cipher = Cipher::with_alphabet(Some(key), alphabet.clone());
+        String::from_utf8(cipher.encode_bytes(plaintext.as_bytes())).ok()
+    } else {
+        None
+    }
+}
+
+pub fn decode_with(alphabet: &Alphabet, key: &str, ciphertext: &str) -> Option<String> {
+    if Cipher::is_valid_key(key, alphabet) && alphabet.accepts(ciphertext) {
+        let cipher = Cipher::with_alphabet(Some(key), alphabet.clone());
+        String::from_utf8(cipher.decode_bytes(ciphertext.as_bytes())).ok()
+    } else {
+        None
+    }
+}
+
+pub fn encode_random(plaintext: &str) -> (String, String) {
+    let cipher = Cipher::new(None);
+    let encoded = cipher.encode(plaintext);
+    (cipher.key().to_string(), encoded)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Long enough to span several blocks, with a key that doesn't divide the block size
+    fn plaintext() -> String {
+        "thequickbrownfoxjumpsoverthelazydog".repeat(7)
+    }
+
+    #[test]
+    fn byte_path_matches_char_path() {
+        let cipher = Cipher::new(Some("lemonade"));
+        let plaintext = plaintext();
+        let encoded = cipher.encode(&plaintext);
+        assert_eq!(cipher.encode_bytes(plaintext.as_bytes()), encoded.as_bytes());
+        assert_eq!(cipher.decode_bytes(encoded.as_bytes()), plaintext.as_bytes());
+        assert_eq!(cipher.decode(&encoded), plaintext);
+    }
+
+    #[test]
+    fn byte_path_wraps_around_z() {
+        let cipher = Cipher::new(Some("b"));
+        assert_eq!(cipher.encode_bytes(b"xyz"), b"yza");
+        assert_eq!(cipher.decode_bytes(b"yza"), b"xyz");
+    }
+
+    #[test]
+    fn free_functions_reject_invalid_keys() {
+        assert_eq!(encode("abc", "hello").as_deref(), Some("hfnlp"));
+        assert_eq!(decode("abc", "hfnlp").as_deref(), Some("hello"));
+        assert_eq!(encode("", "hello"), None);
+        assert_eq!(encode("ABC", "hello"), None);
+        assert_eq!(decode("abc", "Hello"), None);
+    }
+
+    #[test]
+    fn invalid_key_is_replaced_with_a_random_one() {
+        let cipher = Cipher::new(Some("Not A Key"));
+        assert_eq!(cipher.key().len(), 100);
+        assert!(Alphabet::lowercase().accepts(cipher.key()));
+    }
+}
@@ -0,0 +1,676 @@
+use anchor_lang::solana_program::{
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+    system_program, sysvar,
+};
+use anchor_lang::{AccountDeserialize, Discriminator, InstructionData, ToAccountMetas};
+use anchor_spl::associated_token::get_associated_token_address;
+use solana_client::rpc_filter::{Memcmp, RpcFilterType};
+
+use ::betting as program;
+
+pub use program::{Bet, BetPool, BetReceipt, BetStatus, ID as PROGRAM_ID};
+
+// `nonce` is the user's `bet_count` when the bet was placed
+pub fn bet_receipt_address(user: &Pubkey, nonce: u64) -> Pubkey {
+    Pubkey::find_program_address(&[b"bet", user.as_ref(), &nonce.to_le_bytes()], &program::ID).0
+}
+
+// `getProgramAccounts` filters selecting bet receipts, narrowed by whichever fields are given.
+// Only current-version receipts match; run `migrate_account` on older ones first
+pub fn bet_receipt_filters(
+    owner: Option<Pubkey>,
+    pool: Option<Pubkey>,
+    status: Option<BetStatus>,
+) -> Vec<RpcFilterType> {
+    let mut filters = vec![
+        RpcFilterType::DataSize((8 + BetReceipt::LEN) as u64),
+        RpcFilterType::Memcmp(Memcmp::new_raw_bytes(0, BetReceipt::DISCRIMINATOR.to_vec())),
+    ];
+    if let Some(owner) = owner {
+        filters.push(RpcFilterType::Memcmp(Memcmp::new_raw_bytes(
+            program::RECEIPT_OWNER_OFFSET,
+            owner.to_bytes().to_vec(),
+        )));
+    }
+    if let Some(pool) = pool {
+        filters.push(RpcFilterType::Memcmp(Memcmp::new_raw_bytes(
+            program::RECEIPT_POOL_OFFSET,
+            pool.to_bytes().to_vec(),
+        )));
+    }
+    if let Some(status) = status {
+        filters.push(RpcFilterType::Memcmp(Memcmp::new_raw_bytes(
+            program::RECEIPT_STATUS_OFFSET,
+            vec![status as u8],
+        )));
+    }
+    filters
+}
+
+// `user_profile` is a fresh keypair and must sign alongside `user`
+pub fn create_user_profile(user_profile: Pubkey, user: Pubkey) -> Instruction {
+    Instruction {
+        program_id: program::ID,
+        accounts: program::accounts::CreateUserProfile {
+            user_profile,
+            user,
+            system_program: system_program::ID,
+        }
+        .to_account_metas(None),
+        data: program::instruction::CreateUserProfile {}.data(),
+    }
+}
+
+pub fn update_betting_history(user: Pubkey, user_profile: Pubkey, bet: Bet) -> Instruction {
+    Instruction {
+        program_id: program::ID,
+        accounts: program::accounts::UpdateBettingHistory { user, user_profile }.to_account_metas(None),
+        data: program::instruction::UpdateBettingHistory { bet }.data(),
+    }
+}
+
+// `bet_pool` is a fresh keypair and must sign alongside `admin`
+pub fn create_betting_pool(bet_pool: Pubkey, admin: Pubkey, outcome: String) -> Instruction {
+    Instruction {
+        program_id: program::ID,
+        accounts: program::accounts::CreateBettingPool {
+            bet_pool,
+            admin,
+            system_program: system_program::ID,
+        }
+        .to_account_metas(None),
+        data: program::instruction::CreateBettingPool { outcome }.data(),
+    }
+}
+
+// `bet_count` is the user profile's current `bet_count`, used as the receipt nonce.
+// Pools backed by a bankroll need it passed, with its vault as `bet_pool_token_account`;
+// only those accept an `affiliate` (see `affiliate_address`). Priced pools need their `price_feed`.
+// `min_odds_bps` is the lowest odds the user accepts, usually the pool's odds when they were quoted
+#[allow(clippy::too_many_arguments)]
+pub fn place_bet(
+    user: Pubkey,
+    user_profile: Pubkey,
+    bet_pool: Pubkey,
+    user_token_account: Pubkey,
+    bet_pool_token_account: Pubkey,
+    bankroll: Option<Pubkey>,
+    affiliate: Option<Pubkey>,
+    price_feed: Option<Pubkey>,
+    bet_count: u64,
+    amount: u64,
+    min_odds_bps: u64,
+) -> Instruction {
+    Instruction {
+        program_id: program::ID,
+        accounts: program::accounts::PlaceBet {
+            user,
+            user_profile,
+            bet_pool,
+            bet_receipt: bet_receipt_address(&user, bet_count),
+            user_token_account,
+            bet_pool_token_account,
+            bankroll,
+            affiliate,
+            affiliate_vault: affiliate.as_ref().map(affiliate_vault_address),
+            price_feed,
+            pause_registry: crate::pause_registry::registry_address(),
+            token_program: anchor_spl::token::ID,
+            system_program: system_program::ID,
+        }
+        .to_account_metas(None),
+        data: program::instruction::PlaceBet { amount, min_odds_bps }.data(),
+    }
+}
+
+pub fn decode_bet_pool(data: &[u8]) -> Option<BetPool> {
+    BetPool::try_deserialize(&mut &data[..]).ok()
+}
+
+// Associated token accounts for `mint` of the pool's bets on `winning_outcome`, in bet order,
+// as `resolve_bets` expects its `winners`
+pub fn winner_token_accounts(pool: &BetPool, winning_outcome: &str, mint: &Pubkey) -> Vec<Pubkey> {
+    pool.bets
+        .iter()
+        .filter(|bet| bet.outcome == winning_outcome)
+        .map(|bet| get_associated_token_address(&bet.user_id, mint))
+        .collect()
+}
+
+// `winners` are paid, one token account per winning bet (see `winner_token_accounts`).
+// `receipts` are marked settled; find them with `bet_receipt_filters(None, Some(bet_pool), Some(BetStatus::Open))`
+#[allow(clippy::too_many_arguments)]
+pub fn resolve_bets(
+    admin: Pubkey,
+    bet_pool: Pubkey,
+    bet_pool_token_account: Pubkey,
+    bankroll: Option<Pubkey>,
+    price_feed: Option<Pubkey>,
+    winners: &[Pubkey],
+    receipts: &[Pubkey],
+    winning_outcome: String,
+) -> Instruction {
+    let mut accounts = program::accounts::ResolveBets {
+        admin,
+        settle: settle_pool_accounts(bet_pool, bet_pool_token_account, bankroll, price_feed),
+        poker_deal: poker_deal_address(&bet_pool),
+    }
+    .to_account_metas(None);
+    accounts.extend(winners.iter().map(|&winner| AccountMeta::new(winner, false)));
+    accounts.extend(receipts.iter().map(|&receipt| AccountMeta::new(receipt, false)));
+
+    Instruction {
+        program_id: program::ID,
+        accounts,
+        data: program::instruction::ResolveBets { winning_outcome }.data(),
+    }
+}
+
+// `admin` co-signs for unbacked pools, whose winnings sit in its token account; `bankroll` is
+// required for backed ones
+pub fn claim_winnings(
+    user: Pubkey,
+    bet_pool: Pubkey,
+    bet_pool_token_account: Pubkey,
+    bankroll: Option<Pubkey>,
+    admin: Option<Pubkey>,
+    user_token_account: Pubkey,
+) -> Instruction {
+    Instruction {
+        program_id: program::ID,
+        accounts: program::accounts::ClaimWinnings {
+            user,
+            bet_pool,
+            bet_pool_token_account,
+            bankroll,
+            admin,
+            user_token_account,
+            token_program: anchor_spl::token::ID,
+        }
+        .to_account_metas(None),
+        data: program::instruction::ClaimWinnings {}.data(),
+    }
+}
+
+pub fn poker_deal_address(bet_pool: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[program::POKER_DEAL_SEED, bet_pool.as_ref()], &program::ID).0
+}
+
+// One commitment per seat, in the order of the market's outcome labels (see `poker::shuffle::commit`)
+pub fn commit_poker_deal(admin: Pubkey, bet_pool: Pubkey, commitments: Vec<[u8; 32]>) -> Instruction {
+    Instruction {
+        program_id: program::ID,
+        accounts: program::accounts::CommitPokerDeal {
+            admin,
+            bet_pool,
+            metadata: market_metadata_address(&bet_pool),
+            poker_deal: poker_deal_address(&bet_pool),
+            system_program: system_program::ID,
+        }
+        .to_account_metas(None),
+        data: program::instruction::CommitPokerDeal { commitments }.data(),
+    }
+}
+
+fn settle_pool_accounts(
+    bet_pool: Pubkey,
+    bet_pool_token_account: Pubkey,
+    bankroll: Option<Pubkey>,
+    price_feed: Option<Pubkey>,
+) -> program::accounts::SettlePool {
+    program::accounts::SettlePool {
+        bet_pool,
+        bet_pool_token_account,
+        bankroll,
+        price_feed,
+        pause_registry: crate::pause_registry::registry_address(),
+        token_program: anchor_spl::token::ID,
+    }
+}
+
+// Needs no signer. Poker pools are backed, so `vault` is their bankroll's. The winner comes from
+// the deal `secrets` produce, so `winners` must be the token accounts of the bets on the seat
+// that deal makes win
+pub fn resolve_poker_market(
+    bet_pool: Pubkey,
+    bankroll: Pubkey,
+    vault: Pubkey,
+    price_feed: Option<Pubkey>,
+    winners: &[Pubkey],
+    receipts: &[Pubkey],
+    secrets: Vec<[u8; 32]>,
+) -> Instruction {
+    let mut accounts = program::accounts::ResolvePokerMarket {
+        settle: settle_pool_accounts(bet_pool, vault, Some(bankroll), price_feed),
+        metadata: market_metadata_address(&bet_pool),
+        poker_deal: poker_deal_address(&bet_pool),
+    }
+    .to_account_metas(None);
+    accounts.extend(winners.iter().map(|&winner| AccountMeta::new(winner, false)));
+    accounts.extend(receipts.iter().map(|&receipt| AccountMeta::new(receipt, false)));
+
+    Instruction {
+        program_id: program::ID,
+        accounts,
+        data: program::instruction::ResolvePokerMarket { secrets }.data(),
+    }
+}
+
+// Permissionless; `payer` covers rent if the new layout is larger
+pub fn migrate_account(target: Pubkey, payer: Pubkey) -> Instruction {
+    Instruction {
+        program_id: program::ID,
+        accounts: program::accounts::MigrateAccount {
+            target,
+            payer,
+            system_program: system_program::ID,
+        }
+        .to_account_metas(None),
+        data: program::instruction::MigrateAccount {}.data(),
+    }
+}
+
+// Only the pool's admin (its maker) may change odds
+pub fn update_odds(admin: Pubkey, bet_pool: Pubkey, odds_bps: u64) -> Instruction {
+    Instruction {
+        program_id: program::ID,
+        accounts: program::accounts::UpdateOdds { admin, bet_pool }.to_account_metas(None),
+        data: program::instruction::UpdateOdds { odds_bps }.data(),
+    }
+}
+
+// `max_daily_wager` of 0 removes the cap; loosening only takes effect after the program's cooldown
+pub fn set_limits(
+    user: Pubkey,
+    user_profile: Pubkey,
+    max_daily_wager: u64,
+    self_excluded_until: i64,
+) -> Instruction {
+    Instruction {
+        program_id: program::ID,
+        accounts: program::accounts::SetLimits { user, user_profile }.to_account_metas(None),
+        data: program::instruction::SetLimits { max_daily_wager, self_excluded_until }.data(),
+    }
+}
+
+pub fn bankroll_address(mint: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[program::BANKROLL_SEED, mint.as_ref()], &program::ID).0
+}
+
+pub fn bankroll_vault_address(bankroll: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[b"bankroll_vault", bankroll.as_ref()], &program::ID).0
+}
+
+pub fn bankroll_share_mint_address(bankroll: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[b"bankroll_shares", bankroll.as_ref()], &program::ID).0
+}
+
+pub fn create_bankroll(admin: Pubkey, mint: Pubkey, max_utilization_bps: u64) -> Instruction {
+    let bankroll = bankroll_address(&mint);
+    Instruction {
+        program_id: program::ID,
+        accounts: program::accounts::CreateBankroll {
+            bankroll,
+            mint,
+            vault: bankroll_vault_address(&bankroll),
+            share_mint: bankroll_share_mint_address(&bankroll),
+            admin,
+            token_program: anchor_spl::token::ID,
+            system_program: system_program::ID,
+            rent: sysvar::rent::ID,
+        }
+        .to_account_metas(None),
+        data: program::instruction::CreateBankroll { max_utilization_bps }.data(),
+    }
+}
+
+fn liquidity_accounts(
+    provider: Pubkey,
+    mint: Pubkey,
+    provider_token_account: Pubkey,
+    provider_share_account: Pubkey,
+) -> Vec<AccountMeta> {
+    let bankroll = bankroll_address(&mint);
+    program::accounts::Liquidity {
+        provider,
+        bankroll,
+        vault: bankroll_vault_address(&bankroll),
+        share_mint: bankroll_share_mint_address(&bankroll),
+        provider_token_account,
+        provider_share_account,
+        pause_registry: crate::pause_registry::registry_address(),
+        token_program: anchor_spl::token::ID,
+    }
+    .to_account_metas(None)
+}
+
+// `provider_share_account` holds the bankroll's share mint (see `bankroll_share_mint_address`)
+pub fn deposit_liquidity(
+    provider: Pubkey,
+    mint: Pubkey,
+    provider_token_account: Pubkey,
+    provider_share_account: Pubkey,
+    amount: u64,
+) -> Instruction {
+    Instruction {
+        program_id: program::ID,
+        accounts: liquidity_accounts(provider, mint, provider_token_account, provider_share_account),
+        data: program::instruction::DepositLiquidity { amount }.data(),
+    }
+}
+
+pub fn withdraw_liquidity(
+    provider: Pubkey,
+    mint: Pubkey,
+    provider_token_account: Pubkey,
+    provider_share_account: Pubkey,
+    shares: u64,
+) -> Instruction {
+    Instruction {
+        program_id: program::ID,
+        accounts: liquidity_accounts(provider, mint, provider_token_account, provider_share_account),
+        data: program::instruction::WithdrawLiquidity { shares }.data(),
+    }
+}
+
+// The pool must have no open bets, and `admin` must administer both the pool and the bankroll
+pub fn link_bankroll(admin: Pubkey, bet_pool: Pubkey, bankroll: Pubkey) -> Instruction {
+    Instruction {
+        program_id: program::ID,
+        accounts: program::accounts::LinkBankroll { admin, bet_pool, bankroll }.to_account_metas(None),
+        data: program::instruction::LinkBankroll {}.data(),
+    }
+}
+
+pub fn affiliate_address(bankroll: &Pubkey, wallet: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[program::AFFILIATE_SEED, bankroll.as_ref(), wallet.as_ref()],
+        &program::ID,
+    )
+    .0
+}
+
+pub fn affiliate_vault_address(affiliate: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[b"affiliate_vault", affiliate.as_ref()], &program::ID).0
+}
+
+pub fn register_affiliate(
+    admin: Pubkey,
+    bankroll: Pubkey,
+    mint: Pubkey,
+    wallet: Pubkey,
+    commission_bps: u64,
+) -> Instruction {
+    let affiliate = affiliate_address(&bankroll, &wallet);
+    Instruction {
+        program_id: program::ID,
+        accounts: program::accounts::RegisterAffiliate {
+            admin,
+            bankroll,
+            wallet,
+            affiliate,
+            mint,
+            vault: affiliate_vault_address(&affiliate),
+            token_program: anchor_spl::token::ID,
+            system_program: system_program::ID,
+            rent: sysvar::rent::ID,
+        }
+        .to_account_metas(None),
+        data: program::instruction::RegisterAffiliate { commission_bps }.data(),
+    }
+}
+
+pub fn set_commission(admin: Pubkey, bankroll: Pubkey, wallet: Pubkey, commission_bps: u64) -> Instruction {
+    Instruction {
+        program_id: program::ID,
+        accounts: program::accounts::SetCommission {
+            admin,
+            bankroll,
+            affiliate: affiliate_address(&bankroll, &wallet),
+        }
+        .to_account_metas(None),
+        data: program::instruction::SetCommission { commission_bps }.data(),
+    }
+}
+
+pub fn claim_affiliate_fees(bankroll: Pubkey, wallet: Pubkey, wallet_token_account: Pubkey) -> Instruction {
+    let affiliate = affiliate_address(&bankroll, &wallet);
+    Instruction {
+        program_id: program::ID,
+        accounts: program::accounts::ClaimAffiliateFees {
+            wallet,
+            affiliate,
+            vault: affiliate_vault_address(&affiliate),
+            wallet_token_account,
+            token_program: anchor_spl::token::ID,
+        }
+        .to_account_metas(None),
+        data: program::instruction::ClaimAffiliateFees {}.data(),
+    }
+}
+
+pub fn maker_address(bet_pool: &Pubkey, wallet: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[program::MAKER_SEED, bet_pool.as_ref(), wallet.as_ref()], &program::ID).0
+}
+
+pub fn maker_vault_address(maker: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[b"maker_vault", maker.as_ref()], &program::ID).0
+}
+
+// `mint` is the pool's token
+// `bankroll` backs the pool and `mint` is its token
+pub fn register_maker(
+    admin: Pubkey,
+    bet_pool: Pubkey,
+    bankroll: Pubkey,
+    wallet: Pubkey,
+    mint: Pubkey,
+) -> Instruction {
+    let maker = maker_address(&bet_pool, &wallet);
+    Instruction {
+        program_id: program::ID,
+        accounts: program::accounts::RegisterMaker {
+            admin,
+            bet_pool,
+            bankroll,
+            wallet,
+            maker,
+            mint,
+            vault: maker_vault_address(&maker),
+            token_program: anchor_spl::token::ID,
+            system_program: system_program::ID,
+            rent: sysvar::rent::ID,
+        }
+        .to_account_metas(None),
+        data: program::instruction::RegisterMaker {}.data(),
+    }
+}
+
+// Collateral moves between `wallet_token_account` and the maker's vault until `size` is free
+pub fn provide_quotes(
+    bet_pool: Pubkey,
+    wallet: Pubkey,
+    wallet_token_account: Pubkey,
+    outcome_odds: Vec<u64>,
+    size: u64,
+) -> Instruction {
+    let maker = maker_address(&bet_pool, &wallet);
+    Instruction {
+        program_id: program::ID,
+        accounts: program::accounts::ProvideQuotes {
+            wallet,
+            maker,
+            vault: maker_vault_address(&maker),
+            wallet_token_account,
+            token_program: anchor_spl::token::ID,
+        }
+        .to_account_metas(None),
+        data: program::instruction::ProvideQuotes { outcome_odds, size }.data(),
+    }
+}
+
+// `maker` is the maker's account (see `maker_address`); a win pays out to `user_token_account`.
+// Fails if the maker's quote for `outcome` is below `min_odds_bps` when it lands
+#[allow(clippy::too_many_arguments)]
+pub fn take_quote(
+    user: Pubkey,
+    user_profile: Pubkey,
+    bet_pool: Pubkey,
+    maker: Pubkey,
+    user_token_account: Pubkey,
+    outcome: u8,
+    amount: u64,
+    min_odds_bps: u64,
+) -> Instruction {
+    Instruction {
+        program_id: program::ID,
+        accounts: program::accounts::TakeQuote {
+            user,
+            user_profile,
+            bet_pool,
+            maker,
+            vault: maker_vault_address(&maker),
+            user_token_account,
+            pause_registry: crate::pause_registry::registry_address(),
+            token_program: anchor_spl::token::ID,
+        }
+        .to_account_metas(None),
+        data: program::instruction::TakeQuote { outcome, amount, min_odds_bps }.data(),
+    }
+}
+
+// `winning_outcome` indexes the market's labels
+pub fn resolve_makers(admin: Pubkey, bet_pool: Pubkey, winning_outcome: u8) -> Instruction {
+    Instruction {
+        program_id: program::ID,
+        accounts: program::accounts::ResolveMakers {
+            admin,
+            bet_pool,
+            metadata: market_metadata_address(&bet_pool),
+            poker_deal: poker_deal_address(&bet_pool),
+            pause_registry: crate::pause_registry::registry_address(),
+        }
+        .to_account_metas(None),
+        data: program::instruction::ResolveMakers { winning_outcome }.data(),
+    }
+}
+
+// `winners` are the token accounts of the maker's positions on the resolved outcome, in
+// position order
+pub fn settle_maker(admin: Pubkey, bet_pool: Pubkey, maker: Pubkey, winners: &[Pubkey]) -> Instruction {
+    let mut accounts = program::accounts::SettleMaker {
+        admin,
+        bet_pool,
+        metadata: market_metadata_address(&bet_pool),
+        maker,
+        vault: maker_vault_address(&maker),
+        pause_registry: crate::pause_registry::registry_address(),
+        token_program: anchor_spl::token::ID,
+    }
+    .to_account_metas(None);
+    accounts.extend(winners.iter().map(|&winner| AccountMeta::new(winner, false)));
+
+    Instruction {
+        program_id: program::ID,
+        accounts,
+        data: program::instruction::SettleMaker {}.data(),
+    }
+}
+
+// `token_accounts` are every open position's, in position order
+pub fn void_maker(bet_pool: Pubkey, maker: Pubkey, token_accounts: &[Pubkey]) -> Instruction {
+    let mut accounts = program::accounts::VoidMaker {
+        bet_pool,
+        maker,
+        vault: maker_vault_address(&maker),
+        token_program: anchor_spl::token::ID,
+    }
+    .to_account_metas(None);
+    accounts.extend(token_accounts.iter().map(|&account| AccountMeta::new(account, false)));
+
+    Instruction {
+        program_id: program::ID,
+        accounts,
+        data: program::instruction::VoidMaker {}.data(),
+    }
+}
+
+pub fn market_metadata_address(bet_pool: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[program::MARKET_METADATA_SEED, bet_pool.as_ref()], &program::ID).0
+}
+
+pub fn set_market_metadata(
+    admin: Pubkey,
+    bet_pool: Pubkey,
+    outcome_labels: Vec<String>,
+    description: String,
+    tags: Vec<String>,
+) -> Instruction {
+    Instruction {
+        program_id: program::ID,
+        accounts: program::accounts::SetMarketMetadata {
+            admin,
+            bet_pool,
+            metadata: market_metadata_address(&bet_pool),
+            system_program: system_program::ID,
+        }
+        .to_account_metas(None),
+        data: program::instruction::SetMarketMetadata { outcome_labels, description, tags }.data(),
+    }
+}
+
+pub fn set_market_rules(
+    admin: Pubkey,
+    bet_pool: Pubkey,
+    max_payout_per_user: u64,
+    resolution_deadline: i64,
+) -> Instruction {
+    Instruction {
+        program_id: program::ID,
+        accounts: program::accounts::SetMarketRules { admin, bet_pool }.to_account_metas(None),
+        data: program::instruction::SetMarketRules { max_payout_per_user, resolution_deadline }.data(),
+    }
+}
+
+// `bankroll` backs the pool and `mint` is its token; both are only checked when setting a feed.
+// Pass no `price_feed` to stop valuing bets in USD
+pub fn set_price_feed(
+    admin: Pubkey,
+    bet_pool: Pubkey,
+    price_feed: Option<Pubkey>,
+    bankroll: Option<Pubkey>,
+    mint: Pubkey,
+) -> Instruction {
+    Instruction {
+        program_id: program::ID,
+        accounts: program::accounts::SetPriceFeed { admin, bet_pool, price_feed, bankroll, mint }
+            .to_account_metas(None),
+        data: program::instruction::SetPriceFeed {}.data(),
+    }
+}
+
+// `bankroll` is required for backed pools; the stake is refunded to `user_token_account`
+pub fn void_bet(
+    user: Pubkey,
+    bet_pool: Pubkey,
+    bet_receipt: Pubkey,
+    bankroll: Option<Pubkey>,
+    user_token_account: Option<Pubkey>,
+) -> Instruction {
+    Instruction {
+        program_id: program::ID,
+        accounts: program::accounts::VoidBet {
+            user,
+            bet_pool,
+            bet_receipt,
+            bankroll,
+            vault: bankroll.as_ref().map(bankroll_vault_address),
+            user_token_account,
+            token_program: anchor_spl::token::ID,
+        }
+        .to_account_metas(None),
+        data: program::instruction::VoidBet {}.data(),
+    }
+}
+
@@ -0,0 +1,151 @@
+use anchor_lang::solana_program::{instruction::Instruction, pubkey::Pubkey, system_program, sysvar};
+use anchor_lang::{InstructionData, ToAccountMetas};
+use anchor_spl::associated_token::get_associated_token_address;
+
+use ::vesting as program;
+use program::claims::{hash_pair, leaf};
+
+use crate::vesting::{authority_address, state_address};
+
+pub fn distribution_address(id: u64) -> Pubkey {
+    Pubkey::find_program_address(&[b"distribution", &id.to_le_bytes()], &program::ID).0
+}
+
+pub fn vault_address(distribution: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[b"distribution_vault", distribution.as_ref()], &program::ID).0
+}
+
+// Merkle tree over (wallet, amount) allocations, hashed exactly like `claim` verifies them.
+// Claim index `i` is the allocation's position in the input.
+pub struct MerkleTree {
+    // levels[0] are the leaves, the last level is the root
+    levels: Vec<Vec<[u8; 32]>>,
+}
+
+impl MerkleTree {
+    pub fn new(allocations: &[(Pubkey, u64)]) -> Self {
+        let leaves: Vec<[u8; 32]> = allocations
+            .iter()
+            .enumerate()
+            .map(|(i, (wallet, amount))| leaf(i as u32, wallet, *amount))
+            .collect();
+
+        let mut levels = vec![leaves];
+        while levels.last().unwrap().len() > 1 {
+            // An odd node out is promoted unchanged
+            let next = levels
+                .last()
+                .unwrap()
+                .chunks(2)
+                .map(|pair| match pair {
+                    [a, b] => hash_pair(a, b),
+                    [a] => *a,
+                    _ => unreachable!(),
+                })
+                .collect();
+            levels.push(next);
+        }
+
+        MerkleTree { levels }
+    }
+
+    pub fn len(&self) -> usize {
+        self.levels[0].len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.levels[0].is_empty()
+    }
+
+    pub fn root(&self) -> [u8; 32] {
+        self.levels.last().and_then(|level| level.first().copied()).unwrap_or_default()
+    }
+
+    pub fn proof(&self, index: usize) -> Vec<[u8; 32]> {
+        let mut proof = Vec::new();
+        let mut i = index;
+        for level in &self.levels[..self.levels.len() - 1] {
+            if let Some(sibling) = level.get(i ^ 1) {
+                proof.push(*sibling);
+            }
+            i /= 2;
+        }
+        proof
+    }
+}
+
+pub fn create_distribution(
+    admin: Pubkey,
+    mint: Pubkey,
+    treasury: Pubkey,
+    id: u64,
+    tree: &MerkleTree,
+    total_amount: u64,
+    expires_at: i64,
+) -> Instruction {
+    let distribution = distribution_address(id);
+    Instruction {
+        program_id: program::ID,
+        accounts: program::accounts::CreateDistribution {
+            state: state_address(),
+            distribution,
+            vault: vault_address(&distribution),
+            treasury,
+            mint,
+            authority: authority_address(),
+            admin,
+            token_program: anchor_spl::token::ID,
+            system_program: system_program::ID,
+            rent: sysvar::rent::ID,
+        }
+        .to_account_metas(None),
+        data: program::instruction::CreateDistribution {
+            id,
+            merkle_root: tree.root(),
+            total_amount,
+            num_claims: tree.len() as u32,
+            expires_at,
+        }
+        .data(),
+    }
+}
+
+pub fn claim(mint: Pubkey, claimant: Pubkey, id: u64, index: u32, amount: u64, proof: Vec<[u8; 32]>) -> Instruction {
+    let distribution = distribution_address(id);
+    Instruction {
+        program_id: program::ID,
+        accounts: program::accounts::Claim {
+            state: state_address(),
+            distribution,
+            vault: vault_address(&distribution),
+            claimant,
+            claimant_token_account: get_associated_token_address(&claimant, &mint),
+            mint,
+            authority: authority_address(),
+            pause_registry: crate::pause_registry::registry_address(),
+            token_program: anchor_spl::token::ID,
+            associated_token_program: anchor_spl::associated_token::ID,
+            system_program: system_program::ID,
+        }
+        .to_account_metas(None),
+        data: program::instruction::Claim { index, amount, proof }.data(),
+    }
+}
+
+pub fn close_distribution(admin: Pubkey, treasury: Pubkey, id: u64) -> Instruction {
+    let distribution = distribution_address(id);
+    Instruction {
+        program_id: program::ID,
+        accounts: program::accounts::CloseDistribution {
+            state: state_address(),
+            distribution,
+            vault: vault_address(&distribution),
+            treasury,
+            authority: authority_address(),
+            admin,
+            token_program: anchor_spl::token::ID,
+        }
+        .to_account_metas(None),
+        data: program::instruction::CloseDistribution {}.data(),
+    }
+}
@@ -0,0 +1,42 @@
+use errors::CommonError;
+
+// Every custom error code any of the programs can return, resolved from the code alone
+#[derive(Debug, Clone, Copy)]
+pub enum ErrorKind {
+    Common(CommonError),
+    Vesting(::vesting::ErrorCode),
+    Betting(::betting::BettingError),
+}
+
+impl ErrorKind {
+    pub fn from_code(code: u32) -> Option<Self> {
+        if let Some(e) = CommonError::from_code(code) {
+            return Some(ErrorKind::Common(e));
+        }
+        if let Some(e) = ::vesting::ErrorCode::ALL.into_iter().find(|&e| u32::from(e) == code) {
+            return Some(ErrorKind::Vesting(e));
+        }
+        ::betting::BettingError::ALL
+            .into_iter()
+            .find(|&e| u32::from(e) == code)
+            .map(ErrorKind::Betting)
+    }
+
+    pub fn code(self) -> u32 {
+        match self {
+            ErrorKind::Common(e) => e.into(),
+            ErrorKind::Vesting(e) => e.into(),
+            ErrorKind::Betting(e) => e.into(),
+        }
+    }
+}
+
+impl std::fmt::Display for ErrorKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ErrorKind::Common(e) => write!(f, "{}", e),
+            ErrorKind::Vesting(e) => write!(f, "{}", e),
+            ErrorKind::Betting(e) => write!(f, "{}", e),
+        }
+    }
+}
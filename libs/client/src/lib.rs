@@ -0,0 +1,7 @@
+// Instruction builders for the on-chain programs, so integrators don't hand-assemble
+// account lists or Anchor instruction data.
+pub mod betting;
+pub mod claims;
+pub mod errors;
+pub mod pause_registry;
+pub mod vesting;
@@ -0,0 +1,73 @@
+use anchor_lang::solana_program::{
+    bpf_loader_upgradeable, instruction::Instruction, pubkey::Pubkey, system_program,
+};
+use anchor_lang::{InstructionData, ToAccountMetas};
+
+use ::pause_registry as program;
+
+pub use program::{BETTING, ID as PROGRAM_ID, STAKING, VESTING, VOTING};
+
+pub fn registry_address() -> Pubkey {
+    Pubkey::find_program_address(&[program::REGISTRY_SEED], &program::ID).0
+}
+
+fn program_data_address() -> Pubkey {
+    Pubkey::find_program_address(&[program::ID.as_ref()], &bpf_loader_upgradeable::ID).0
+}
+
+// `guardian` must be the program's upgrade authority
+pub fn initialize(guardian: Pubkey) -> Instruction {
+    Instruction {
+        program_id: program::ID,
+        accounts: program::accounts::Initialize {
+            registry: registry_address(),
+            guardian,
+            program: program::ID,
+            program_data: program_data_address(),
+            system_program: system_program::ID,
+        }
+        .to_account_metas(None),
+        data: program::instruction::Initialize {}.data(),
+    }
+}
+
+pub fn set_global_pause(guardian: Pubkey, paused: bool) -> Instruction {
+    Instruction {
+        program_id: program::ID,
+        accounts: guardian_accounts(guardian),
+        data: program::instruction::SetGlobalPause { paused }.data(),
+    }
+}
+
+// `programs` is a mask of the program bits (`VESTING | BETTING`, ...)
+pub fn set_programs_paused(guardian: Pubkey, programs: u32, paused: bool) -> Instruction {
+    Instruction {
+        program_id: program::ID,
+        accounts: guardian_accounts(guardian),
+        data: program::instruction::SetProgramsPaused { programs, paused }.data(),
+    }
+}
+
+pub fn set_programs_exempt(guardian: Pubkey, programs: u32, exempt: bool) -> Instruction {
+    Instruction {
+        program_id: program::ID,
+        accounts: guardian_accounts(guardian),
+        data: program::instruction::SetProgramsExempt { programs, exempt }.data(),
+    }
+}
+
+pub fn set_guardian(guardian: Pubkey, new_guardian: Pubkey) -> Instruction {
+    Instruction {
+        program_id: program::ID,
+        accounts: guardian_accounts(guardian),
+        data: program::instruction::SetGuardian { new_guardian }.data(),
+    }
+}
+
+fn guardian_accounts(guardian: Pubkey) -> Vec<anchor_lang::solana_program::instruction::AccountMeta> {
+    program::accounts::GuardianAction {
+        registry: registry_address(),
+        guardian,
+    }
+    .to_account_metas(None)
+}
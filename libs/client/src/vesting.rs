@@ -0,0 +1,331 @@
+use anchor_lang::solana_program::{instruction::Instruction, pubkey::Pubkey, system_program, sysvar};
+use anchor_lang::{InstructionData, ToAccountMetas};
+use anchor_spl::associated_token::get_associated_token_address;
+
+use ::vesting as program;
+
+pub use program::{UserType, ID as PROGRAM_ID};
+
+pub fn state_address() -> Pubkey {
+    Pubkey::find_program_address(&[b"state"], &program::ID).0
+}
+
+pub fn authority_address() -> Pubkey {
+    Pubkey::find_program_address(&[b"authority"], &program::ID).0
+}
+
+pub fn beneficiary_address(user: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[b"beneficiary", user.as_ref()], &program::ID).0
+}
+
+// Grant in a mint other than the program's own
+pub fn grant_address(user: &Pubkey, mint: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[b"beneficiary", user.as_ref(), mint.as_ref()], &program::ID).0
+}
+
+// Treasury registered by `add_vault` for `mint`
+pub fn treasury_vault_address(mint: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[b"vault", mint.as_ref()], &program::ID).0
+}
+
+// `mint` and `treasury` are fresh keypairs and must sign alongside `payer`
+pub fn initialize(
+    payer: Pubkey,
+    mint: Pubkey,
+    treasury: Pubkey,
+    total_supply: u64,
+    cliff_duration: i64,
+    vesting_duration: i64,
+) -> Instruction {
+    Instruction {
+        program_id: program::ID,
+        accounts: program::accounts::Initialize {
+            state: state_address(),
+            mint,
+            treasury,
+            authority: authority_address(),
+            payer,
+            system_program: system_program::ID,
+            token_program: anchor_spl::token::ID,
+            rent: sysvar::rent::ID,
+        }
+        .to_account_metas(None),
+        data: program::instruction::Initialize {
+            total_supply,
+            cliff_duration,
+            vesting_duration,
+        }
+        .data(),
+    }
+}
+
+pub fn add_beneficiary(
+    admin: Pubkey,
+    payer: Pubkey,
+    user: Pubkey,
+    treasury: Pubkey,
+    allocation: u64,
+    user_type: UserType,
+    min_claim_interval: Option<i64>,
+) -> Instruction {
+    Instruction {
+        program_id: program::ID,
+        accounts: program::accounts::AddBeneficiary {
+            state: state_address(),
+            admin,
+            beneficiary: beneficiary_address(&user),
+            user,
+            treasury,
+            payer,
+            system_program: system_program::ID,
+        }
+        .to_account_metas(None),
+        data: program::instruction::AddBeneficiary {
+            beneficiary: user,
+            allocation,
+            user_type,
+            min_claim_interval,
+        }
+        .data(),
+    }
+}
+
+// Pays out to the user's associated token account for `mint`
+pub fn release(mint: Pubkey, treasury: Pubkey, user: Pubkey) -> Instruction {
+    Instruction {
+        program_id: program::ID,
+        accounts: program::accounts::Release {
+            state: state_address(),
+            beneficiary: beneficiary_address(&user),
+            beneficiary_token_account: get_associated_token_address(&user, &mint),
+            treasury,
+            authority: authority_address(),
+            pause_registry: crate::pause_registry::registry_address(),
+            token_program: anchor_spl::token::ID,
+            clock: sysvar::clock::ID,
+        }
+        .to_account_metas(None),
+        data: program::instruction::Release {}.data(),
+    }
+}
+
+// Pays out a grant made with `add_grant` from the vault for `mint`
+pub fn release_grant(mint: Pubkey, user: Pubkey) -> Instruction {
+    Instruction {
+        program_id: program::ID,
+        accounts: program::accounts::Release {
+            state: state_address(),
+            beneficiary: grant_address(&user, &mint),
+            beneficiary_token_account: get_associated_token_address(&user, &mint),
+            treasury: treasury_vault_address(&mint),
+            authority: authority_address(),
+            pause_registry: crate::pause_registry::registry_address(),
+            token_program: anchor_spl::token::ID,
+            clock: sysvar::clock::ID,
+        }
+        .to_account_metas(None),
+        data: program::instruction::Release {}.data(),
+    }
+}
+
+// The vault starts empty; fund it with a plain token transfer
+pub fn add_vault(admin: Pubkey, mint: Pubkey) -> Instruction {
+    Instruction {
+        program_id: program::ID,
+        accounts: program::accounts::AddVault {
+            state: state_address(),
+            mint,
+            treasury: treasury_vault_address(&mint),
+            authority: authority_address(),
+            admin,
+            token_program: anchor_spl::token::ID,
+            system_program: system_program::ID,
+            rent: sysvar::rent::ID,
+        }
+        .to_account_metas(None),
+        data: program::instruction::AddVault {}.data(),
+    }
+}
+
+pub fn add_grant(
+    admin: Pubkey,
+    payer: Pubkey,
+    user: Pubkey,
+    mint: Pubkey,
+    allocation: u64,
+    user_type: UserType,
+    min_claim_interval: Option<i64>,
+) -> Instruction {
+    Instruction {
+        program_id: program::ID,
+        accounts: program::accounts::AddGrant {
+            state: state_address(),
+            admin,
+            mint,
+            treasury: treasury_vault_address(&mint),
+            grant: grant_address(&user, &mint),
+            user,
+            payer,
+            system_program: system_program::ID,
+        }
+        .to_account_metas(None),
+        data: program::instruction::AddGrant {
+            beneficiary: user,
+            allocation,
+            user_type,
+            min_claim_interval,
+        }
+        .data(),
+    }
+}
+
+// Permissionless; `treasury` is the program's treasury or a vault from `treasury_vault_address`
+pub fn verify_solvency(treasury: Pubkey) -> Instruction {
+    Instruction {
+        program_id: program::ID,
+        accounts: program::accounts::VerifySolvency { state: state_address(), treasury }.to_account_metas(None),
+        data: program::instruction::VerifySolvency {}.data(),
+    }
+}
+
+fn guardian_freeze_accounts(guardian: Pubkey) -> Vec<anchor_lang::solana_program::instruction::AccountMeta> {
+    program::accounts::GuardianFreeze {
+        state: state_address(),
+        pause_registry: crate::pause_registry::registry_address(),
+        guardian,
+    }
+    .to_account_metas(None)
+}
+
+// `guardian` is the pause registry's guardian
+pub fn freeze_releases(guardian: Pubkey) -> Instruction {
+    Instruction {
+        program_id: program::ID,
+        accounts: guardian_freeze_accounts(guardian),
+        data: program::instruction::FreezeReleases {}.data(),
+    }
+}
+
+// Starts the `UNFREEZE_DELAY` timelock; send `unfreeze_releases` once it has passed
+pub fn request_unfreeze(guardian: Pubkey) -> Instruction {
+    Instruction {
+        program_id: program::ID,
+        accounts: guardian_freeze_accounts(guardian),
+        data: program::instruction::RequestUnfreeze {}.data(),
+    }
+}
+
+pub fn unfreeze_releases(guardian: Pubkey) -> Instruction {
+    Instruction {
+        program_id: program::ID,
+        accounts: guardian_freeze_accounts(guardian),
+        data: program::instruction::UnfreezeReleases {}.data(),
+    }
+}
+
+// Works for grants in any mint; pass the grant's address as `beneficiary`
+pub fn accelerate(admin: Pubkey, beneficiary: Pubkey, bps: u64) -> Instruction {
+    Instruction {
+        program_id: program::ID,
+        accounts: program::accounts::Accelerate {
+            state: state_address(),
+            admin,
+            beneficiary,
+        }
+        .to_account_metas(None),
+        data: program::instruction::Accelerate { bps }.data(),
+    }
+}
+
+pub fn amendment_address(beneficiary: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[b"amendment", beneficiary.as_ref()], &program::ID).0
+}
+
+pub fn propose_amendment(
+    admin: Pubkey,
+    beneficiary: Pubkey,
+    allocation: u64,
+    cliff_duration: i64,
+    vesting_duration: i64,
+) -> Instruction {
+    Instruction {
+        program_id: program::ID,
+        accounts: program::accounts::ProposeAmendment {
+            state: state_address(),
+            admin,
+            beneficiary,
+            amendment: amendment_address(&beneficiary),
+            system_program: system_program::ID,
+        }
+        .to_account_metas(None),
+        data: program::instruction::ProposeAmendment { allocation, cliff_duration, vesting_duration }.data(),
+    }
+}
+
+// Signed by the grant's beneficiary; `admin` only receives the proposal's rent. `treasury` pays
+// the grant's mint and must cover a raised allocation
+pub fn accept_amendment(admin: Pubkey, user: Pubkey, beneficiary: Pubkey, treasury: Pubkey) -> Instruction {
+    Instruction {
+        program_id: program::ID,
+        accounts: program::accounts::AcceptAmendment {
+            state: state_address(),
+            admin,
+            user,
+            beneficiary,
+            amendment: amendment_address(&beneficiary),
+            treasury,
+        }
+        .to_account_metas(None),
+        data: program::instruction::AcceptAmendment {}.data(),
+    }
+}
+
+// `signer` is the admin or the grant's beneficiary
+pub fn cancel_amendment(admin: Pubkey, signer: Pubkey, beneficiary: Pubkey) -> Instruction {
+    Instruction {
+        program_id: program::ID,
+        accounts: program::accounts::CancelAmendment {
+            state: state_address(),
+            admin,
+            signer,
+            beneficiary,
+            amendment: amendment_address(&beneficiary),
+        }
+        .to_account_metas(None),
+        data: program::instruction::CancelAmendment {}.data(),
+    }
+}
+
+// Permissionless crank; call repeatedly until it fails with AccrualNotDue to catch a grant up
+pub fn record_accrual(beneficiary: Pubkey) -> Instruction {
+    Instruction {
+        program_id: program::ID,
+        accounts: program::accounts::RecordAccrual { state: state_address(), beneficiary }
+            .to_account_metas(None),
+        data: program::instruction::RecordAccrual {}.data(),
+    }
+}
+
+// `user` signs for their own grant, `beneficiary`
+pub fn request_statement(user: Pubkey, beneficiary: Pubkey) -> Instruction {
+    Instruction {
+        program_id: program::ID,
+        accounts: program::accounts::RequestStatement { state: state_address(), user, beneficiary }
+            .to_account_metas(None),
+        data: program::instruction::RequestStatement {}.data(),
+    }
+}
+
+// Permissionless; `payer` covers rent if the new layout is larger
+pub fn migrate_account(target: Pubkey, payer: Pubkey) -> Instruction {
+    Instruction {
+        program_id: program::ID,
+        accounts: program::accounts::MigrateAccount {
+            target,
+            payer,
+            system_program: system_program::ID,
+        }
+        .to_account_metas(None),
+        data: program::instruction::MigrateAccount {}.data(),
+    }
+}
@@ -0,0 +1,43 @@
+// Error taxonomy shared by the Anchor programs.
+//
+// Failures every program can hit live in `CommonError` (codes 6000..). Each program keeps its
+// own enum for domain errors in a range of its own, so a code alone identifies the error.
+use anchor_lang::prelude::*;
+
+pub const COMMON_OFFSET: u32 = 6000;
+pub const VESTING_OFFSET: u32 = 7000;
+pub const BETTING_OFFSET: u32 = 7100;
+
+#[error_code(offset = 6000)]
+pub enum CommonError {
+    #[msg("Unauthorized operation")]
+    Unauthorized,
+    #[msg("Arithmetic overflow")]
+    Overflow,
+    #[msg("Invalid duration")]
+    InvalidDuration,
+    #[msg("Token account has the wrong mint or owner")]
+    InvalidTokenAccount,
+    #[msg("Account is not owned by this program or has an unknown type")]
+    UnknownAccountType,
+    #[msg("Account could not be migrated to the current version")]
+    MigrationFailed,
+    #[msg("Paused by the guardian")]
+    Paused,
+}
+
+impl CommonError {
+    pub const ALL: [CommonError; 7] = [
+        CommonError::Unauthorized,
+        CommonError::Overflow,
+        CommonError::InvalidDuration,
+        CommonError::InvalidTokenAccount,
+        CommonError::UnknownAccountType,
+        CommonError::MigrationFailed,
+        CommonError::Paused,
+    ];
+
+    pub fn from_code(code: u32) -> Option<Self> {
+        Self::ALL.into_iter().find(|&e| u32::from(e) == code)
+    }
+}
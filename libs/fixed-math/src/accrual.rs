@@ -0,0 +1,95 @@
+// Reward/vesting accrual shared by the programs and their off-chain mirrors, so projections match byte for byte.
+use crate::{apply_bps, prorate, Rounding};
+
+// Linear vesting after a cliff, as stored on each vesting `Beneficiary`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct VestingSchedule {
+    pub allocation: u64,
+    pub start_time: i64,
+    pub cliff_duration: i64,
+    pub vesting_duration: i64,
+    pub accelerated: u64, // vested immediately by `accelerate`, taken off the end of the schedule
+}
+
+impl VestingSchedule {
+    // Total vested at `now`, `None` on overflow
+    pub fn vested_at(&self, now: i64) -> Option<u64> {
+        let linear = self.linear_vested_at(now)?;
+        Some(linear.saturating_add(self.accelerated).min(self.allocation))
+    }
+
+    fn linear_vested_at(&self, now: i64) -> Option<u64> {
+        if now < self.start_time {
+            return Some(0);
+        }
+
+        let elapsed = now.checked_sub(self.start_time)?;
+        if elapsed < self.cliff_duration {
+            return Some(0);
+        }
+
+        prorate(self.allocation, elapsed as u64, self.vesting_duration as u64, Rounding::Down)
+    }
+
+    // Vests `bps` of what is still unvested at `now` immediately; returns the amount accelerated.
+    // `None` on overflow or if `bps` is above 100%.
+    pub fn accelerate(&mut self, now: i64, bps: u64) -> Option<u64> {
+        let unvested = self.allocation - self.vested_at(now)?;
+        let amount = apply_bps(unvested, bps, Rounding::Down)?;
+        self.accelerated = self.accelerated.checked_add(amount)?;
+        Some(amount)
+    }
+
+    // Vested but not yet released at `now`, `None` on overflow. 0 while more has been released than
+    // vested, as after an amendment stretches the schedule.
+    pub fn releasable_at(&self, now: i64, released: u64) -> Option<u64> {
+        Some(self.vested_at(now)?.saturating_sub(released))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SCHEDULE: VestingSchedule = VestingSchedule {
+        allocation: 1_000,
+        start_time: 100,
+        cliff_duration: 50,
+        vesting_duration: 200,
+        accelerated: 0,
+    };
+
+    #[test]
+    fn nothing_vests_before_the_cliff() {
+        assert_eq!(SCHEDULE.vested_at(0), Some(0));
+        assert_eq!(SCHEDULE.vested_at(149), Some(0));
+        // The cliff releases everything accrued since the start
+        assert_eq!(SCHEDULE.vested_at(150), Some(250));
+    }
+
+    #[test]
+    fn vests_linearly_up_to_the_allocation() {
+        assert_eq!(SCHEDULE.vested_at(201), Some(505));
+        assert_eq!(SCHEDULE.vested_at(300), Some(1_000));
+        assert_eq!(SCHEDULE.vested_at(i64::MAX), Some(1_000));
+    }
+
+    #[test]
+    fn acceleration_comes_off_the_end() {
+        let mut schedule = SCHEDULE;
+        // 20% of the 750 still unvested
+        assert_eq!(schedule.accelerate(150, 2_000), Some(150));
+        assert_eq!(schedule.vested_at(150), Some(400));
+        assert_eq!(schedule.vested_at(250), Some(900));
+        // Fully vested 30 seconds before the original end
+        assert_eq!(schedule.vested_at(270), Some(1_000));
+        assert_eq!(schedule.accelerate(150, 10_001), None);
+    }
+
+    #[test]
+    fn releasable_is_vested_less_released() {
+        assert_eq!(SCHEDULE.releasable_at(200, 100), Some(400));
+        assert_eq!(SCHEDULE.releasable_at(200, 500), Some(0));
+        assert_eq!(SCHEDULE.releasable_at(200, 501), Some(0));
+    }
+}
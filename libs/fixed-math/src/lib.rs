@@ -0,0 +1,183 @@
+// Integer math shared by the on-chain programs so every proration rounds and overflows the same way.
+// Everything widens to u128 internally; results that do not fit back into u64 are reported, never truncated.
+// `no_std` so the same code runs inside BPF programs and in off-chain mirrors.
+#![no_std]
+
+extern crate alloc;
+
+pub mod accrual;
+pub mod mirror;
+pub mod side_pots;
+
+pub const BPS_DENOMINATOR: u64 = 10_000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Rounding {
+    Down,
+    Up,
+    Nearest, // halves round up
+}
+
+// a * b / denominator, `None` on division by zero or if the result exceeds u64
+pub fn mul_div(a: u64, b: u64, denominator: u64, rounding: Rounding) -> Option<u64> {
+    if denominator == 0 {
+        return None;
+    }
+
+    let product = a as u128 * b as u128;
+    let denominator = denominator as u128;
+    let quotient = product / denominator;
+    let remainder = product % denominator;
+
+    let rounded = match rounding {
+        Rounding::Down => quotient,
+        Rounding::Up if remainder > 0 => quotient + 1,
+        Rounding::Up => quotient,
+        Rounding::Nearest if remainder * 2 >= denominator => quotient + 1,
+        Rounding::Nearest => quotient,
+    };
+
+    u64::try_from(rounded).ok()
+}
+
+// Like `mul_div` but clamps to u64::MAX instead of failing; division by zero still yields `None`
+pub fn saturating_mul_div(a: u64, b: u64, denominator: u64, rounding: Rounding) -> Option<u64> {
+    if denominator == 0 {
+        return None;
+    }
+    Some(mul_div(a, b, denominator, rounding).unwrap_or(u64::MAX))
+}
+
+// amount * bps / 10_000, `None` if bps is above 100%
+pub fn apply_bps(amount: u64, bps: u64, rounding: Rounding) -> Option<u64> {
+    if bps > BPS_DENOMINATOR {
+        return None;
+    }
+    mul_div(amount, bps, BPS_DENOMINATOR, rounding)
+}
+
+// Multiplier form of `apply_bps` that allows values above 100% (e.g. odds or reward boosts)
+pub fn scale_bps(amount: u64, bps: u64, rounding: Rounding) -> Option<u64> {
+    mul_div(amount, bps, BPS_DENOMINATOR, rounding)
+}
+
+// Linear share of `total` unlocked after `elapsed` out of `duration`, capped at `total`
+pub fn prorate(total: u64, elapsed: u64, duration: u64, rounding: Rounding) -> Option<u64> {
+    if elapsed >= duration {
+        return Some(total);
+    }
+    mul_div(total, elapsed, duration, rounding)
+}
+
+// Pool shares worth `assets` at the current exchange rate (ERC-4626 style). The +1 virtual share and
+// asset keep the first deposit well defined and make donating to the pool to skew the rate unprofitable.
+pub fn convert_to_shares(
+    assets: u64,
+    total_assets: u64,
+    total_shares: u64,
+    rounding: Rounding,
+) -> Option<u64> {
+    mul_div(assets, total_shares.checked_add(1)?, total_assets.checked_add(1)?, rounding)
+}
+
+// Inverse of `convert_to_shares`; round down when paying out so the pool never owes more than it holds
+pub fn convert_to_assets(
+    shares: u64,
+    total_assets: u64,
+    total_shares: u64,
+    rounding: Rounding,
+) -> Option<u64> {
+    mul_div(shares, total_assets.checked_add(1)?, total_shares.checked_add(1)?, rounding)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn mul_div_rounds_each_way() {
+        assert_eq!(mul_div(10, 1, 3, Rounding::Down), Some(3));
+        assert_eq!(mul_div(10, 1, 3, Rounding::Up), Some(4));
+        assert_eq!(mul_div(10, 1, 3, Rounding::Nearest), Some(3));
+        assert_eq!(mul_div(5, 1, 2, Rounding::Nearest), Some(3)); // halves round up
+        assert_eq!(mul_div(9, 1, 3, Rounding::Up), Some(3)); // exact results aren't bumped
+    }
+
+    #[test]
+    fn mul_div_rejects_zero_denominator() {
+        assert_eq!(mul_div(1, 1, 0, Rounding::Down), None);
+        assert_eq!(saturating_mul_div(1, 1, 0, Rounding::Down), None);
+    }
+
+    #[test]
+    fn mul_div_widens_intermediate_product() {
+        assert_eq!(mul_div(u64::MAX, u64::MAX, u64::MAX, Rounding::Down), Some(u64::MAX));
+        assert_eq!(mul_div(u64::MAX, 3, 4, Rounding::Down), Some(u64::MAX / 4 * 3 + 2));
+    }
+
+    #[test]
+    fn mul_div_reports_u64_overflow() {
+        assert_eq!(mul_div(u64::MAX, 2, 1, Rounding::Down), None);
+        assert_eq!(mul_div(u64::MAX, u64::MAX, u64::MAX - 1, Rounding::Down), None);
+        // (2^65 - 1) / 2 is u64::MAX and a half, so only rounding up overflows
+        let a = 1_190_112_520_884_487_201; // (2^65 - 1) / 31
+        assert_eq!(mul_div(a, 31, 2, Rounding::Down), Some(u64::MAX));
+        assert_eq!(mul_div(a, 31, 2, Rounding::Up), None);
+        assert_eq!(mul_div(a, 31, 2, Rounding::Nearest), None);
+        assert_eq!(saturating_mul_div(u64::MAX, 2, 1, Rounding::Down), Some(u64::MAX));
+    }
+
+    #[test]
+    fn apply_bps_caps_at_one_hundred_percent() {
+        assert_eq!(apply_bps(1_000, 2_500, Rounding::Down), Some(250));
+        assert_eq!(apply_bps(1_000, BPS_DENOMINATOR, Rounding::Down), Some(1_000));
+        assert_eq!(apply_bps(1_000, BPS_DENOMINATOR + 1, Rounding::Down), None);
+        assert_eq!(apply_bps(1, 1, Rounding::Down), Some(0));
+        assert_eq!(apply_bps(1, 1, Rounding::Up), Some(1));
+    }
+
+    #[test]
+    fn scale_bps_allows_multipliers() {
+        assert_eq!(scale_bps(1_000, 25_000, Rounding::Down), Some(2_500));
+        assert_eq!(scale_bps(3, 15_000, Rounding::Down), Some(4));
+        assert_eq!(scale_bps(3, 15_000, Rounding::Up), Some(5));
+        assert_eq!(scale_bps(u64::MAX, 20_000, Rounding::Down), None);
+    }
+
+    #[test]
+    fn prorate_is_linear_and_capped() {
+        assert_eq!(prorate(1_000, 0, 100, Rounding::Up), Some(0));
+        assert_eq!(prorate(1_000, 100, 100, Rounding::Down), Some(1_000));
+        assert_eq!(prorate(1_000, 250, 100, Rounding::Down), Some(1_000));
+        assert_eq!(prorate(1_000, 1, 3, Rounding::Down), Some(333));
+        assert_eq!(prorate(1_000, 1, 3, Rounding::Up), Some(334));
+        // A zero duration unlocks everything at once
+        assert_eq!(prorate(1_000, 0, 0, Rounding::Down), Some(1_000));
+    }
+
+    #[test]
+    fn first_deposit_mints_one_share_per_asset() {
+        assert_eq!(convert_to_shares(500, 0, 0, Rounding::Down), Some(500));
+        assert_eq!(convert_to_assets(500, 500, 500, Rounding::Down), Some(500));
+    }
+
+    #[test]
+    fn share_conversions_round_against_the_caller() {
+        // 3 assets per share (plus the virtual share and asset)
+        let (total_assets, total_shares) = (2_999, 999);
+        assert_eq!(convert_to_shares(10, total_assets, total_shares, Rounding::Down), Some(3));
+        assert_eq!(convert_to_shares(10, total_assets, total_shares, Rounding::Up), Some(4));
+        assert_eq!(convert_to_assets(1, total_assets, total_shares, Rounding::Down), Some(3));
+
+        let shares = convert_to_shares(1_000, total_assets, total_shares, Rounding::Down).unwrap();
+        let assets = convert_to_assets(shares, total_assets, total_shares, Rounding::Down).unwrap();
+        assert!(assets <= 1_000);
+    }
+
+    #[test]
+    fn share_conversions_report_overflow() {
+        assert_eq!(convert_to_shares(1, u64::MAX, 0, Rounding::Down), None);
+        assert_eq!(convert_to_assets(1, 0, u64::MAX, Rounding::Down), None);
+        assert_eq!(convert_to_shares(u64::MAX, 0, 1, Rounding::Down), None);
+    }
+}
@@ -0,0 +1,184 @@
+// Off-chain replay of program events using the same accrual math, to check on-chain state against projections.
+// Events are keyed by grant account: a beneficiary holds one grant per mint.
+use alloc::collections::BTreeMap;
+
+use crate::accrual::VestingSchedule;
+
+pub type Address = [u8; 32];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VestingEvent {
+    // `GrantCreated`, with no acceleration yet
+    GrantCreated { grant: Address, schedule: VestingSchedule },
+    Released { grant: Address, amount: u64, timestamp: i64 },
+    Accelerated { grant: Address, bps: u64, timestamp: i64 },
+    // An accepted amendment's new terms (`AmendmentAccepted::after`)
+    Amended { grant: Address, allocation: u64, cliff_duration: i64, vesting_duration: i64 },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MirrorError {
+    UnknownGrant(Address),
+    DuplicateGrant(Address),
+    // A release paid out something other than everything releasable at that time
+    ReleaseMismatch { grant: Address, expected: u64, actual: u64 },
+    // The on-chain `released` total disagrees with the replay
+    StateMismatch { grant: Address, expected: u64, actual: u64 },
+    Overflow,
+}
+
+#[derive(Debug, Default)]
+pub struct VestingMirror {
+    // schedule and released-so-far per grant
+    grants: BTreeMap<Address, (VestingSchedule, u64)>,
+}
+
+impl VestingMirror {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn replay<'a>(events: impl IntoIterator<Item = &'a VestingEvent>) -> Result<Self, MirrorError> {
+        let mut mirror = Self::new();
+        for event in events {
+            mirror.apply(event)?;
+        }
+        Ok(mirror)
+    }
+
+    pub fn apply(&mut self, event: &VestingEvent) -> Result<(), MirrorError> {
+        match *event {
+            VestingEvent::GrantCreated { grant, schedule } => {
+                if self.grants.contains_key(&grant) {
+                    return Err(MirrorError::DuplicateGrant(grant));
+                }
+                self.grants.insert(grant, (schedule, 0));
+            }
+            VestingEvent::Released { grant, amount, timestamp } => {
+                let (schedule, released) = self.grants.get_mut(&grant).ok_or(MirrorError::UnknownGrant(grant))?;
+
+                let expected = schedule.releasable_at(timestamp, *released).ok_or(MirrorError::Overflow)?;
+                if expected != amount {
+                    return Err(MirrorError::ReleaseMismatch { grant, expected, actual: amount });
+                }
+                *released = released.checked_add(amount).ok_or(MirrorError::Overflow)?;
+            }
+            VestingEvent::Accelerated { grant, bps, timestamp } => {
+                let (schedule, _) = self.grants.get_mut(&grant).ok_or(MirrorError::UnknownGrant(grant))?;
+                schedule.accelerate(timestamp, bps).ok_or(MirrorError::Overflow)?;
+            }
+            VestingEvent::Amended { grant, allocation, cliff_duration, vesting_duration } => {
+                let (schedule, _) = self.grants.get_mut(&grant).ok_or(MirrorError::UnknownGrant(grant))?;
+                // Start time and acceleration carry over, as they do on chain
+                schedule.allocation = allocation;
+                schedule.cliff_duration = cliff_duration;
+                schedule.vesting_duration = vesting_duration;
+            }
+        }
+        Ok(())
+    }
+
+    pub fn released(&self, grant: &Address) -> Option<u64> {
+        self.grants.get(grant).map(|&(_, released)| released)
+    }
+
+    // What a `release` at `now` would pay out
+    pub fn projected_release(&self, grant: &Address, now: i64) -> Option<u64> {
+        let (schedule, released) = self.grants.get(grant)?;
+        schedule.releasable_at(now, *released)
+    }
+
+    pub fn verify(&self, grant: &Address, on_chain_released: u64) -> Result<(), MirrorError> {
+        let expected = self.released(grant).ok_or(MirrorError::UnknownGrant(*grant))?;
+        if expected != on_chain_released {
+            return Err(MirrorError::StateMismatch {
+                grant: *grant,
+                expected,
+                actual: on_chain_released,
+            });
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const GRANT: Address = [1; 32];
+
+    #[test]
+    fn amendments_change_the_replayed_schedule() {
+        let schedule = VestingSchedule {
+            allocation: 1_000,
+            start_time: 0,
+            cliff_duration: 0,
+            vesting_duration: 100,
+            accelerated: 0,
+        };
+        let events = [
+            VestingEvent::GrantCreated { grant: GRANT, schedule },
+            VestingEvent::Released { grant: GRANT, amount: 100, timestamp: 10 },
+            VestingEvent::Amended { grant: GRANT, allocation: 2_000, cliff_duration: 0, vesting_duration: 200 },
+            // 2_000 * 50 / 200 vested, less the 100 already released
+            VestingEvent::Released { grant: GRANT, amount: 400, timestamp: 50 },
+        ];
+        let mirror = VestingMirror::replay(&events).unwrap();
+        assert_eq!(mirror.released(&GRANT), Some(500));
+        assert_eq!(mirror.projected_release(&GRANT, 200), Some(1_500));
+    }
+
+    #[test]
+    fn amending_an_unknown_grant_fails() {
+        let event = VestingEvent::Amended { grant: GRANT, allocation: 1, cliff_duration: 0, vesting_duration: 1 };
+        assert_eq!(VestingMirror::new().apply(&event), Err(MirrorError::UnknownGrant(GRANT)));
+    }
+
+    #[test]
+    fn replays_acceleration_and_checks_each_release() {
+        let schedule = VestingSchedule {
+            allocation: 1_000,
+            start_time: 100,
+            cliff_duration: 50,
+            vesting_duration: 200,
+            accelerated: 0,
+        };
+        let mut mirror = VestingMirror::replay(&[
+            VestingEvent::GrantCreated { grant: GRANT, schedule },
+            VestingEvent::Accelerated { grant: GRANT, bps: 2_000, timestamp: 150 },
+            VestingEvent::Released { grant: GRANT, amount: 400, timestamp: 150 },
+        ])
+        .unwrap();
+        assert_eq!(mirror.projected_release(&GRANT, 250), Some(500));
+
+        let short = VestingEvent::Released { grant: GRANT, amount: 200, timestamp: 250 };
+        assert_eq!(
+            mirror.apply(&short),
+            Err(MirrorError::ReleaseMismatch { grant: GRANT, expected: 500, actual: 200 })
+        );
+        let created = VestingEvent::GrantCreated { grant: GRANT, schedule };
+        assert_eq!(mirror.apply(&created), Err(MirrorError::DuplicateGrant(GRANT)));
+    }
+
+    #[test]
+    fn verify_compares_released_totals() {
+        let schedule = VestingSchedule {
+            allocation: 100,
+            start_time: 0,
+            cliff_duration: 0,
+            vesting_duration: 10,
+            accelerated: 0,
+        };
+        let mirror = VestingMirror::replay(&[
+            VestingEvent::GrantCreated { grant: GRANT, schedule },
+            VestingEvent::Released { grant: GRANT, amount: 50, timestamp: 5 },
+        ])
+        .unwrap();
+        assert_eq!(mirror.verify(&GRANT, 50), Ok(()));
+        assert_eq!(
+            mirror.verify(&GRANT, 60),
+            Err(MirrorError::StateMismatch { grant: GRANT, expected: 50, actual: 60 })
+        );
+        assert_eq!(mirror.verify(&[2; 32], 0), Err(MirrorError::UnknownGrant([2; 32])));
+    }
+}
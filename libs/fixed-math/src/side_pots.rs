@@ -0,0 +1,104 @@
+// Poker-style side pots, reused to settle several capped claims against limited funds.
+use alloc::vec::Vec;
+
+// One layer of the pot: `amount` is shared by every claim in `eligible` (indices into the claims)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SidePot {
+    pub amount: u64,
+    pub eligible: Vec<usize>,
+}
+
+// Main pot first. Each pot takes the next slice of every claim large enough to reach it, so a
+// claim is only eligible for pots up to its own size. `None` if a pot exceeds u64.
+pub fn compute_side_pots(claims: &[u64]) -> Option<Vec<SidePot>> {
+    let mut levels: Vec<u64> = claims.iter().copied().filter(|&claim| claim > 0).collect();
+    levels.sort_unstable();
+    levels.dedup();
+
+    let mut pots = Vec::with_capacity(levels.len());
+    let mut previous = 0;
+    for level in levels {
+        let eligible: Vec<usize> = (0..claims.len()).filter(|&i| claims[i] >= level).collect();
+        let amount = (level - previous).checked_mul(eligible.len() as u64)?;
+        pots.push(SidePot { amount, eligible });
+        previous = level;
+    }
+    Some(pots)
+}
+
+// Pays `claims` out of `available` by funding side pots from the main pot up. Within the first
+// pot that can't be covered in full, eligible claims split what's left equally, rounding down;
+// every claim is paid in full if `available` covers their sum. `None` on overflow.
+pub fn allocate(claims: &[u64], available: u64) -> Option<Vec<u64>> {
+    let mut paid = alloc::vec![0u64; claims.len()];
+    let mut remaining = available;
+
+    for pot in compute_side_pots(claims)? {
+        let claimants = pot.eligible.len() as u64;
+        let per_claim = pot.amount / claimants;
+        let share = per_claim.min(remaining / claimants);
+        for &i in &pot.eligible {
+            paid[i] += share;
+        }
+        remaining -= share * claimants;
+        if share < per_claim {
+            break;
+        }
+    }
+    Some(paid)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+
+    #[test]
+    fn all_ins_at_different_levels() {
+        let pots = compute_side_pots(&[100, 50, 200]).unwrap();
+        assert_eq!(
+            pots,
+            vec![
+                SidePot { amount: 150, eligible: vec![0, 1, 2] },
+                SidePot { amount: 100, eligible: vec![0, 2] },
+                SidePot { amount: 100, eligible: vec![2] },
+            ]
+        );
+        assert_eq!(pots.iter().map(|pot| pot.amount).sum::<u64>(), 350);
+    }
+
+    #[test]
+    fn equal_and_zero_claims_share_a_level() {
+        let pots = compute_side_pots(&[0, 40, 40]).unwrap();
+        assert_eq!(pots, vec![SidePot { amount: 80, eligible: vec![1, 2] }]);
+        assert_eq!(compute_side_pots(&[]).unwrap(), vec![]);
+    }
+
+    #[test]
+    fn covered_claims_are_paid_in_full() {
+        assert_eq!(allocate(&[100, 50, 200], 350).unwrap(), vec![100, 50, 200]);
+        assert_eq!(allocate(&[100, 50, 200], 1_000).unwrap(), vec![100, 50, 200]);
+    }
+
+    #[test]
+    fn short_pot_is_split_within_the_first_uncovered_layer() {
+        // Main pot (150) is covered; the 50 left is split between the two claims reaching layer two
+        assert_eq!(allocate(&[100, 50, 200], 200).unwrap(), vec![75, 50, 75]);
+        // Not even the main pot is covered
+        assert_eq!(allocate(&[100, 50, 200], 90).unwrap(), vec![30, 30, 30]);
+        assert_eq!(allocate(&[100, 50, 200], 0).unwrap(), vec![0, 0, 0]);
+    }
+
+    #[test]
+    fn remainder_is_left_unallocated() {
+        let paid = allocate(&[10, 10, 10], 20).unwrap();
+        assert_eq!(paid, vec![6, 6, 6]);
+        assert_eq!(20 - paid.iter().sum::<u64>(), 2);
+    }
+
+    #[test]
+    fn overflowing_pot_is_rejected() {
+        assert_eq!(compute_side_pots(&[u64::MAX, u64::MAX]), None);
+        assert_eq!(allocate(&[u64::MAX, u64::MAX], 10), None);
+    }
+}
@@ -0,0 +1,19 @@
+// Account checks shared by the Anchor programs for cases the `#[account(...)]`
+// constraints can't express on their own, e.g. relations between two passed-in accounts.
+
+// Fails with `$err` unless the token account `$account` holds `$mint` and belongs to `$owner`
+#[macro_export]
+macro_rules! require_token_account {
+    ($account:expr, $mint:expr, $owner:expr, $err:expr) => {{
+        anchor_lang::require_keys_eq!($account.mint, $mint, $err);
+        anchor_lang::require_keys_eq!($account.owner, $owner, $err);
+    }};
+}
+
+// Fails with `$err` unless `$signer` is the wallet recorded as the account's owner
+#[macro_export]
+macro_rules! require_owner {
+    ($signer:expr, $recorded_owner:expr, $err:expr) => {
+        anchor_lang::require_keys_eq!($signer.key(), $recorded_owner, $err)
+    };
+}
@@ -0,0 +1,327 @@
+use std::error::Error;
+use std::fmt;
+
+#[cfg(feature = "rayon")]
+use rayon::prelude::*;
+
+use crate::{parse_value, Category};
+
+// Rollouts per independently seeded chunk. Chunks, not threads, own the random streams, so the
+// result for a given seed doesn't depend on how many threads run them.
+const CHUNK: u64 = 1 << 14;
+
+const RANKS: [&str; 14] = ["2", "3", "4", "5", "6", "7", "8", "9", "10", "T", "J", "Q", "K", "A"];
+const SUITS: &str = "CDHS";
+
+// (value, suit) with aces as 14
+type Card = (u8, char);
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum EquityError {
+    InvalidCard(String),
+    DuplicateCard(String),
+    NotEnoughHands,
+    HoleCards(usize), // hand at this index doesn't have exactly two cards
+    BoardTooLong,
+    NotEnoughCards, // the deck left after the dealt cards can't complete the board
+}
+
+impl fmt::Display for EquityError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            EquityError::InvalidCard(card) => write!(f, "{card:?} is not a card"),
+            EquityError::DuplicateCard(card) => write!(f, "{card} is dealt more than once"),
+            EquityError::NotEnoughHands => write!(f, "equity needs at least two hands"),
+            EquityError::HoleCards(i) => write!(f, "hand {i} must have exactly two cards"),
+            EquityError::BoardTooLong => write!(f, "board has more than five cards"),
+            EquityError::NotEnoughCards => write!(f, "too few cards left to complete the board"),
+        }
+    }
+}
+
+impl Error for EquityError {}
+
+// Each hand's share of the pot over `rollouts` random run-outs of the board, ties split evenly.
+// Hands are two hole cards ("AS KD"), the board holds zero to five cards. The same `seed` always
+// gives the same result, with or without the `rayon` feature.
+pub fn equity(hands: &[&str], board: &str, rollouts: u64, seed: u64) -> Result<Vec<f64>, EquityError> {
+    if hands.len() < 2 {
+        return Err(EquityError::NotEnoughHands);
+    }
+    let mut dealt = Vec::new();
+    let holes = hands.iter()
+        .enumerate()
+        .map(|(i, hand)| {
+            let cards = parse_cards(hand, &mut dealt)?;
+            if cards.len() == 2 { Ok(cards) } else { Err(EquityError::HoleCards(i)) }
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    let board = parse_cards(board, &mut dealt)?;
+    if board.len() > 5 {
+        return Err(EquityError::BoardTooLong);
+    }
+    let deck: Vec<Card> = full_deck().into_iter().filter(|card| !dealt.contains(card)).collect();
+    if deck.len() < 5 - board.len() {
+        return Err(EquityError::NotEnoughCards);
+    }
+
+    let sim = Simulation { holes, board, deck };
+    let chunks = rollouts.div_ceil(CHUNK);
+    let run = |chunk: u64| sim.run(CHUNK.min(rollouts - chunk * CHUNK), splitmix(seed ^ splitmix(chunk)));
+
+    #[cfg(feature = "rayon")]
+    let totals: Vec<Vec<f64>> = (0..chunks).into_par_iter().map(run).collect();
+    #[cfg(not(feature = "rayon"))]
+    let totals: Vec<Vec<f64>> = (0..chunks).map(run).collect();
+
+    // Summed in chunk order so floating-point rounding is the same however chunks were scheduled
+    let mut shares = vec![0.0; hands.len()];
+    for chunk in totals {
+        for (share, won) in shares.iter_mut().zip(chunk) {
+            *share += won;
+        }
+    }
+    if rollouts > 0 {
+        for share in &mut shares {
+            *share /= rollouts as f64;
+        }
+    }
+    Ok(shares)
+}
+
+struct Simulation {
+    holes: Vec<Vec<Card>>,
+    board: Vec<Card>,
+    deck: Vec<Card>,
+}
+
+impl Simulation {
+    // Pot shares won by each hand over `rollouts` run-outs
+    fn run(&self, rollouts: u64, seed: u64) -> Vec<f64> {
+        let mut rng = SplitMix64(seed);
+        let mut deck = self.deck.clone();
+        let missing = 5 - self.board.len();
+        let mut won = vec![0.0; self.holes.len()];
+        let mut ranks = Vec::with_capacity(self.holes.len());
+        let mut seven = Vec::with_capacity(7);
+
+        for _ in 0..rollouts {
+            // Partial Fisher–Yates: the first `missing` cards become the rest of the board
+            for i in 0..missing {
+                let j = i + rng.below((deck.len() - i) as u64) as usize;
+                deck.swap(i, j);
+            }
+
+            ranks.clear();
+            for hole in &self.holes {
+                seven.clear();
+                seven.extend_from_slice(hole);
+                seven.extend_from_slice(&self.board);
+                seven.extend_from_slice(&deck[..missing]);
+                ranks.push(strength(&seven));
+            }
+
+            let best = ranks.iter().max().unwrap();
+            let winners = ranks.iter().filter(|&rank| rank == best).count();
+            for (w, rank) in won.iter_mut().zip(&ranks) {
+                if rank == best {
+                    *w += 1.0 / winners as f64;
+                }
+            }
+        }
+        won
+    }
+}
+
+// Strength of the best five cards among up to seven, as one integer: the category in the top bits,
+// then the tie-break values four bits each. Orders exactly like `HandRank` but allocates nothing,
+// which is what makes millions of rollouts a second possible.
+fn strength(cards: &[Card]) -> u32 {
+    let mut counts = [0u8; 15];
+    let mut by_suit = [0u16; 4];
+    let mut all = 0u16;
+    for &(value, suit) in cards {
+        counts[value as usize] += 1;
+        by_suit[SUITS.find(suit).unwrap()] |= 1 << value;
+        all |= 1 << value;
+    }
+
+    if let Some(&suited) = by_suit.iter().find(|mask| mask.count_ones() >= 5) {
+        return match straight_high(suited) {
+            Some(high) => encode(Category::StraightFlush, &[high]),
+            None => encode(Category::Flush, &top(suited, 5, 0)),
+        };
+    }
+
+    let highest = |count: u8, except: u8| (2..=14u8).rev().find(|&v| counts[v as usize] >= count && v != except);
+    if let Some(quads) = highest(4, 0) {
+        return encode(Category::FourOfAKind, &[quads, top(all, 1, 1 << quads)[0]]);
+    }
+    if let Some(trips) = highest(3, 0) {
+        if let Some(pair) = highest(2, trips) {
+            return encode(Category::FullHouse, &[trips, pair]);
+        }
+    }
+    if let Some(high) = straight_high(all) {
+        return encode(Category::Straight, &[high]);
+    }
+    if let Some(trips) = highest(3, 0) {
+        let kickers = top(all, 2, 1 << trips);
+        return encode(Category::ThreeOfAKind, &[trips, kickers[0], kickers[1]]);
+    }
+    if let Some(high_pair) = highest(2, 0) {
+        if let Some(low_pair) = highest(2, high_pair) {
+            let kicker = top(all, 1, 1 << high_pair | 1 << low_pair)[0];
+            return encode(Category::TwoPair, &[high_pair, low_pair, kicker]);
+        }
+        let kickers = top(all, 3, 1 << high_pair);
+        return encode(Category::OnePair, &[high_pair, kickers[0], kickers[1], kickers[2]]);
+    }
+    encode(Category::HighCard, &top(all, 5, 0))
+}
+
+// Highest card of the best five-in-a-row in `mask`, the ace also playing low
+fn straight_high(mask: u16) -> Option<u8> {
+    let mask = if mask & 1 << 14 != 0 { mask | 1 << 1 } else { mask };
+    (5..=14u8).rev().find(|&high| {
+        let run = 0b11111 << (high - 4);
+        mask & run == run
+    })
+}
+
+// The `n` highest values set in `mask` and not in `except`, zero-padded
+fn top(mask: u16, n: usize, except: u16) -> [u8; 5] {
+    let mut values = [0; 5];
+    let mut found = 0;
+    for v in (2..=14u8).rev() {
+        if found < n && mask & !except & 1 << v != 0 {
+            values[found] = v;
+            found += 1;
+        }
+    }
+    values
+}
+
+fn encode(category: Category, values: &[u8]) -> u32 {
+    values.iter().take(5).enumerate().fold((category as u32) << 20, |key, (i, &v)| {
+        key | (v as u32) << (16 - 4 * i)
+    })
+}
+
+// Also records each card in `dealt` so no card can be dealt twice
+pub(crate) fn parse_cards(cards: &str, dealt: &mut Vec<Card>) -> Result<Vec<Card>, EquityError> {
+    cards.split_whitespace()
+        .map(|card| {
+            let invalid = || EquityError::InvalidCard(card.to_string());
+            let split = card.len().checked_sub(1).filter(|&i| card.is_char_boundary(i)).ok_or_else(invalid)?;
+            let (value, suit) = card.split_at(split);
+            let suit = suit.chars().next().filter(|&s| SUITS.contains(s)).ok_or_else(invalid)?;
+            if !RANKS.contains(&value) {
+                return Err(invalid());
+            }
+            let parsed = (parse_value(value), suit);
+            if dealt.contains(&parsed) {
+                return Err(EquityError::DuplicateCard(card.to_string()));
+            }
+            dealt.push(parsed);
+            Ok(parsed)
+        })
+        .collect()
+}
+
+fn full_deck() -> Vec<Card> {
+    SUITS.chars().flat_map(|suit| (2..=14).map(move |value| (value, suit))).collect()
+}
+
+const GOLDEN_GAMMA: u64 = 0x9e37_79b9_7f4a_7c15;
+
+// Spreads chunk indices over the seed space
+fn splitmix(x: u64) -> u64 {
+    mix(x.wrapping_add(GOLDEN_GAMMA))
+}
+
+fn mix(mut z: u64) -> u64 {
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    z ^ (z >> 31)
+}
+
+// Small and fast; quality is ample for dealing cards, not for anything adversarial (see `shuffle`)
+struct SplitMix64(u64);
+
+impl SplitMix64 {
+    fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(GOLDEN_GAMMA);
+        mix(self.0)
+    }
+
+    // Uniform in 0..bound; rejection sampling avoids modulo bias
+    fn below(&mut self, bound: u64) -> u64 {
+        let zone = u64::MAX - u64::MAX % bound;
+        loop {
+            let value = self.next_u64();
+            if value < zone {
+                return value % bound;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rejects_a_deck_too_small_for_the_board() {
+        // Every card as a hole card, two at a time
+        let name = |(value, suit): Card| format!("{}{suit}", "23456789TJQKA".as_bytes()[value as usize - 2] as char);
+        let hands: Vec<String> =
+            full_deck().chunks(2).take(24).map(|pair| format!("{} {}", name(pair[0]), name(pair[1]))).collect();
+        let hands: Vec<&str> = hands.iter().map(String::as_str).collect();
+        assert_eq!(equity(&hands, "", 10, 0), Err(EquityError::NotEnoughCards));
+        // With a flop out, two cards complete the board and four are left
+        assert!(equity(&hands[..22], "AS KS QS", 10, 0).is_ok());
+    }
+
+    #[test]
+    fn rejects_malformed_input() {
+        assert_eq!(equity(&["AS KS"], "", 10, 0), Err(EquityError::NotEnoughHands));
+        assert_eq!(equity(&["AS KS", "QS"], "", 10, 0), Err(EquityError::HoleCards(1)));
+        assert_eq!(equity(&["AS KS", "AS QS"], "", 10, 0), Err(EquityError::DuplicateCard("AS".into())));
+        assert_eq!(equity(&["AS KS", "1S QS"], "", 10, 0), Err(EquityError::InvalidCard("1S".into())));
+        assert_eq!(
+            equity(&["AS KS", "QS JS"], "2C 3C 4C 5C 6C 7C", 10, 0),
+            Err(EquityError::BoardTooLong)
+        );
+    }
+
+    #[test]
+    fn same_seed_same_result() {
+        let hands = ["AS KD", "7H 7C", "QC JC"];
+        let run = |seed| equity(&hands, "2D 9S", 3 * CHUNK + 17, seed).unwrap();
+        assert_eq!(run(42), run(42));
+        assert_ne!(run(42), run(43));
+    }
+
+    #[test]
+    fn complete_board_is_exact() {
+        let board = "2C 7D 9H JS 3D";
+        assert_eq!(equity(&["AS AH", "KS KH"], board, 100, 0).unwrap(), vec![1.0, 0.0]);
+        // Royal flush on the board plays for both
+        let royal = "AS KS QS JS TS";
+        assert_eq!(equity(&["2C 3C", "4D 5D"], royal, 100, 0).unwrap(), vec![0.5, 0.5]);
+    }
+
+    #[test]
+    fn aces_against_kings_preflop() {
+        // About 82% for aces against kings of other suits
+        let shares = equity(&["AS AH", "KD KC"], "", 40_000, 7).unwrap();
+        assert!((shares[0] - 0.82).abs() < 0.015, "{shares:?}");
+        assert!((shares[0] + shares[1] - 1.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn zero_rollouts_share_nothing() {
+        assert_eq!(equity(&["AS KD", "7H 7C"], "", 0, 0).unwrap(), vec![0.0, 0.0]);
+    }
+}
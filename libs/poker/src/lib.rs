@@ -0,0 +1,329 @@
+pub mod equity;
+pub mod shuffle;
+pub mod tournament;
+
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt;
+
+use equity::EquityError;
+
+// Cards in a hand
+pub const HAND_SIZE: usize = 5;
+
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy)]
+pub enum Category {
+    HighCard,
+    OnePair,
+    TwoPair,
+    ThreeOfAKind,
+    Straight,
+    Flush,
+    FullHouse,
+    FourOfAKind,
+    StraightFlush,
+}
+
+// What a tie-break card stands for within its category
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy)]
+pub enum Role {
+    HighCard,
+    Kicker,
+    Pair,
+    TopPair,
+    BottomPair,
+    Trips,
+    Quads,
+    StraightHigh,
+}
+
+// Why `compare_hands` ordered two hands; values are card ranks with aces as 14
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum TieBreakTrace {
+    Category { a: Category, b: Category },
+    Card { role: Role, a: u8, b: u8 },
+    Tie,
+}
+
+// Same category means same roles at the same positions, so the derived order only compares values
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone)]
+struct HandRank {
+    category: Category,
+    cards: Vec<(Role, u8)>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum HandError {
+    InvalidCard(String),
+    DuplicateCard(String), // within a hand or across hands
+    HandSize(usize),       // hand at this index doesn't have exactly five cards
+}
+
+impl fmt::Display for HandError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            HandError::InvalidCard(card) => write!(f, "{card:?} is not a card"),
+            HandError::DuplicateCard(card) => write!(f, "{card} is dealt more than once"),
+            HandError::HandSize(i) => write!(f, "hand {i} must have exactly {HAND_SIZE} cards"),
+        }
+    }
+}
+
+impl Error for HandError {}
+
+// Assumes well-formed hands from one deck; use `try_winning_hands` on untrusted input
+pub fn winning_hands<'a>(hands: &[&'a str]) -> Vec<&'a str> {
+    let ranked: Vec<(&'a str, HandRank)> = hands.iter()
+        .map(|&h| (h, rank_hand(h)))
+        .collect();
+
+    let max_rank = ranked.iter().max_by_key(|(_, r)| r).map(|(_, r)| r.clone());
+
+    ranked.into_iter()
+        .filter(|(_, r)| Some(r) == max_rank.as_ref())
+        .map(|(h, _)| h)
+        .collect()
+}
+
+// `winning_hands`, after checking every card parses, no card is dealt twice and each hand has
+// `HAND_SIZE` cards
+pub fn try_winning_hands<'a>(hands: &[&'a str]) -> Result<Vec<&'a str>, HandError> {
+    let mut dealt = Vec::new();
+    for (i, hand) in hands.iter().enumerate() {
+        let cards = equity::parse_cards(hand, &mut dealt).map_err(|e| match e {
+            EquityError::DuplicateCard(card) => HandError::DuplicateCard(card),
+            EquityError::InvalidCard(card) => HandError::InvalidCard(card),
+            _ => unreachable!("parse_cards only fails on a card"),
+        })?;
+        if cards.len() != HAND_SIZE {
+            return Err(HandError::HandSize(i));
+        }
+    }
+    Ok(winning_hands(hands))
+}
+
+pub fn compare_hands(a: &str, b: &str) -> (Ordering, TieBreakTrace) {
+    let (a, b) = (rank_hand(a), rank_hand(b));
+    if a.category != b.category {
+        return (a.category.cmp(&b.category), TieBreakTrace::Category { a: a.category, b: b.category });
+    }
+    for (&(role, x), &(_, y)) in a.cards.iter().zip(&b.cards) {
+        if x != y {
+            return (x.cmp(&y), TieBreakTrace::Card { role, a: x, b: y });
+        }
+    }
+    (Ordering::Equal, TieBreakTrace::Tie)
+}
+
+fn rank_hand(hand: &str) -> HandRank {
+    let mut values = Vec::new();
+    let mut suits = Vec::new();
+
+    for card in hand.split_whitespace() {
+        let (v, s) = card.split_at(card.len() - 1);
+        values.push(parse_value(v));
+        suits.push(s.chars().next().unwrap());
+    }
+
+    values.sort_unstable_by(|a, b| b.cmp(a)); // descending
+    let is_flush = suits.iter().all(|&s| s == suits[0]);
+    let mut is_straight = values.windows(2).all(|w| w[0] == w[1] + 1);
+
+    // Ace-low straight
+    if values == vec![14, 5, 4, 3, 2] {
+        is_straight = true;
+        values = vec![5, 4, 3, 2, 1];
+    }
+
+    let mut counts = HashMap::new();
+    for &v in &values {
+        *counts.entry(v).or_insert(0) += 1;
+    }
+
+    let mut count_vec: Vec<_> = counts.iter().collect();
+    count_vec.sort_by(|a, b| b.1.cmp(a.1).then_with(|| b.0.cmp(a.0)));
+
+    let (category, mut cards) = match (is_flush, is_straight, count_vec.as_slice()) {
+        (true, true, _) => (Category::StraightFlush, vec![(Role::StraightHigh, values[0])]),
+        (_, _, &[(v, &4), (k, &1)]) =>
+            (Category::FourOfAKind, vec![(Role::Quads, *v), (Role::Kicker, *k)]),
+        (_, _, &[(v3, &3), (v2, &2)]) =>
+            (Category::FullHouse, vec![(Role::Trips, *v3), (Role::Pair, *v2)]),
+        (true, false, _) => (Category::Flush, high_cards(&values)),
+        (false, true, _) => (Category::Straight, vec![(Role::StraightHigh, values[0])]),
+        (_, _, &[(v, &3), (_, &1), (_, &1)]) =>
+            (Category::ThreeOfAKind, vec![(Role::Trips, *v)]),
+        (_, _, &[(p1, &2), (p2, &2), (_, &1)]) =>
+            (Category::TwoPair, vec![(Role::TopPair, *p1), (Role::BottomPair, *p2)]),
+        (_, _, &[(p, &2), (_, &1), (_, &1), (_, &1)]) =>
+            (Category::OnePair, vec![(Role::Pair, *p)]),
+        _ => (Category::HighCard, high_cards(&values)),
+    };
+
+    if matches!(category, Category::ThreeOfAKind | Category::TwoPair | Category::OnePair) {
+        let made: Vec<u8> = cards.iter().map(|&(_, v)| v).collect();
+        cards.extend(kickers(&values, &made).into_iter().map(|k| (Role::Kicker, k)));
+    }
+
+    HandRank { category, cards }
+}
+
+fn high_cards(values: &[u8]) -> Vec<(Role, u8)> {
+    values.iter()
+        .enumerate()
+        .map(|(i, &v)| (if i == 0 { Role::HighCard } else { Role::Kicker }, v))
+        .collect()
+}
+
+pub(crate) fn parse_value(v: &str) -> u8 {
+    match v {
+        "A" => 14,
+        "K" => 13,
+        "Q" => 12,
+        "J" => 11,
+        "T" => 10,
+        _ => v.parse().unwrap(),
+    }
+}
+
+fn kickers(values: &[u8], exclude: &[u8]) -> Vec<u8> {
+    values.iter().filter(|&&v| !exclude.contains(&v)).cloned().collect()
+}
+
+fn value_name(v: u8) -> String {
+    match v {
+        14 | 1 => "A".to_string(),
+        13 => "K".to_string(),
+        12 => "Q".to_string(),
+        11 => "J".to_string(),
+        _ => v.to_string(),
+    }
+}
+
+impl fmt::Display for Category {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Category::HighCard => "high card",
+            Category::OnePair => "one pair",
+            Category::TwoPair => "two pair",
+            Category::ThreeOfAKind => "three of a kind",
+            Category::Straight => "straight",
+            Category::Flush => "flush",
+            Category::FullHouse => "full house",
+            Category::FourOfAKind => "four of a kind",
+            Category::StraightFlush => "straight flush",
+        };
+        f.write_str(name)
+    }
+}
+
+impl fmt::Display for Role {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Role::HighCard => "high card",
+            Role::Kicker => "kicker",
+            Role::Pair => "pair",
+            Role::TopPair => "top pair",
+            Role::BottomPair => "bottom pair",
+            Role::Trips => "trips",
+            Role::Quads => "quads",
+            Role::StraightHigh => "straight to",
+        };
+        f.write_str(name)
+    }
+}
+
+// e.g. "flush vs straight", "kicker Q vs J"
+impl fmt::Display for TieBreakTrace {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TieBreakTrace::Category { a, b } => write!(f, "{a} vs {b}"),
+            TieBreakTrace::Card { role, a, b } => {
+                write!(f, "{role} {} vs {}", value_name(*a), value_name(*b))
+            }
+            TieBreakTrace::Tie => f.write_str("identical hands"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn category_decides_first() {
+        let (order, trace) = compare_hands("2S 3S 4S 5S 7S", "AH AD AC KS KD");
+        assert_eq!(order, Ordering::Less);
+        assert_eq!(trace, TieBreakTrace::Category { a: Category::Flush, b: Category::FullHouse });
+        assert_eq!(trace.to_string(), "flush vs full house");
+    }
+
+    #[test]
+    fn kicker_decides_equal_pairs() {
+        let (order, trace) = compare_hands("KS KD QH 7C 3D", "KH KC JS 7D 3S");
+        assert_eq!(order, Ordering::Greater);
+        assert_eq!(trace, TieBreakTrace::Card { role: Role::Kicker, a: 12, b: 11 });
+        assert_eq!(trace.to_string(), "kicker Q vs J");
+    }
+
+    #[test]
+    fn made_cards_decide_before_kickers() {
+        let (order, trace) = compare_hands("9S 9D 4H 4C AD", "9H 9C 5S 5D 2S");
+        assert_eq!(order, Ordering::Less);
+        assert_eq!(trace, TieBreakTrace::Card { role: Role::BottomPair, a: 4, b: 5 });
+    }
+
+    #[test]
+    fn ace_low_straight_loses_to_six_high() {
+        let (order, trace) = compare_hands("AS 2D 3H 4C 5S", "2S 3D 4S 5C 6H");
+        assert_eq!(order, Ordering::Less);
+        assert_eq!(trace, TieBreakTrace::Card { role: Role::StraightHigh, a: 5, b: 6 });
+        assert_eq!(trace.to_string(), "straight to 5 vs 6");
+    }
+
+    #[test]
+    fn identical_ranks_tie() {
+        let (order, trace) = compare_hands("AS KS QD JC 9H", "AD KH QC JS 9D");
+        assert_eq!(order, Ordering::Equal);
+        assert_eq!(trace, TieBreakTrace::Tie);
+    }
+
+    #[test]
+    fn try_winning_hands_picks_the_winners() {
+        let hands = ["4D 5C 6D 2C JC", "2S 3C 7S 9H 10H", "3S 4S 5D 6H JH", "3H 4H 5H 6C JD"];
+        assert_eq!(try_winning_hands(&hands), Ok(vec![hands[2], hands[3]]));
+        assert_eq!(try_winning_hands(&[]), Ok(vec![]));
+    }
+
+    #[test]
+    fn try_winning_hands_rejects_a_card_dealt_twice() {
+        // Within one hand and across two
+        assert_eq!(
+            try_winning_hands(&["AS AS KD QH 2C"]),
+            Err(HandError::DuplicateCard("AS".into()))
+        );
+        assert_eq!(
+            try_winning_hands(&["AS KS QS JS TS", "2D 3D 4D 5D AS"]),
+            Err(HandError::DuplicateCard("AS".into()))
+        );
+    }
+
+    #[test]
+    fn try_winning_hands_rejects_wrong_hand_sizes() {
+        assert_eq!(try_winning_hands(&["AS KS QS JS TS", "2D 3D 4D 5D"]), Err(HandError::HandSize(1)));
+        assert_eq!(try_winning_hands(&["AS KS QS JS TS 9S"]), Err(HandError::HandSize(0)));
+    }
+
+    #[test]
+    fn try_winning_hands_rejects_invalid_cards() {
+        for card in ["1S", "AX", "S", "11H", "a♠"] {
+            let hand = format!("{card} KS QS JS TS");
+            assert_eq!(
+                try_winning_hands(&[hand.as_str()]),
+                Err(HandError::InvalidCard(card.to_string())),
+                "{card}"
+            );
+        }
+    }
+}
@@ -0,0 +1,220 @@
+use std::error::Error;
+use std::fmt;
+
+use sha2::{Digest as _, Sha256};
+
+pub type Digest = [u8; 32];
+pub type Secret = [u8; 32];
+
+const COMMIT_DOMAIN: &[u8] = b"poker-shuffle-commit";
+const SEED_DOMAIN: &[u8] = b"poker-shuffle-seed";
+const STREAM_DOMAIN: &[u8] = b"poker-shuffle-stream";
+
+const RANKS: &str = "23456789TJQKA";
+const SUITS: &str = "CDHS";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ShuffleError {
+    UnknownPlayer(usize),
+    CommitmentMismatch(usize), // reveal doesn't hash to the player's commitment
+    MissingReveal(usize),
+}
+
+impl fmt::Display for ShuffleError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ShuffleError::UnknownPlayer(i) => write!(f, "no commitment for player {i}"),
+            ShuffleError::CommitmentMismatch(i) => {
+                write!(f, "secret revealed by player {i} does not match their commitment")
+            }
+            ShuffleError::MissingReveal(i) => write!(f, "player {i} has not revealed"),
+        }
+    }
+}
+
+impl Error for ShuffleError {}
+
+// Commitment a player publishes before anyone reveals. Binding the seat index stops a player
+// from replaying someone else's commitment.
+pub fn commit(player: usize, secret: &Secret) -> Digest {
+    Sha256::new()
+        .chain_update(COMMIT_DOMAIN)
+        .chain_update((player as u64).to_le_bytes())
+        .chain_update(secret)
+        .finalize()
+        .into()
+}
+
+// One commit–reveal round. No single player can steer the deal: the seed depends on every
+// secret, and all commitments are fixed before the first reveal.
+#[derive(Debug, Clone)]
+pub struct ShuffleRound {
+    commitments: Vec<Digest>,
+    secrets: Vec<Option<Secret>>,
+}
+
+impl ShuffleRound {
+    pub fn new(commitments: Vec<Digest>) -> Self {
+        let secrets = vec![None; commitments.len()];
+        ShuffleRound { commitments, secrets }
+    }
+
+    pub fn commitments(&self) -> &[Digest] {
+        &self.commitments
+    }
+
+    pub fn reveal(&mut self, player: usize, secret: Secret) -> Result<(), ShuffleError> {
+        let commitment = self.commitments.get(player).ok_or(ShuffleError::UnknownPlayer(player))?;
+        if commit(player, &secret) != *commitment {
+            return Err(ShuffleError::CommitmentMismatch(player));
+        }
+        self.secrets[player] = Some(secret);
+        Ok(())
+    }
+
+    pub fn is_complete(&self) -> bool {
+        self.secrets.iter().all(Option::is_some)
+    }
+
+    // Combined seed, available once every player has revealed
+    pub fn seed(&self) -> Result<Digest, ShuffleError> {
+        let mut hasher = Sha256::new().chain_update(SEED_DOMAIN);
+        for (player, secret) in self.secrets.iter().enumerate() {
+            hasher.update(secret.ok_or(ShuffleError::MissingReveal(player))?);
+        }
+        Ok(hasher.finalize().into())
+    }
+
+    pub fn deck(&self) -> Result<Vec<String>, ShuffleError> {
+        Ok(shuffled_deck(&self.seed()?))
+    }
+}
+
+// Re-derives a finished hand's deck from the published commitments and revealed secrets
+pub fn audit(commitments: &[Digest], secrets: &[Secret]) -> Result<Vec<String>, ShuffleError> {
+    let mut round = ShuffleRound::new(commitments.to_vec());
+    for (player, secret) in secrets.iter().enumerate() {
+        round.reveal(player, *secret)?;
+    }
+    round.deck()
+}
+
+// All 52 cards in the notation `winning_hands` reads ("TH", "AS", ...), shuffled by `seed`
+pub fn shuffled_deck(seed: &Digest) -> Vec<String> {
+    let mut deck: Vec<String> = SUITS
+        .chars()
+        .flat_map(|suit| RANKS.chars().map(move |rank| format!("{rank}{suit}")))
+        .collect();
+    shuffle(&mut deck, seed);
+    deck
+}
+
+// Fisher–Yates driven by a SHA-256 counter stream, so every implementation deals the same order
+pub fn shuffle<T>(items: &mut [T], seed: &Digest) {
+    let mut stream = Stream::new(seed);
+    for i in (1..items.len()).rev() {
+        let j = stream.below(i as u64 + 1) as usize;
+        items.swap(i, j);
+    }
+}
+
+struct Stream {
+    seed: Digest,
+    counter: u64,
+    block: Digest,
+    offset: usize,
+}
+
+impl Stream {
+    fn new(seed: &Digest) -> Self {
+        Stream { seed: *seed, counter: 0, block: [0; 32], offset: 32 }
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        if self.offset == 32 {
+            self.block = Sha256::new()
+                .chain_update(STREAM_DOMAIN)
+                .chain_update(self.seed)
+                .chain_update(self.counter.to_le_bytes())
+                .finalize()
+                .into();
+            self.counter += 1;
+            self.offset = 0;
+        }
+        let bytes = self.block[self.offset..self.offset + 8].try_into().unwrap();
+        self.offset += 8;
+        u64::from_le_bytes(bytes)
+    }
+
+    // Uniform in 0..bound; rejection sampling avoids modulo bias
+    fn below(&mut self, bound: u64) -> u64 {
+        let zone = u64::MAX - u64::MAX % bound;
+        loop {
+            let value = self.next_u64();
+            if value < zone {
+                return value % bound;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SECRETS: [Secret; 3] = [[1; 32], [2; 32], [3; 32]];
+
+    fn commitments() -> Vec<Digest> {
+        SECRETS.iter().enumerate().map(|(player, secret)| commit(player, secret)).collect()
+    }
+
+    #[test]
+    fn reveals_complete_the_round() {
+        let mut round = ShuffleRound::new(commitments());
+        assert_eq!(round.seed(), Err(ShuffleError::MissingReveal(0)));
+        for (player, secret) in SECRETS.iter().enumerate() {
+            assert!(!round.is_complete());
+            round.reveal(player, *secret).unwrap();
+        }
+        assert!(round.is_complete());
+        assert_eq!(round.deck().unwrap(), audit(&commitments(), &SECRETS).unwrap());
+    }
+
+    #[test]
+    fn rejects_a_tampered_reveal() {
+        let mut round = ShuffleRound::new(commitments());
+        let mut tampered = SECRETS[1];
+        tampered[31] ^= 1;
+        assert_eq!(round.reveal(1, tampered), Err(ShuffleError::CommitmentMismatch(1)));
+        // Another seat's secret doesn't open this seat's commitment
+        assert_eq!(round.reveal(1, SECRETS[0]), Err(ShuffleError::CommitmentMismatch(1)));
+        assert_eq!(round.reveal(3, SECRETS[0]), Err(ShuffleError::UnknownPlayer(3)));
+
+        let mut secrets = SECRETS;
+        secrets[2] = [9; 32];
+        assert_eq!(audit(&commitments(), &secrets), Err(ShuffleError::CommitmentMismatch(2)));
+    }
+
+    #[test]
+    fn commitments_bind_the_seat() {
+        assert_ne!(commit(0, &SECRETS[0]), commit(1, &SECRETS[0]));
+    }
+
+    #[test]
+    fn deck_is_a_permutation_fixed_by_the_seed() {
+        let deck = audit(&commitments(), &SECRETS).unwrap();
+        assert_eq!(deck, audit(&commitments(), &SECRETS).unwrap());
+
+        let mut sorted = deck.clone();
+        sorted.sort();
+        sorted.dedup();
+        assert_eq!(sorted.len(), 52);
+        assert!(deck.contains(&"TH".to_string()));
+
+        // Changing any one secret changes the deal
+        let mut secrets = SECRETS;
+        secrets[0] = [7; 32];
+        let other: Vec<Digest> = secrets.iter().enumerate().map(|(p, s)| commit(p, s)).collect();
+        assert_ne!(audit(&other, &secrets).unwrap(), deck);
+    }
+}
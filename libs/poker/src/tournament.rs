@@ -0,0 +1,348 @@
+use std::error::Error;
+use std::fmt;
+use std::time::Duration;
+
+pub type PlayerId = u32;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BlindLevel {
+    pub small_blind: u64,
+    pub big_blind: u64,
+    pub ante: u64,
+    pub duration: Duration,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TournamentError {
+    NoLevels,
+    NotEnoughPlayers,
+    NoSeats,
+    UnknownPlayer(PlayerId),
+    InvalidPayouts, // shares must not exceed 100%
+}
+
+impl fmt::Display for TournamentError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TournamentError::NoLevels => write!(f, "tournament needs at least one blind level"),
+            TournamentError::NotEnoughPlayers => write!(f, "tournament needs at least two players"),
+            TournamentError::NoSeats => write!(f, "tables need at least two seats"),
+            TournamentError::UnknownPlayer(id) => write!(f, "player {id} is not seated"),
+            TournamentError::InvalidPayouts => write!(f, "payout shares exceed 10000 bps"),
+        }
+    }
+}
+
+impl Error for TournamentError {}
+
+// A player moved by `Tournament::balance`; table indices refer to the layout before balancing
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Move {
+    pub player: PlayerId,
+    pub from: usize,
+    pub to: usize,
+}
+
+// Clock and seating for a multi-table tournament. The server drives it with `tick` and reports
+// busts with `eliminate`; nothing here reads the wall clock.
+#[derive(Debug, Clone)]
+pub struct Tournament {
+    levels: Vec<BlindLevel>,
+    level: usize,
+    elapsed_in_level: Duration,
+    seats_per_table: usize,
+    tables: Vec<Vec<PlayerId>>,
+    eliminated: Vec<PlayerId>, // bust order, first out first
+    entrants: usize,
+}
+
+impl Tournament {
+    // Seats players round-robin over as few tables as fit them
+    pub fn new(
+        levels: Vec<BlindLevel>,
+        seats_per_table: usize,
+        players: &[PlayerId],
+    ) -> Result<Self, TournamentError> {
+        if levels.is_empty() {
+            return Err(TournamentError::NoLevels);
+        }
+        if seats_per_table < 2 {
+            return Err(TournamentError::NoSeats);
+        }
+        if players.len() < 2 {
+            return Err(TournamentError::NotEnoughPlayers);
+        }
+
+        let table_count = players.len().div_ceil(seats_per_table);
+        let mut tables = vec![Vec::with_capacity(seats_per_table); table_count];
+        for (i, &player) in players.iter().enumerate() {
+            tables[i % table_count].push(player);
+        }
+
+        Ok(Tournament {
+            levels,
+            level: 0,
+            elapsed_in_level: Duration::ZERO,
+            seats_per_table,
+            tables,
+            eliminated: Vec::new(),
+            entrants: players.len(),
+        })
+    }
+
+    pub fn current_level(&self) -> &BlindLevel {
+        &self.levels[self.level]
+    }
+
+    pub fn level_index(&self) -> usize {
+        self.level
+    }
+
+    // `None` once the last level is reached; it then runs until the tournament ends
+    pub fn time_to_next_level(&self) -> Option<Duration> {
+        if self.level + 1 == self.levels.len() {
+            return None;
+        }
+        Some(self.current_level().duration.saturating_sub(self.elapsed_in_level))
+    }
+
+    // Advances the clock, possibly across several levels; returns the new level if it changed
+    pub fn tick(&mut self, elapsed: Duration) -> Option<&BlindLevel> {
+        let start = self.level;
+        self.elapsed_in_level += elapsed;
+
+        while self.level + 1 < self.levels.len()
+            && self.elapsed_in_level >= self.levels[self.level].duration
+        {
+            self.elapsed_in_level -= self.levels[self.level].duration;
+            self.level += 1;
+        }
+
+        (self.level != start).then(|| self.current_level())
+    }
+
+    pub fn tables(&self) -> &[Vec<PlayerId>] {
+        &self.tables
+    }
+
+    pub fn players_remaining(&self) -> usize {
+        self.entrants - self.eliminated.len()
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.players_remaining() <= 1
+    }
+
+    // Removes a busted player and returns their finishing position (1 = winner).
+    // The last player standing is placed first automatically.
+    pub fn eliminate(&mut self, player: PlayerId) -> Result<usize, TournamentError> {
+        let table = self
+            .tables
+            .iter_mut()
+            .find(|table| table.contains(&player))
+            .ok_or(TournamentError::UnknownPlayer(player))?;
+        let position = self.entrants - self.eliminated.len();
+
+        table.retain(|&p| p != player);
+        self.tables.retain(|table| !table.is_empty());
+        self.eliminated.push(player);
+
+        if self.is_finished() {
+            let winner = self.tables.iter().flatten().copied().next();
+            self.eliminated.extend(winner);
+            self.tables.clear();
+        }
+        Ok(position)
+    }
+
+    // Players in finishing order so far, best first; only complete once `is_finished`
+    pub fn standings(&self) -> Vec<PlayerId> {
+        self.eliminated.iter().rev().copied().collect()
+    }
+
+    // Breaks tables that are no longer needed, then evens out the rest so no two differ by
+    // more than one player. Players are always moved from the back of a table.
+    pub fn balance(&mut self) -> Vec<Move> {
+        let mut moves = Vec::new();
+        if self.tables.is_empty() {
+            return moves;
+        }
+
+        let needed = self.players_remaining().div_ceil(self.seats_per_table);
+        let mut open: Vec<usize> = (0..self.tables.len()).collect();
+
+        while open.len() > needed {
+            let broken = self.smallest_of(&open);
+            open.retain(|&i| i != broken);
+            for player in std::mem::take(&mut self.tables[broken]) {
+                let to = self.smallest_of(&open);
+                self.tables[to].push(player);
+                moves.push(Move { player, from: broken, to });
+            }
+        }
+
+        loop {
+            let (largest, smallest) = (self.largest_of(&open), self.smallest_of(&open));
+            if self.tables[largest].len() <= self.tables[smallest].len() + 1 {
+                break;
+            }
+            let player = self.tables[largest].pop().expect("largest table is not empty");
+            self.tables[smallest].push(player);
+            moves.push(Move { player, from: largest, to: smallest });
+        }
+
+        self.tables.retain(|table| !table.is_empty());
+        moves
+    }
+
+    fn smallest_of(&self, open: &[usize]) -> usize {
+        open.iter().copied().min_by_key(|&i| self.tables[i].len()).unwrap_or(0)
+    }
+
+    fn largest_of(&self, open: &[usize]) -> usize {
+        open.iter().copied().max_by_key(|&i| self.tables[i].len()).unwrap_or(0)
+    }
+}
+
+// Splits `prize_pool` by finishing position, `shares_bps[0]` going to the winner.
+// When the shares total 10_000 bps, rounding dust goes to first place so the whole pool is paid
+// out; below that, the unallocated share and its dust stay unpaid.
+pub fn payouts(prize_pool: u64, shares_bps: &[u64]) -> Result<Vec<u64>, TournamentError> {
+    let total_bps = shares_bps
+        .iter()
+        .try_fold(0u64, |total, &bps| total.checked_add(bps))
+        .ok_or(TournamentError::InvalidPayouts)?;
+    if total_bps > 10_000 {
+        return Err(TournamentError::InvalidPayouts);
+    }
+
+    let mut amounts: Vec<u64> = shares_bps
+        .iter()
+        .map(|&bps| (prize_pool as u128 * bps as u128 / 10_000) as u64)
+        .collect();
+
+    if total_bps == 10_000 {
+        let paid: u64 = amounts.iter().sum();
+        if let Some(first) = amounts.first_mut() {
+            *first += prize_pool - paid;
+        }
+    }
+    Ok(amounts)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn level(big_blind: u64, minutes: u64) -> BlindLevel {
+        BlindLevel {
+            small_blind: big_blind / 2,
+            big_blind,
+            ante: big_blind / 10,
+            duration: Duration::from_secs(60 * minutes),
+        }
+    }
+
+    fn table_sizes(tournament: &Tournament) -> Vec<usize> {
+        tournament.tables().iter().map(Vec::len).collect()
+    }
+
+    #[test]
+    fn new_validates_its_inputs() {
+        let players = [1, 2, 3];
+        assert_eq!(Tournament::new(vec![], 9, &players).unwrap_err(), TournamentError::NoLevels);
+        assert_eq!(Tournament::new(vec![level(20, 10)], 1, &players).unwrap_err(), TournamentError::NoSeats);
+        assert_eq!(
+            Tournament::new(vec![level(20, 10)], 9, &[1]).unwrap_err(),
+            TournamentError::NotEnoughPlayers
+        );
+    }
+
+    #[test]
+    fn clock_moves_through_levels() {
+        let levels = vec![level(20, 10), level(40, 10), level(100, 15)];
+        let mut tournament = Tournament::new(levels.clone(), 9, &[1, 2]).unwrap();
+        assert_eq!(tournament.time_to_next_level(), Some(Duration::from_secs(600)));
+
+        assert_eq!(tournament.tick(Duration::from_secs(599)), None);
+        assert_eq!(tournament.time_to_next_level(), Some(Duration::from_secs(1)));
+
+        // One long tick can cross several levels, carrying the remainder over
+        assert_eq!(tournament.tick(Duration::from_secs(700)), Some(&levels[2]));
+        assert_eq!(tournament.level_index(), 2);
+        assert_eq!(tournament.current_level().ante, 10);
+
+        // The last level runs until the end
+        assert_eq!(tournament.time_to_next_level(), None);
+        assert_eq!(tournament.tick(Duration::from_secs(3600)), None);
+        assert_eq!(tournament.level_index(), 2);
+    }
+
+    #[test]
+    fn seats_round_robin_over_as_few_tables_as_fit() {
+        let players: Vec<PlayerId> = (1..=10).collect();
+        let tournament = Tournament::new(vec![level(20, 10)], 4, &players).unwrap();
+        assert_eq!(table_sizes(&tournament), vec![4, 3, 3]);
+        assert_eq!(tournament.tables()[0], vec![1, 4, 7, 10]);
+    }
+
+    #[test]
+    fn balance_breaks_and_evens_tables() {
+        let players: Vec<PlayerId> = (1..=10).collect();
+        let mut tournament = Tournament::new(vec![level(20, 10)], 4, &players).unwrap();
+        for player in [2, 5, 8] {
+            tournament.eliminate(player).unwrap();
+        }
+        assert_eq!(table_sizes(&tournament), vec![4, 3]);
+        assert!(tournament.balance().is_empty());
+
+        for player in [1, 4, 3] {
+            tournament.eliminate(player).unwrap();
+        }
+        // Four left fit one table, so the smaller table breaks into the other
+        assert_eq!(table_sizes(&tournament), vec![2, 2]);
+        let moves = tournament.balance();
+        assert_eq!(moves.len(), 2);
+        assert!(moves.iter().all(|m| m.from != m.to));
+        assert_eq!(table_sizes(&tournament), vec![4]);
+    }
+
+    #[test]
+    fn balance_moves_players_from_the_largest_table() {
+        let players: Vec<PlayerId> = (1..=8).collect();
+        let mut tournament = Tournament::new(vec![level(20, 10)], 4, &players).unwrap();
+        for player in [2, 4] {
+            tournament.eliminate(player).unwrap();
+        }
+        assert_eq!(table_sizes(&tournament), vec![4, 2]);
+        assert_eq!(tournament.balance(), vec![Move { player: 7, from: 0, to: 1 }]);
+        assert_eq!(table_sizes(&tournament), vec![3, 3]);
+    }
+
+    #[test]
+    fn eliminations_fill_the_standings() {
+        let mut tournament = Tournament::new(vec![level(20, 10)], 9, &[1, 2, 3]).unwrap();
+        assert_eq!(tournament.eliminate(9), Err(TournamentError::UnknownPlayer(9)));
+        assert_eq!(tournament.eliminate(2), Ok(3));
+        assert!(!tournament.is_finished());
+        assert_eq!(tournament.eliminate(1), Ok(2));
+        assert!(tournament.is_finished());
+        assert_eq!(tournament.standings(), vec![3, 1, 2]);
+        assert!(tournament.tables().is_empty());
+    }
+
+    #[test]
+    fn payouts_split_the_pool() {
+        // Dust goes to first place when the shares cover the whole pool
+        assert_eq!(payouts(1_001, &[5_000, 3_000, 2_000]), Ok(vec![501, 300, 200]));
+        // Otherwise the unallocated share stays unpaid
+        assert_eq!(payouts(1_000, &[5_000, 3_000]), Ok(vec![500, 300]));
+        assert_eq!(payouts(1_000, &[]), Ok(vec![]));
+    }
+
+    #[test]
+    fn payouts_reject_shares_over_the_whole() {
+        assert_eq!(payouts(1_000, &[6_000, 4_001]), Err(TournamentError::InvalidPayouts));
+        assert_eq!(payouts(1_000, &[u64::MAX, 1]), Err(TournamentError::InvalidPayouts));
+    }
+}
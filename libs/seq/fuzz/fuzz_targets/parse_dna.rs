@@ -0,0 +1,32 @@
+// Parsers never panic, agree with each other, and parsed sequences survive every round trip
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use seq::{Dna, DnaSlice, Strictness};
+
+fuzz_target!(|data: &[u8]| {
+    // The byte parser sees raw input, including invalid UTF-8
+    let slice = DnaSlice::from_bytes(data);
+    let Ok(text) = std::str::from_utf8(data) else {
+        assert!(slice.is_err());
+        return;
+    };
+
+    let strict = Dna::parse(text);
+    assert_eq!(strict.as_ref().ok(), slice.ok().map(|s| s.to_dna()).as_ref());
+    if let Ok(dna) = &strict {
+        assert_eq!(Dna::parse_with(text, Strictness::IgnoreCase).as_ref(), Ok(dna));
+        assert_eq!(Dna::from_packed(&dna.to_packed().unwrap()), *dna);
+    }
+
+    for strictness in [Strictness::Strict, Strictness::IgnoreCase, Strictness::Iupac] {
+        let Ok(dna) = Dna::parse_with(text, strictness) else { continue };
+        assert_eq!(dna.reverse_complement().reverse_complement(), dna);
+        // dna -> rna -> dna
+        assert_eq!(dna.clone().into_rna().into_dna(), dna);
+        // The serde path reads back whatever any strictness built
+        assert_eq!(Dna::try_from(String::from(dna.clone())).as_ref(), Ok(&dna));
+        let _ = dna.gc_content();
+        let _ = dna.melting_temperature();
+    }
+});
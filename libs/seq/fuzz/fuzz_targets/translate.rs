@@ -0,0 +1,25 @@
+// Translation and ORF scanning never panic, whatever the input
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use seq::codon_tables::TABLES;
+use seq::translation::{translate, translate_frame};
+use seq::{Rna, Strictness};
+
+fuzz_target!(|data: &[u8]| {
+    let Ok(text) = std::str::from_utf8(data) else { return };
+
+    let protein = translate(text);
+    assert!(protein.len() <= text.len() / 3);
+    for table in TABLES {
+        for offset in 0..4 {
+            let _ = translate_frame(text, offset, table);
+        }
+    }
+
+    if let Ok(rna) = Rna::parse_with(text, Strictness::Iupac) {
+        for orf in rna.open_reading_frames(0) {
+            assert!(orf.start <= orf.end && orf.end <= text.len());
+        }
+    }
+});
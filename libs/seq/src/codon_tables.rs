@@ -0,0 +1,67 @@
+// NCBI genetic codes, indexed by codon with bases ordered U, C, A, G. Strings are copied from
+// NCBI's "AAs" and "Starts" rows, where 'M' in `starts` marks a codon that can initiate translation.
+#[derive(Debug, PartialEq, Eq)]
+pub struct CodonTable {
+    pub id: u8, // NCBI transl_table
+    pub name: &'static str,
+    pub amino_acids: &'static [u8; 64],
+    pub starts: &'static [u8; 64],
+}
+
+pub const STANDARD: CodonTable = CodonTable {
+    id: 1,
+    name: "Standard",
+    amino_acids: b"FFLLSSSSYY**CC*WLLLLPPPPHHQQRRRRIIIMTTTTNNKKSSRRVVVVAAAADDEEGGGG",
+    starts: b"---M------**--*----M---------------M----------------------------",
+};
+
+pub const VERTEBRATE_MITOCHONDRIAL: CodonTable = CodonTable {
+    id: 2,
+    name: "Vertebrate Mitochondrial",
+    amino_acids: b"FFLLSSSSYY**CCWWLLLLPPPPHHQQRRRRIIMMTTTTNNKKSS**VVVVAAAADDEEGGGG",
+    starts: b"----------**--------------------MMMM----------**---M------------",
+};
+
+pub const YEAST_MITOCHONDRIAL: CodonTable = CodonTable {
+    id: 3,
+    name: "Yeast Mitochondrial",
+    amino_acids: b"FFLLSSSSYY**CCWWTTTTPPPPHHQQRRRRIIMMTTTTNNKKSSRRVVVVAAAADDEEGGGG",
+    starts: b"----------**----------------------MM---------------M------------",
+};
+
+pub const MOLD_MITOCHONDRIAL: CodonTable = CodonTable {
+    id: 4,
+    name: "Mold, Protozoan, and Coelenterate Mitochondrial and Mycoplasma/Spiroplasma",
+    amino_acids: b"FFLLSSSSYY**CCWWLLLLPPPPHHQQRRRRIIIMTTTTNNKKSSRRVVVVAAAADDEEGGGG",
+    starts: b"--MM------**-------M------------MMMM---------------M------------",
+};
+
+pub const INVERTEBRATE_MITOCHONDRIAL: CodonTable = CodonTable {
+    id: 5,
+    name: "Invertebrate Mitochondrial",
+    amino_acids: b"FFLLSSSSYY**CCWWLLLLPPPPHHQQRRRRIIMMTTTTNNKKSSSSVVVVAAAADDEEGGGG",
+    starts: b"---M------**--------------------MMMM---------------M------------",
+};
+
+pub const BACTERIAL: CodonTable = CodonTable {
+    id: 11,
+    name: "Bacterial, Archaeal and Plant Plastid",
+    amino_acids: b"FFLLSSSSYY**CC*WLLLLPPPPHHQQRRRRIIIMTTTTNNKKSSRRVVVVAAAADDEEGGGG",
+    starts: b"---M------**--*----M------------MMMM---------------M------------",
+};
+
+pub const TABLES: [&CodonTable; 6] = [
+    &STANDARD,
+    &VERTEBRATE_MITOCHONDRIAL,
+    &YEAST_MITOCHONDRIAL,
+    &MOLD_MITOCHONDRIAL,
+    &INVERTEBRATE_MITOCHONDRIAL,
+    &BACTERIAL,
+];
+
+impl CodonTable {
+    // `None` for table ids not compiled in
+    pub fn by_id(id: u8) -> Option<&'static CodonTable> {
+        TABLES.iter().copied().find(|table| table.id == id)
+    }
+}
@@ -0,0 +1,184 @@
+use std::collections::HashMap;
+
+use crate::seq_error::{validate, KmerError, SequenceError, Strictness, DNA_ALPHABET};
+
+// Bases a packed k-mer key holds, 2 bits each
+pub const MAX_PACKED_K: usize = 32;
+
+pub fn try_count(nucleotide: char, dna: &str) -> Result<usize, SequenceError> {
+    count_with(nucleotide, dna, Strictness::Strict)
+}
+
+pub fn try_nucleotide_counts(dna: &str) -> Result<HashMap<char, usize>, SequenceError> {
+    nucleotide_counts_with(dna, Strictness::Strict)
+}
+
+pub fn count_with(nucleotide: char, dna: &str, strictness: Strictness) -> Result<usize, SequenceError> {
+    let nucleotide = strictness
+        .normalize(nucleotide, DNA_ALPHABET)
+        .ok_or(SequenceError::argument(nucleotide, DNA_ALPHABET))?;
+
+    let mut total = 0;
+    for (i, c) in dna.chars().enumerate() {
+        let c = strictness
+            .normalize(c, DNA_ALPHABET)
+            .ok_or(SequenceError::at(i, c, DNA_ALPHABET))?;
+        if c == nucleotide {
+            total += 1;
+        }
+    }
+
+    Ok(total)
+}
+
+// Ambiguity codes only show up as keys when present in the input
+pub fn nucleotide_counts_with(dna: &str, strictness: Strictness) -> Result<HashMap<char, usize>, SequenceError> {
+    let mut counts = HashMap::from([
+        ('A', 0),
+        ('C', 0),
+        ('G', 0),
+        ('T', 0),
+    ]);
+
+    for (i, c) in dna.chars().enumerate() {
+        let c = strictness
+            .normalize(c, DNA_ALPHABET)
+            .ok_or(SequenceError::at(i, c, DNA_ALPHABET))?;
+        *counts.entry(c).or_insert(0) += 1;
+    }
+
+    Ok(counts)
+}
+
+#[deprecated(note = "use `try_count`, which reports where the invalid nucleotide was found")]
+pub fn count(nucleotide: char, dna: &str) -> Result<usize, char> {
+    try_count(nucleotide, dna).map_err(|e| e.found)
+}
+
+#[deprecated(note = "use `try_nucleotide_counts`, which reports where the invalid nucleotide was found")]
+pub fn nucleotide_counts(dna: &str) -> Result<HashMap<char, usize>, char> {
+    try_nucleotide_counts(dna).map_err(|e| e.found)
+}
+
+pub fn kmer_counts(dna: &str, k: usize) -> Result<HashMap<&str, usize>, SequenceError> {
+    validate(dna, DNA_ALPHABET)?;
+
+    let mut counts = HashMap::new();
+    if k == 0 || k > dna.len() {
+        return Ok(counts);
+    }
+
+    // Validated input is pure ASCII, so byte windows are valid str slices
+    for i in 0..=dna.len() - k {
+        *counts.entry(&dna[i..i + k]).or_insert(0) += 1;
+    }
+
+    Ok(counts)
+}
+
+// Same as `kmer_counts` but keys are 2-bit packed k-mers (A=0, C=1, G=2, T=3); fails for
+// k > `MAX_PACKED_K`
+pub fn packed_kmer_counts(dna: &str, k: usize) -> Result<HashMap<u64, usize>, KmerError> {
+    if k > MAX_PACKED_K {
+        return Err(KmerError::TooLong { k, max: MAX_PACKED_K });
+    }
+    validate(dna, DNA_ALPHABET)?;
+
+    let mut counts = HashMap::new();
+    if k == 0 || k > dna.len() {
+        return Ok(counts);
+    }
+
+    let mask = if k == MAX_PACKED_K { u64::MAX } else { (1u64 << (2 * k)) - 1 };
+    let mut window = 0u64;
+
+    for (i, b) in dna.bytes().enumerate() {
+        window = ((window << 2) | encode_base(b)) & mask;
+        if i + 1 >= k {
+            *counts.entry(window).or_insert(0) += 1;
+        }
+    }
+
+    Ok(counts)
+}
+
+fn encode_base(b: u8) -> u64 {
+    match b {
+        b'A' => 0,
+        b'C' => 1,
+        b'G' => 2,
+        b'T' => 3,
+        _ => unreachable!(), // Already validated
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn unpack(mut packed: u64, k: usize) -> String {
+        let mut kmer = vec![b'A'; k];
+        for b in kmer.iter_mut().rev() {
+            *b = b"ACGT"[(packed & 3) as usize];
+            packed >>= 2;
+        }
+        String::from_utf8(kmer).unwrap()
+    }
+
+    #[test]
+    fn kmer_counts_of_a_known_sequence() {
+        let counts = kmer_counts("ACGTAC", 2).unwrap();
+        assert_eq!(counts, HashMap::from([("AC", 2), ("CG", 1), ("GT", 1), ("TA", 1)]));
+
+        let packed = packed_kmer_counts("ACGTAC", 2).unwrap();
+        assert_eq!(packed, HashMap::from([(0b0001, 2), (0b0110, 1), (0b1011, 1), (0b1100, 1)]));
+    }
+
+    #[test]
+    fn packed_counts_match_string_counts() {
+        let dna = "GATTACAGATTACACCGGTTAAGATTACA";
+        for k in [1, 3, 5, dna.len()] {
+            let counts = kmer_counts(dna, k).unwrap();
+            let unpacked: HashMap<String, usize> = packed_kmer_counts(dna, k)
+                .unwrap()
+                .into_iter()
+                .map(|(kmer, n)| (unpack(kmer, k), n))
+                .collect();
+            assert_eq!(unpacked.len(), counts.len());
+            for (kmer, n) in counts {
+                assert_eq!(unpacked[kmer], n, "k = {k}, kmer = {kmer}");
+            }
+        }
+    }
+
+    #[test]
+    fn packed_counts_keep_all_32_bases() {
+        let dna = "T".repeat(33);
+        assert_eq!(packed_kmer_counts(&dna, 32).unwrap(), HashMap::from([(u64::MAX, 2)]));
+    }
+
+    #[test]
+    fn out_of_range_k_counts_nothing() {
+        assert!(kmer_counts("ACGT", 0).unwrap().is_empty());
+        assert!(kmer_counts("ACGT", 5).unwrap().is_empty());
+        assert!(packed_kmer_counts("ACGT", 0).unwrap().is_empty());
+        assert!(packed_kmer_counts("ACGT", 5).unwrap().is_empty());
+    }
+
+    #[test]
+    fn packed_k_above_32_is_an_error() {
+        assert_eq!(
+            packed_kmer_counts(&"A".repeat(40), 33),
+            Err(KmerError::TooLong { k: 33, max: MAX_PACKED_K })
+        );
+    }
+
+    #[test]
+    fn invalid_nucleotide_is_reported() {
+        assert_eq!(kmer_counts("ACNT", 2).unwrap_err().position, Some(2));
+        assert_eq!(
+            packed_kmer_counts("ACNT", 2),
+            Err(KmerError::Sequence(SequenceError::at(2, 'N', DNA_ALPHABET)))
+        );
+    }
+}
@@ -0,0 +1,16 @@
+pub mod codon_tables;
+pub mod dna;
+pub mod orf;
+pub mod profile;
+pub mod rna;
+pub mod seq_error;
+// Also built for this crate's own tests, which use the strategies without the feature
+#[cfg(any(test, feature = "proptest"))]
+pub mod strategies;
+pub mod translation;
+
+pub use codon_tables::CodonTable;
+pub use orf::Orf;
+pub use profile::{Profile, WindowScore};
+pub use rna::{Dna, DnaSlice, PackedDna, Rna, RnaSlice};
+pub use seq_error::{KmerError, PackedDnaError, SequenceError, Strictness};
@@ -0,0 +1,128 @@
+use crate::codon_tables::CodonTable;
+use crate::rna::Rna;
+use crate::translation::{is_start_codon, is_start_codon_in, translate_codon, translate_codon_with, STOP};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Orf {
+    // 1, 2, 3 on the forward strand, -1, -2, -3 on the reverse complement
+    pub frame: i8,
+    // Half-open range on the forward strand, stop codon included
+    pub start: usize,
+    pub end: usize,
+    pub protein: String,
+}
+
+impl Rna {
+    // Every AUG..stop reading frame across all six frames whose protein has at least `min_protein_len` residues
+    pub fn open_reading_frames(&self, min_protein_len: usize) -> Vec<Orf> {
+        self.scan_frames(min_protein_len, |codon| is_start_codon(codon).then_some('M'), translate_codon)
+    }
+
+    // Same under another genetic code; any of the table's initiators opens a frame and reads as Met
+    pub fn open_reading_frames_with(&self, min_protein_len: usize, table: &CodonTable) -> Vec<Orf> {
+        self.scan_frames(
+            min_protein_len,
+            |codon| is_start_codon_in(codon, table).then_some('M'),
+            |codon| translate_codon_with(codon, table),
+        )
+    }
+
+    fn scan_frames(
+        &self,
+        min_protein_len: usize,
+        start: impl Fn(&[u8]) -> Option<char>,
+        translate: impl Fn(&[u8]) -> char,
+    ) -> Vec<Orf> {
+        let forward = self.as_str().as_bytes();
+        let reverse = self.reverse_complement();
+        let reverse = reverse.as_str().as_bytes();
+        let len = forward.len();
+
+        let mut orfs = Vec::new();
+        for offset in 0..3 {
+            for (start, end, protein) in scan_frame(forward, offset, &start, &translate) {
+                if protein.len() >= min_protein_len {
+                    orfs.push(Orf { frame: offset as i8 + 1, start, end, protein });
+                }
+            }
+            for (start, end, protein) in scan_frame(reverse, offset, &start, &translate) {
+                if protein.len() >= min_protein_len {
+                    orfs.push(Orf { frame: -(offset as i8 + 1), start: len - end, end: len - start, protein });
+                }
+            }
+        }
+
+        orfs
+    }
+}
+
+// ORFs without a stop codon before the end of the sequence are not reported
+fn scan_frame(
+    seq: &[u8],
+    offset: usize,
+    start: &impl Fn(&[u8]) -> Option<char>,
+    translate: &impl Fn(&[u8]) -> char,
+) -> Vec<(usize, usize, String)> {
+    let mut found = Vec::new();
+    let mut open: Option<(usize, String)> = None;
+
+    let mut i = offset;
+    while i + 3 <= seq.len() {
+        let codon = &seq[i..i + 3];
+        match open.as_mut() {
+            None => open = start(codon).map(|aa| (i, aa.to_string())),
+            Some((start, protein)) => {
+                let aa = translate(codon);
+                if aa == STOP {
+                    found.push((*start, i + 3, std::mem::take(protein)));
+                    open = None;
+                } else {
+                    protein.push(aa);
+                }
+            }
+        }
+        i += 3;
+    }
+
+    found
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::codon_tables::BACTERIAL;
+
+    #[test]
+    fn forward_orf() {
+        let rna = Rna::parse("CCAUGGCCUAAGG").unwrap();
+        assert_eq!(
+            rna.open_reading_frames(1),
+            vec![Orf { frame: 3, start: 2, end: 11, protein: "MA".to_string() }]
+        );
+    }
+
+    #[test]
+    fn reverse_strand_orf_has_forward_coordinates() {
+        // Reverse complement is GAUGAAAUAACC: AUG AAA UAA in its second frame
+        let rna = Rna::parse("GGUUAUUUCAUC").unwrap();
+        let orfs = rna.open_reading_frames(1);
+        assert_eq!(orfs, vec![Orf { frame: -2, start: 2, end: 11, protein: "MK".to_string() }]);
+        assert_eq!(&rna.as_str()[orfs[0].start..orfs[0].end], "UUAUUUCAU");
+    }
+
+    #[test]
+    fn unterminated_and_short_orfs_are_skipped() {
+        assert!(Rna::parse("AUGGCCGCC").unwrap().open_reading_frames(1).is_empty());
+        assert!(Rna::parse("AUGGCCUAA").unwrap().open_reading_frames(3).is_empty());
+    }
+
+    #[test]
+    fn alternative_initiators_open_frames_as_met() {
+        let rna = Rna::parse("GUGGCCUAA").unwrap();
+        assert!(rna.open_reading_frames(1).is_empty());
+        assert_eq!(
+            rna.open_reading_frames_with(1, &BACTERIAL),
+            vec![Orf { frame: 1, start: 0, end: 9, protein: "MA".to_string() }]
+        );
+    }
+}
@@ -0,0 +1,130 @@
+use crate::rna::{Dna, DnaSlice};
+
+// Score of the half-open window `start..end` of the sequence
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct WindowScore {
+    pub start: usize,
+    pub end: usize,
+    pub score: f64,
+}
+
+// Scores windows of `window` bases every `step` bases. Only full windows are scored, so a trailing
+// stretch shorter than `window` is left out, and a zero `window` or `step` yields nothing.
+#[derive(Debug, Clone)]
+pub struct Profile<'a> {
+    seq: &'a [u8],
+    window: usize,
+    step: usize,
+    next: usize,
+    score: fn(&[u8]) -> f64,
+}
+
+impl<'a> Profile<'a> {
+    fn new(seq: &'a str, window: usize, step: usize, score: fn(&[u8]) -> f64) -> Profile<'a> {
+        Profile { seq: seq.as_bytes(), window, step, next: 0, score }
+    }
+}
+
+impl Iterator for Profile<'_> {
+    type Item = WindowScore;
+
+    fn next(&mut self) -> Option<WindowScore> {
+        if self.window == 0 || self.step == 0 {
+            return None;
+        }
+        let start = self.next;
+        let end = start.checked_add(self.window).filter(|&end| end <= self.seq.len())?;
+        self.next = start.saturating_add(self.step);
+        Some(WindowScore { start, end, score: (self.score)(&self.seq[start..end]) })
+    }
+}
+
+// Fraction of G/C bases; ambiguity codes count as neither
+fn gc_fraction(window: &[u8]) -> f64 {
+    window.iter().filter(|&&b| b == b'G' || b == b'C').count() as f64 / window.len() as f64
+}
+
+// Longest single-base run as a fraction of the window: near 1.0 for homopolymer stretches such as
+// poly-A tails, which sequencers tend to miscall
+fn homopolymer_fraction(window: &[u8]) -> f64 {
+    let mut longest = 0;
+    let mut run = 0;
+    for (i, b) in window.iter().enumerate() {
+        run = if i > 0 && window[i - 1] == *b { run + 1 } else { 1 };
+        longest = longest.max(run);
+    }
+    longest as f64 / window.len() as f64
+}
+
+impl Dna {
+    // GC content of each window, see `Profile`
+    pub fn gc_profile(&self, window: usize, step: usize) -> Profile<'_> {
+        Profile::new(self.as_str(), window, step, gc_fraction)
+    }
+
+    // Low-complexity score of each window; flag windows whose score exceeds a QC threshold
+    pub fn homopolymer_profile(&self, window: usize, step: usize) -> Profile<'_> {
+        Profile::new(self.as_str(), window, step, homopolymer_fraction)
+    }
+}
+
+impl<'a> DnaSlice<'a> {
+    pub fn gc_profile(&self, window: usize, step: usize) -> Profile<'a> {
+        Profile::new(self.as_str(), window, step, gc_fraction)
+    }
+
+    pub fn homopolymer_profile(&self, window: usize, step: usize) -> Profile<'a> {
+        Profile::new(self.as_str(), window, step, homopolymer_fraction)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn scores(profile: Profile<'_>) -> Vec<(usize, usize, f64)> {
+        profile.map(|w| (w.start, w.end, w.score)).collect()
+    }
+
+    #[test]
+    fn gc_profile_of_a_known_sequence() {
+        let dna = Dna::parse("GGCCATATGC").unwrap();
+        assert_eq!(
+            scores(dna.gc_profile(4, 3)),
+            vec![(0, 4, 1.0), (3, 7, 0.25), (6, 10, 0.5)]
+        );
+    }
+
+    #[test]
+    fn trailing_partial_window_is_left_out() {
+        let dna = Dna::parse("GGCCATA").unwrap();
+        let windows = scores(dna.gc_profile(4, 2));
+        assert_eq!(windows, vec![(0, 4, 1.0), (2, 6, 0.5)]);
+    }
+
+    #[test]
+    fn homopolymer_profile_flags_runs() {
+        let dna = Dna::parse("ACGTAAAAAAAA").unwrap();
+        assert_eq!(
+            scores(dna.homopolymer_profile(4, 4)),
+            vec![(0, 4, 0.25), (4, 8, 1.0), (8, 12, 1.0)]
+        );
+    }
+
+    #[test]
+    fn slice_profile_matches_owned_profile() {
+        let slice = DnaSlice::new("GATTACAGGC").unwrap();
+        let dna = slice.to_dna();
+        assert_eq!(scores(slice.gc_profile(3, 1)), scores(dna.gc_profile(3, 1)));
+        assert_eq!(scores(slice.homopolymer_profile(3, 1)), scores(dna.homopolymer_profile(3, 1)));
+    }
+
+    #[test]
+    fn degenerate_windows_yield_nothing() {
+        let dna = Dna::parse("ACGT").unwrap();
+        assert_eq!(dna.gc_profile(0, 1).count(), 0);
+        assert_eq!(dna.gc_profile(2, 0).count(), 0);
+        assert_eq!(dna.gc_profile(5, 1).count(), 0);
+        assert_eq!(dna.gc_profile(usize::MAX, 1).count(), 0);
+    }
+}
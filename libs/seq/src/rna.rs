@@ -0,0 +1,411 @@
+use std::fmt;
+
+use crate::seq_error::{validate, PackedDnaError, SequenceError, Strictness, DNA_ALPHABET, RNA_ALPHABET};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(try_from = "String", into = "String"))]
+pub struct Dna(String);
+
+// 2 bits per base (A=0, C=1, G=2, T=3), first base in the high bits of the first byte
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(try_from = "RawPackedDna"))]
+pub struct PackedDna {
+    len: usize,
+    bytes: Vec<u8>,
+}
+
+// Unchecked wire form of `PackedDna`, validated by `PackedDna::from_bytes` on the way in
+#[cfg(feature = "serde")]
+#[derive(Deserialize)]
+struct RawPackedDna {
+    len: usize,
+    bytes: Vec<u8>,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct Rna(String);
+
+// Borrowed views validated once over the caller's buffer, no allocation
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DnaSlice<'a>(&'a str);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RnaSlice<'a>(&'a str);
+
+fn normalize(seq: &str, alphabet: &'static str, strictness: Strictness) -> Result<String, SequenceError> {
+    seq.chars()
+        .enumerate()
+        .map(|(i, c)| strictness.normalize(c, alphabet).ok_or(SequenceError::at(i, c, alphabet)))
+        .collect()
+}
+
+// IUPAC-aware DNA complement; ambiguity codes map to their complementary code
+fn complement(c: char) -> char {
+    match c {
+        'G' => 'C',
+        'C' => 'G',
+        'T' => 'A',
+        'A' => 'T',
+        'R' => 'Y',
+        'Y' => 'R',
+        'K' => 'M',
+        'M' => 'K',
+        'B' => 'V',
+        'V' => 'B',
+        'D' => 'H',
+        'H' => 'D',
+        'S' | 'W' | 'N' => c,
+        _ => unreachable!(), // Already validated in `parse_with`
+    }
+}
+
+fn validate_bytes(seq: &[u8], alphabet: &'static str) -> Result<(), SequenceError> {
+    match seq.iter().position(|&b| !alphabet.as_bytes().contains(&b)) {
+        Some(i) => Err(SequenceError::at(i, seq[i] as char, alphabet)),
+        None => Ok(()),
+    }
+}
+
+impl Dna {
+    pub fn parse(dna: &str) -> Result<Dna, SequenceError> {
+        validate(dna, DNA_ALPHABET)?;
+        Ok(Dna(dna.to_string()))
+    }
+
+    // Lowercase input is stored uppercased
+    pub fn parse_with(dna: &str, strictness: Strictness) -> Result<Dna, SequenceError> {
+        normalize(dna, DNA_ALPHABET, strictness).map(Dna)
+    }
+
+    #[deprecated(note = "use `Dna::parse`, which returns a `SequenceError`")]
+    pub fn new(dna: &str) -> Result<Dna, usize> {
+        Dna::parse(dna).map_err(|e| e.position.unwrap_or_default())
+    }
+
+    pub fn into_rna(self) -> Rna {
+        let transcribed = self.0.chars().map(|c| {
+            match complement(c) {
+                'T' => 'U',
+                other => other,
+            }
+        }).collect();
+        Rna(transcribed)
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    pub fn reverse_complement(&self) -> Dna {
+        let complemented = self.0.chars().rev().map(complement).collect();
+        Dna(complemented)
+    }
+
+    // Fraction of G/C bases, 0.0 for an empty sequence
+    pub fn gc_content(&self) -> f64 {
+        if self.0.is_empty() {
+            return 0.0;
+        }
+        self.gc_count() as f64 / self.0.len() as f64
+    }
+
+    // Melting temperature in °C: Wallace rule below 14 bases, GC-adjusted formula above
+    pub fn melting_temperature(&self) -> f64 {
+        let len = self.0.len();
+        let gc = self.gc_count();
+        let at = len - gc;
+
+        if len < 14 {
+            (2 * at + 4 * gc) as f64
+        } else {
+            64.9 + 41.0 * (gc as f64 - 16.4) / len as f64
+        }
+    }
+
+    // `None` if the sequence holds IUPAC ambiguity codes, which need more than 2 bits
+    pub fn to_packed(&self) -> Option<PackedDna> {
+        let mut bytes = vec![0u8; self.0.len().div_ceil(4)];
+        for (i, b) in self.0.bytes().enumerate() {
+            let code = match b {
+                b'A' => 0,
+                b'C' => 1,
+                b'G' => 2,
+                b'T' => 3,
+                _ => return None,
+            };
+            bytes[i / 4] |= code << (6 - 2 * (i % 4));
+        }
+        Some(PackedDna { len: self.0.len(), bytes })
+    }
+
+    pub fn from_packed(packed: &PackedDna) -> Dna {
+        let unpacked = (0..packed.len).map(|i| {
+            match (packed.bytes[i / 4] >> (6 - 2 * (i % 4))) & 0b11 {
+                0 => 'A',
+                1 => 'C',
+                2 => 'G',
+                _ => 'T',
+            }
+        }).collect();
+        Dna(unpacked)
+    }
+
+    fn gc_count(&self) -> usize {
+        self.0.chars().filter(|&c| c == 'G' || c == 'C').count()
+    }
+}
+
+impl PackedDna {
+    // `bytes` must hold exactly `len.div_ceil(4)` bytes with zeroed padding, as `Dna::to_packed`
+    // produces, so equal sequences always have equal bytes
+    pub fn from_bytes(len: usize, bytes: Vec<u8>) -> Result<PackedDna, PackedDnaError> {
+        let expected = len.div_ceil(4);
+        if bytes.len() != expected {
+            return Err(PackedDnaError::Length { len, expected, found: bytes.len() });
+        }
+        // A partial final byte keeps its bases in the high bits
+        let last_byte = bytes.last().copied().unwrap_or(0);
+        let bases_in_last = len % 4;
+        if bases_in_last != 0 && last_byte & (0xff >> (2 * bases_in_last)) != 0 {
+            return Err(PackedDnaError::Padding { len, last_byte });
+        }
+        Ok(PackedDna { len, bytes })
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.bytes
+    }
+}
+
+#[cfg(feature = "serde")]
+impl TryFrom<RawPackedDna> for PackedDna {
+    type Error = PackedDnaError;
+
+    fn try_from(raw: RawPackedDna) -> Result<PackedDna, PackedDnaError> {
+        PackedDna::from_bytes(raw.len, raw.bytes)
+    }
+}
+
+// Accepts everything `Dna::parse_with` does, so any `Dna` reads back what it serialized
+impl TryFrom<String> for Dna {
+    type Error = SequenceError;
+
+    fn try_from(dna: String) -> Result<Dna, SequenceError> {
+        Dna::parse_with(&dna, Strictness::Iupac)
+    }
+}
+
+impl fmt::Display for Dna {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl From<Dna> for String {
+    fn from(dna: Dna) -> String {
+        dna.0
+    }
+}
+
+impl Rna {
+    pub fn parse(rna: &str) -> Result<Rna, SequenceError> {
+        validate(rna, RNA_ALPHABET)?;
+        Ok(Rna(rna.to_string()))
+    }
+
+    // Lowercase input is stored uppercased
+    pub fn parse_with(rna: &str, strictness: Strictness) -> Result<Rna, SequenceError> {
+        normalize(rna, RNA_ALPHABET, strictness).map(Rna)
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    pub fn reverse_complement(&self) -> Rna {
+        let complemented = self.0.chars().rev().map(|c| {
+            match complement(if c == 'U' { 'T' } else { c }) {
+                'T' => 'U',
+                other => other,
+            }
+        }).collect();
+        Rna(complemented)
+    }
+
+    // Reverse transcription, the inverse of `Dna::into_rna`
+    pub fn into_dna(self) -> Dna {
+        let transcribed = self.0.chars().map(|c| complement(if c == 'U' { 'T' } else { c })).collect();
+        Dna(transcribed)
+    }
+
+    #[deprecated(note = "use `Rna::parse`, which returns a `SequenceError`")]
+    pub fn new(rna: &str) -> Result<Rna, usize> {
+        Rna::parse(rna).map_err(|e| e.position.unwrap_or_default())
+    }
+}
+
+impl fmt::Display for Rna {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl<'a> DnaSlice<'a> {
+    pub fn new(dna: &'a str) -> Result<DnaSlice<'a>, SequenceError> {
+        validate(dna, DNA_ALPHABET)?;
+        Ok(DnaSlice(dna))
+    }
+
+    pub fn from_bytes(dna: &'a [u8]) -> Result<DnaSlice<'a>, SequenceError> {
+        validate_bytes(dna, DNA_ALPHABET)?;
+        // All bytes are ASCII nucleotides at this point
+        Ok(DnaSlice(std::str::from_utf8(dna).unwrap()))
+    }
+
+    pub fn as_str(&self) -> &'a str {
+        self.0
+    }
+
+    pub fn to_dna(&self) -> Dna {
+        Dna(self.0.to_string())
+    }
+}
+
+impl<'a> RnaSlice<'a> {
+    pub fn new(rna: &'a str) -> Result<RnaSlice<'a>, SequenceError> {
+        validate(rna, RNA_ALPHABET)?;
+        Ok(RnaSlice(rna))
+    }
+
+    pub fn from_bytes(rna: &'a [u8]) -> Result<RnaSlice<'a>, SequenceError> {
+        validate_bytes(rna, RNA_ALPHABET)?;
+        // All bytes are ASCII nucleotides at this point
+        Ok(RnaSlice(std::str::from_utf8(rna).unwrap()))
+    }
+
+    pub fn as_str(&self) -> &'a str {
+        self.0
+    }
+
+    pub fn to_rna(&self) -> Rna {
+        Rna(self.0.to_string())
+    }
+}
+
+impl<'a> From<DnaSlice<'a>> for Dna {
+    fn from(slice: DnaSlice<'a>) -> Dna {
+        slice.to_dna()
+    }
+}
+
+impl<'a> From<RnaSlice<'a>> for Rna {
+    fn from(slice: RnaSlice<'a>) -> Rna {
+        slice.to_rna()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn empty_sequence() {
+        let dna = Dna::parse("").unwrap();
+        assert_eq!(dna.reverse_complement(), dna);
+        assert_eq!(dna.gc_content(), 0.0);
+        assert_eq!(dna.melting_temperature(), 0.0);
+        assert_eq!(Rna::parse("").unwrap().reverse_complement(), Rna::parse("").unwrap());
+    }
+
+    #[test]
+    fn palindrome_is_its_own_reverse_complement() {
+        let dna = Dna::parse("GAATTC").unwrap(); // EcoRI site
+        assert_eq!(dna.reverse_complement(), dna);
+        let rna = Rna::parse("GAAUUC").unwrap();
+        assert_eq!(rna.reverse_complement(), rna);
+    }
+
+    #[test]
+    fn reverse_complement_reverses_and_complements() {
+        assert_eq!(Dna::parse("AACG").unwrap().reverse_complement().as_str(), "CGTT");
+        assert_eq!(Rna::parse("AACG").unwrap().reverse_complement().as_str(), "CGUU");
+        let iupac = Dna::parse_with("ARN", Strictness::Iupac).unwrap();
+        assert_eq!(iupac.reverse_complement().as_str(), "NYT");
+    }
+
+    #[test]
+    fn gc_content_is_a_fraction() {
+        assert_eq!(Dna::parse("GCGC").unwrap().gc_content(), 1.0);
+        assert_eq!(Dna::parse("ATGC").unwrap().gc_content(), 0.5);
+        assert_eq!(Dna::parse("ATAT").unwrap().gc_content(), 0.0);
+    }
+
+    #[test]
+    fn melting_temperature_switches_formula_at_14_bases() {
+        // Wallace rule: 2 °C per A/T, 4 °C per G/C
+        let thirteen = Dna::parse("GGGGAAAAAAAAA").unwrap();
+        assert_eq!(thirteen.melting_temperature(), (2 * 9 + 4 * 4) as f64);
+
+        // GC-adjusted from 14 bases on
+        let fourteen = Dna::parse("GGGGAAAAAAAAAA").unwrap();
+        let expected = 64.9 + 41.0 * (4.0 - 16.4) / 14.0;
+        assert!((fourteen.melting_temperature() - expected).abs() < 1e-9);
+        assert_ne!(fourteen.melting_temperature(), (2 * 10 + 4 * 4) as f64);
+    }
+
+    #[test]
+    fn iupac_dna_converts_back_from_its_string() {
+        let dna = Dna::parse_with("acgtRYN", Strictness::Iupac).unwrap();
+        assert_eq!(Dna::try_from(String::from(dna.clone())), Ok(dna));
+        assert_eq!(Dna::try_from("ACXT".to_string()).unwrap_err().position, Some(2));
+    }
+
+    #[test]
+    fn packed_round_trip() {
+        let dna = Dna::parse("ACGTTGCAG").unwrap();
+        let packed = dna.to_packed().unwrap();
+        assert_eq!(packed.as_bytes().len(), 3);
+        assert_eq!(Dna::from_packed(&packed), dna);
+        assert_eq!(PackedDna::from_bytes(packed.len(), packed.as_bytes().to_vec()), Ok(packed));
+    }
+
+    #[test]
+    fn from_bytes_checks_byte_count() {
+        assert!(PackedDna::from_bytes(0, vec![]).is_ok());
+        assert!(PackedDna::from_bytes(4, vec![0]).is_ok());
+        assert!(PackedDna::from_bytes(5, vec![0, 0]).is_ok());
+        assert_eq!(
+            PackedDna::from_bytes(5, vec![0]),
+            Err(PackedDnaError::Length { len: 5, expected: 2, found: 1 })
+        );
+        assert!(PackedDna::from_bytes(1, vec![0, 0]).is_err());
+    }
+
+    #[test]
+    fn from_bytes_rejects_set_padding() {
+        // "ACGTA": the fifth base is the top two bits of the second byte
+        let packed = Dna::parse("ACGTA").unwrap().to_packed().unwrap();
+        assert_eq!(PackedDna::from_bytes(5, packed.as_bytes().to_vec()), Ok(packed));
+        assert_eq!(
+            PackedDna::from_bytes(5, vec![0b0001_1011, 0b0000_0001]),
+            Err(PackedDnaError::Padding { len: 5, last_byte: 0b0000_0001 })
+        );
+        // T in the last base's slot is data, not padding
+        assert!(PackedDna::from_bytes(7, vec![0, 0b0000_1100]).is_ok());
+        assert!(PackedDna::from_bytes(7, vec![0, 0b0000_0011]).is_err());
+        assert!(PackedDna::from_bytes(8, vec![0, 0xff]).is_ok());
+    }
+}
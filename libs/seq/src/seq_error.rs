@@ -0,0 +1,131 @@
+use std::error::Error;
+use std::fmt;
+
+pub const DNA_ALPHABET: &str = "ACGT";
+pub const RNA_ALPHABET: &str = "ACGU";
+pub const IUPAC_CODES: &str = "RYSWKMBDHVN";
+
+// How forgiving validation is about input exported from other tools
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Strictness {
+    #[default]
+    Strict,
+    IgnoreCase,
+    Iupac, // also ignores case
+}
+
+impl Strictness {
+    // Returns the uppercased nucleotide if it is accepted under this strictness
+    pub fn normalize(self, c: char, alphabet: &'static str) -> Option<char> {
+        let c = match self {
+            Strictness::Strict => c,
+            Strictness::IgnoreCase | Strictness::Iupac => c.to_ascii_uppercase(),
+        };
+
+        if alphabet.contains(c) || (self == Strictness::Iupac && IUPAC_CODES.contains(c)) {
+            Some(c)
+        } else {
+            None
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SequenceError {
+    // Index of the offending character, `None` when it came from an argument rather than the sequence
+    pub position: Option<usize>,
+    pub found: char,
+    pub expected_alphabet: &'static str,
+}
+
+impl SequenceError {
+    pub fn at(position: usize, found: char, expected_alphabet: &'static str) -> Self {
+        SequenceError { position: Some(position), found, expected_alphabet }
+    }
+
+    pub fn argument(found: char, expected_alphabet: &'static str) -> Self {
+        SequenceError { position: None, found, expected_alphabet }
+    }
+}
+
+impl fmt::Display for SequenceError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.position {
+            Some(i) => write!(
+                f,
+                "invalid nucleotide {:?} at position {}, expected one of {}",
+                self.found, i, self.expected_alphabet
+            ),
+            None => write!(
+                f,
+                "invalid nucleotide {:?}, expected one of {}",
+                self.found, self.expected_alphabet
+            ),
+        }
+    }
+}
+
+impl Error for SequenceError {}
+
+// Strict check against `alphabet`, reporting the first character outside it
+pub(crate) fn validate(seq: &str, alphabet: &'static str) -> Result<(), SequenceError> {
+    match seq.chars().enumerate().find(|&(_, c)| !alphabet.contains(c)) {
+        Some((i, c)) => Err(SequenceError::at(i, c, alphabet)),
+        None => Ok(()),
+    }
+}
+
+// Why `packed_kmer_counts` couldn't count
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum KmerError {
+    Sequence(SequenceError),
+    // `k` needs more than the 64 bits a packed k-mer has
+    TooLong { k: usize, max: usize },
+}
+
+impl From<SequenceError> for KmerError {
+    fn from(e: SequenceError) -> Self {
+        KmerError::Sequence(e)
+    }
+}
+
+impl fmt::Display for KmerError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            KmerError::Sequence(e) => e.fmt(f),
+            KmerError::TooLong { k, max } => write!(f, "k = {} exceeds the packed maximum of {}", k, max),
+        }
+    }
+}
+
+impl Error for KmerError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            KmerError::Sequence(e) => Some(e),
+            KmerError::TooLong { .. } => None,
+        }
+    }
+}
+
+// `PackedDna` bytes that don't encode exactly its base count
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PackedDnaError {
+    Length { len: usize, expected: usize, found: usize }, // `len` bases
+    // Bits past the last base are set in the final byte; `Dna::to_packed` leaves them zero
+    Padding { len: usize, last_byte: u8 },
+}
+
+impl fmt::Display for PackedDnaError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            PackedDnaError::Length { len, expected, found } => {
+                write!(f, "{} packed bases need {} bytes, found {}", len, expected, found)
+            }
+            PackedDnaError::Padding { len, last_byte } => {
+                write!(f, "padding after {} packed bases is not zero in final byte {:#010b}", len, last_byte)
+            }
+        }
+    }
+}
+
+impl Error for PackedDnaError {}
@@ -0,0 +1,95 @@
+// proptest strategies for property tests of code built on this crate. Enable the `proptest`
+// feature in dev-dependencies.
+use proptest::prelude::*;
+use proptest::sample::select;
+
+use crate::seq_error::{DNA_ALPHABET, IUPAC_CODES, RNA_ALPHABET};
+
+fn over(symbols: Vec<char>, max_len: usize) -> impl Strategy<Value = String> {
+    prop::collection::vec(select(symbols), 0..=max_len).prop_map(|chars| chars.into_iter().collect())
+}
+
+// Sequences `Dna::parse` accepts
+pub fn dna(max_len: usize) -> impl Strategy<Value = String> {
+    over(DNA_ALPHABET.chars().collect(), max_len)
+}
+
+// Sequences `Rna::parse` accepts
+pub fn rna(max_len: usize) -> impl Strategy<Value = String> {
+    over(RNA_ALPHABET.chars().collect(), max_len)
+}
+
+// Mixed-case sequences with ambiguity codes, as `Dna::parse_with(_, Strictness::Iupac)` accepts
+pub fn iupac_dna(max_len: usize) -> impl Strategy<Value = String> {
+    let upper = DNA_ALPHABET.chars().chain(IUPAC_CODES.chars());
+    over(upper.clone().chain(upper.map(|c| c.to_ascii_lowercase())).collect(), max_len)
+}
+
+// Valid DNA with one character outside the alphabet, paired with that character's index
+pub fn invalid_dna(max_len: usize) -> impl Strategy<Value = (String, usize)> {
+    (dna(max_len), any::<char>().prop_filter("outside the DNA alphabet", |c| !DNA_ALPHABET.contains(*c)))
+        .prop_flat_map(|(dna, bad)| {
+            let len = dna.chars().count();
+            (Just(dna), Just(bad), 0..=len)
+        })
+        .prop_map(|(dna, bad, at)| {
+            let mut chars: Vec<char> = dna.chars().collect();
+            chars.insert(at, bad);
+            (chars.into_iter().collect(), at)
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::seq_error::Strictness;
+    use crate::translation::translate;
+    use crate::{Dna, Rna};
+
+    proptest! {
+        #[test]
+        fn dna_rna_dna_is_identity(seq in iupac_dna(200)) {
+            let dna = Dna::parse_with(&seq, Strictness::Iupac).unwrap();
+            prop_assert_eq!(dna.clone().into_rna().into_dna(), dna);
+        }
+
+        #[test]
+        fn display_round_trips_through_parse(seq in dna(200)) {
+            let dna = Dna::parse(&seq).unwrap();
+            prop_assert_eq!(dna.to_string(), seq);
+            prop_assert_eq!(Dna::parse(&dna.to_string()), Ok(dna));
+        }
+
+        #[test]
+        fn display_round_trips_through_parse_with(seq in iupac_dna(200)) {
+            let dna = Dna::parse_with(&seq, Strictness::Iupac).unwrap();
+            // Stored uppercased, so only the case may differ from the input
+            prop_assert_eq!(dna.to_string(), seq.to_ascii_uppercase());
+            prop_assert_eq!(Dna::parse_with(&dna.to_string(), Strictness::Iupac), Ok(dna));
+        }
+
+        #[test]
+        fn rna_display_round_trips_through_parse(seq in rna(200)) {
+            let rna = Rna::parse(&seq).unwrap();
+            prop_assert_eq!(Rna::parse(&rna.to_string()), Ok(rna));
+        }
+
+        #[test]
+        fn invalid_dna_reports_its_position((seq, at) in invalid_dna(200)) {
+            prop_assert_eq!(Dna::parse(&seq).unwrap_err().position, Some(at));
+        }
+
+        #[test]
+        fn translate_never_panics(text in any::<String>()) {
+            let protein = translate(&text);
+            prop_assert!(protein.chars().count() <= text.len() / 3);
+        }
+
+        #[test]
+        fn translate_valid_rna_has_no_unknown_residues(seq in rna(300)) {
+            let protein = translate(&seq);
+            prop_assert!(protein.chars().count() <= seq.len() / 3);
+            prop_assert!(!protein.contains('X'));
+        }
+    }
+}
@@ -0,0 +1,101 @@
+use crate::codon_tables::{CodonTable, STANDARD};
+
+pub const STOP: char = '*';
+
+fn base_index(b: u8) -> Option<usize> {
+    match b {
+        b'U' | b'T' => Some(0),
+        b'C' => Some(1),
+        b'A' => Some(2),
+        b'G' => Some(3),
+        _ => None,
+    }
+}
+
+fn codon_index(codon: &[u8]) -> Option<usize> {
+    match codon {
+        &[a, b, c] => Some(base_index(a)? * 16 + base_index(b)? * 4 + base_index(c)?),
+        _ => None,
+    }
+}
+
+// One-letter amino acid for a codon, 'X' when it contains an ambiguity code
+pub fn translate_codon(codon: &[u8]) -> char {
+    translate_codon_with(codon, &STANDARD)
+}
+
+pub fn translate_codon_with(codon: &[u8], table: &CodonTable) -> char {
+    codon_index(codon).map_or('X', |i| table.amino_acids[i] as char)
+}
+
+pub fn is_start_codon(codon: &[u8]) -> bool {
+    codon == b"AUG"
+}
+
+// Includes the table's alternative initiators, e.g. UUG and GUG in bacteria
+pub fn is_start_codon_in(codon: &[u8], table: &CodonTable) -> bool {
+    codon_index(codon).is_some_and(|i| table.starts[i] == b'M')
+}
+
+// Translates codon by codon until the first stop codon, dropping any trailing partial codon
+pub fn translate(rna: &str) -> String {
+    translate_with(rna, &STANDARD)
+}
+
+pub fn translate_with(rna: &str, table: &CodonTable) -> String {
+    rna.as_bytes()
+        .chunks_exact(3)
+        .map(|codon| translate_codon_with(codon, table))
+        .take_while(|&aa| aa != STOP)
+        .collect()
+}
+
+// Translation starting `offset` bases in (0, 1 or 2 for the three forward frames)
+pub fn translate_frame(rna: &str, offset: usize, table: &CodonTable) -> String {
+    translate_with(rna.get(offset..).unwrap_or(""), table)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::codon_tables::{VERTEBRATE_MITOCHONDRIAL, YEAST_MITOCHONDRIAL};
+
+    #[test]
+    fn standard_translation_stops_at_the_first_stop() {
+        assert_eq!(translate("AUGGCCUGAAAA"), "MA");
+        assert_eq!(translate("AUGGC"), "M");
+        assert_eq!(translate("AUGNNN"), "MX");
+    }
+
+    #[test]
+    fn vertebrate_mitochondrial_code() {
+        // UGA reads through as Trp, AUA is Met, and AGA stops
+        let rna = "AUGUGAAUAAAAAGAGGG";
+        assert_eq!(translate(rna), "M");
+        assert_eq!(translate_with(rna, &VERTEBRATE_MITOCHONDRIAL), "MWMK");
+    }
+
+    #[test]
+    fn yeast_mitochondrial_code() {
+        // CUN codes for Thr instead of Leu
+        assert_eq!(translate("CUUCUG"), "LL");
+        assert_eq!(translate_with("CUUCUG", &YEAST_MITOCHONDRIAL), "TT");
+    }
+
+    #[test]
+    fn alternative_start_codons() {
+        assert!(is_start_codon(b"AUG"));
+        assert!(!is_start_codon(b"AUA"));
+        assert!(is_start_codon_in(b"AUA", &VERTEBRATE_MITOCHONDRIAL));
+        assert!(!is_start_codon_in(b"UGA", &VERTEBRATE_MITOCHONDRIAL));
+        assert!(!is_start_codon_in(b"NNN", &STANDARD));
+    }
+
+    #[test]
+    fn frames_and_table_lookup() {
+        assert_eq!(translate_frame("GAUGGCC", 1, &STANDARD), "MA");
+        assert_eq!(translate_frame("AUG", 5, &STANDARD), "");
+        assert_eq!(CodonTable::by_id(2), Some(&VERTEBRATE_MITOCHONDRIAL));
+        assert_eq!(CodonTable::by_id(7), None);
+    }
+}
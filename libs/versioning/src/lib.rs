@@ -0,0 +1,135 @@
+// Schema versioning shared by the Anchor programs.
+//
+// Every account stores its layout version as the first byte after the 8-byte discriminator.
+// A program lists the body rewrites for each account type; `migrate` replays whichever steps
+// an account is missing and `rewrite_account` stores the result, growing the account if needed.
+// Accounts written before versioning have no version byte. A type that existed then declares
+// the length those accounts were allocated with, and `migrate` inserts the byte first.
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+
+pub const VERSION_OFFSET: usize = 8;
+
+// Rewrites the bytes after the version byte from one version to the next, `None` if they don't parse
+pub type Migration = fn(&[u8]) -> Option<Vec<u8>>;
+
+pub trait Versioned {
+    // `MIGRATIONS[i]` turns a version `i + 1` body into version `i + 2`
+    const MIGRATIONS: &'static [Migration] = &[];
+    const CURRENT_VERSION: u8 = 1 + Self::MIGRATIONS.len() as u8;
+    // Data length, discriminator included, of accounts written before versioning. Their body is
+    // the version 1 body without the version byte.
+    const UNVERSIONED_LEN: Option<usize> = None;
+
+    fn version(&self) -> u8;
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MigrationError {
+    TooShort,
+    UnknownVersion(u8),
+    StepFailed(u8),
+    Invalid, // migrated data doesn't deserialize as the current layout
+}
+
+// Full migrated account data, or `None` if the account is already current
+pub fn migrate<T: Versioned + AccountDeserialize>(
+    data: &[u8],
+) -> std::result::Result<Option<Vec<u8>>, MigrationError> {
+    // Version 0 -> 1: insert the version byte. A versioned account can have the same length, so
+    // this reading only wins if it migrates to a valid account.
+    if Some(data.len()) == T::UNVERSIONED_LEN {
+        let mut versioned = data[..VERSION_OFFSET].to_vec();
+        versioned.push(1);
+        versioned.extend_from_slice(&data[VERSION_OFFSET..]);
+        if let Ok(migrated) = migrate_versioned::<T>(&versioned) {
+            let migrated = migrated.unwrap_or(versioned);
+            if is_valid::<T>(&migrated) {
+                return Ok(Some(migrated));
+            }
+        }
+    }
+    match migrate_versioned::<T>(data)? {
+        Some(migrated) if !is_valid::<T>(&migrated) => Err(MigrationError::Invalid),
+        migrated => Ok(migrated),
+    }
+}
+
+fn is_valid<T: AccountDeserialize>(data: &[u8]) -> bool {
+    T::try_deserialize(&mut &data[..]).is_ok()
+}
+
+fn migrate_versioned<T: Versioned>(
+    data: &[u8],
+) -> std::result::Result<Option<Vec<u8>>, MigrationError> {
+    if data.len() <= VERSION_OFFSET {
+        return Err(MigrationError::TooShort);
+    }
+
+    let mut version = data[VERSION_OFFSET];
+    if version == 0 || version > T::CURRENT_VERSION {
+        return Err(MigrationError::UnknownVersion(version));
+    }
+    if version == T::CURRENT_VERSION {
+        return Ok(None);
+    }
+
+    let mut body = data[VERSION_OFFSET + 1..].to_vec();
+    while version < T::CURRENT_VERSION {
+        let step = T::MIGRATIONS[(version - 1) as usize];
+        body = step(&body).ok_or(MigrationError::StepFailed(version))?;
+        version += 1;
+    }
+
+    let mut migrated = data[..VERSION_OFFSET].to_vec();
+    migrated.push(version);
+    migrated.extend_from_slice(&body);
+    Ok(Some(migrated))
+}
+
+// Replaces the account's data, growing it to at least `min_len` bytes so the current layout's
+// variable-length fields have room, and topping up rent from `payer` when it grows
+pub fn rewrite_account<'info>(
+    account: &AccountInfo<'info>,
+    payer: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    data: &[u8],
+    min_len: usize,
+) -> Result<()> {
+    let len = data.len().max(min_len);
+    let required = Rent::get()?.minimum_balance(len);
+    let current = account.lamports();
+
+    if required > current {
+        system_program::transfer(
+            CpiContext::new(
+                system_program.clone(),
+                system_program::Transfer {
+                    from: payer.clone(),
+                    to: account.clone(),
+                },
+            ),
+            required - current,
+        )?;
+    }
+
+    account.realloc(len, false)?;
+    let mut account_data = account.try_borrow_mut_data()?;
+    account_data[..data.len()].copy_from_slice(data);
+    account_data[data.len()..].fill(0);
+    Ok(())
+}
+
+// Migration helper for appending a field: inserts `field` right after the borsh-encoded `T`
+// prefix of `body`, so any trailing zero padding stays at the end of the account
+pub fn insert_after<T: AnchorDeserialize>(body: &[u8], field: &[u8]) -> Option<Vec<u8>> {
+    let mut rest = body;
+    T::deserialize(&mut rest).ok()?;
+    let prefix_len = body.len() - rest.len();
+
+    let mut migrated = Vec::with_capacity(body.len() + field.len());
+    migrated.extend_from_slice(&body[..prefix_len]);
+    migrated.extend_from_slice(field);
+    migrated.extend_from_slice(&body[prefix_len..]);
+    Some(migrated)
+}
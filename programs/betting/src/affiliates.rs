@@ -0,0 +1,168 @@
+//! Affiliate commissions on bankroll-backed pools.
+//!
+//! A bankroll's admin registers affiliates with a commission rate of at most `MAX_COMMISSION_BPS`.
+//! Bets placed with a registered `referrer` route that cut of the stake to the affiliate's own
+//! vault instead of the bankroll, where it stays claimable until the affiliate withdraws it. The
+//! bankroll still owes the full payout, so the cut is a fee on the house's edge. It isn't refunded
+//! when the bet is voided.
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+use errors::CommonError;
+use fixed_math::{apply_bps, Rounding};
+use versioning::Versioned;
+
+use crate::{Bankroll, BettingError};
+
+pub const AFFILIATE_SEED: &[u8] = b"affiliate";
+/// Highest commission an affiliate can earn, kept well inside the house's edge.
+pub const MAX_COMMISSION_BPS: u64 = 500;
+
+pub(crate) fn register_affiliate(ctx: Context<RegisterAffiliate>, commission_bps: u64) -> Result<()> {
+    require!(commission_bps <= MAX_COMMISSION_BPS, BettingError::InvalidCommission);
+
+    let affiliate = &mut ctx.accounts.affiliate;
+    affiliate.version = Affiliate::CURRENT_VERSION;
+    affiliate.bankroll = ctx.accounts.bankroll.key();
+    affiliate.wallet = ctx.accounts.wallet.key();
+    affiliate.vault = ctx.accounts.vault.key();
+    affiliate.commission_bps = commission_bps;
+    affiliate.accrued = 0;
+    affiliate.claimed = 0;
+    affiliate.bump = *ctx.bumps.get("affiliate").unwrap();
+
+    msg!("Affiliate {:?} registered at {} bps", affiliate.wallet, commission_bps);
+    Ok(())
+}
+
+pub(crate) fn set_commission(ctx: Context<SetCommission>, commission_bps: u64) -> Result<()> {
+    require!(commission_bps <= MAX_COMMISSION_BPS, BettingError::InvalidCommission);
+    ctx.accounts.affiliate.commission_bps = commission_bps;
+    Ok(())
+}
+
+pub(crate) fn claim_affiliate_fees(ctx: Context<ClaimAffiliateFees>) -> Result<()> {
+    let affiliate = &mut ctx.accounts.affiliate;
+    let amount = affiliate.claimable();
+    require!(amount > 0, BettingError::NothingToClaim);
+
+    affiliate.claimed = affiliate.claimed.checked_add(amount).ok_or(CommonError::Overflow)?;
+
+    let (bankroll, wallet) = (affiliate.bankroll, affiliate.wallet);
+    let seeds = &[AFFILIATE_SEED, bankroll.as_ref(), wallet.as_ref(), &[affiliate.bump]];
+    token::transfer(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.vault.to_account_info(),
+                to: ctx.accounts.wallet_token_account.to_account_info(),
+                authority: affiliate.to_account_info(),
+            },
+            &[&seeds[..]],
+        ),
+        amount,
+    )?;
+
+    emit!(AffiliateFeesClaimed { affiliate: wallet, amount });
+    Ok(())
+}
+
+#[account]
+pub struct Affiliate {
+    pub version: u8,
+    pub bankroll: Pubkey,
+    pub wallet: Pubkey,
+    pub vault: Pubkey,
+    pub commission_bps: u64, // share of each referred stake
+    pub accrued: u64,
+    pub claimed: u64,
+    pub bump: u8,
+}
+
+impl Affiliate {
+    pub const LEN: usize = 1 + 32 + 32 + 32 + 8 + 8 + 8 + 1;
+
+    pub fn claimable(&self) -> u64 {
+        self.accrued - self.claimed
+    }
+
+    /// Book the commission on a referred stake and return it.
+    pub fn accrue(&mut self, stake: u64) -> Result<u64> {
+        let commission =
+            apply_bps(stake, self.commission_bps, Rounding::Down).ok_or(CommonError::Overflow)?;
+        self.accrued = self.accrued.checked_add(commission).ok_or(CommonError::Overflow)?;
+        Ok(commission)
+    }
+}
+
+impl Versioned for Affiliate {
+    fn version(&self) -> u8 {
+        self.version
+    }
+}
+
+#[derive(Accounts)]
+pub struct RegisterAffiliate<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    #[account(has_one = admin @ CommonError::Unauthorized)]
+    pub bankroll: Account<'info, Bankroll>,
+    /// CHECK: Affiliate's wallet; only recorded
+    pub wallet: UncheckedAccount<'info>,
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + Affiliate::LEN,
+        seeds = [AFFILIATE_SEED, bankroll.key().as_ref(), wallet.key().as_ref()],
+        bump
+    )]
+    pub affiliate: Account<'info, Affiliate>,
+    #[account(address = bankroll.mint)]
+    pub mint: Account<'info, anchor_spl::token::Mint>,
+    #[account(
+        init,
+        payer = admin,
+        token::mint = mint,
+        token::authority = affiliate,
+        seeds = [b"affiliate_vault", affiliate.key().as_ref()],
+        bump
+    )]
+    pub vault: Account<'info, TokenAccount>,
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+#[derive(Accounts)]
+pub struct SetCommission<'info> {
+    pub admin: Signer<'info>,
+    #[account(has_one = admin @ CommonError::Unauthorized)]
+    pub bankroll: Account<'info, Bankroll>,
+    #[account(mut, has_one = bankroll @ BettingError::AffiliateMismatch)]
+    pub affiliate: Account<'info, Affiliate>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimAffiliateFees<'info> {
+    pub wallet: Signer<'info>,
+    #[account(mut, has_one = wallet @ CommonError::Unauthorized, has_one = vault)]
+    pub affiliate: Account<'info, Affiliate>,
+    #[account(mut)]
+    pub vault: Account<'info, TokenAccount>,
+    #[account(mut, token::mint = vault.mint, token::authority = wallet)]
+    pub wallet_token_account: Account<'info, TokenAccount>,
+    pub token_program: Program<'info, Token>,
+}
+
+#[event]
+pub struct AffiliateCommission {
+    pub affiliate: Pubkey,
+    pub bettor: Pubkey,
+    pub pool: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+pub struct AffiliateFeesClaimed {
+    pub affiliate: Pubkey,
+    pub amount: u64,
+}
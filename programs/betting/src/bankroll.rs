@@ -0,0 +1,266 @@
+//! House bankroll funded by liquidity providers.
+//!
+//! LPs deposit into the bankroll's vault and receive share tokens. Pools linked to a bankroll
+//! take stakes into the vault and reserve each bet's full payout against it, so the vault's
+//! value net of those liabilities is what LP shares are priced at: losing bets raise the share
+//! value, winning bets lower it.
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Burn, Mint, MintTo, Token, TokenAccount, Transfer};
+use errors::CommonError;
+use fixed_math::{convert_to_assets, convert_to_shares, Rounding, BPS_DENOMINATOR};
+use pause_registry::{PauseRegistry, BETTING, REGISTRY_SEED};
+use versioning::Versioned;
+
+use crate::{BetPool, BettingError};
+
+pub const BANKROLL_SEED: &[u8] = b"bankroll";
+
+pub(crate) fn create_bankroll(ctx: Context<CreateBankroll>, max_utilization_bps: u64) -> Result<()> {
+    require!(
+        max_utilization_bps > 0 && max_utilization_bps <= BPS_DENOMINATOR,
+        BettingError::InvalidUtilization
+    );
+
+    let bankroll = &mut ctx.accounts.bankroll;
+    bankroll.version = Bankroll::CURRENT_VERSION;
+    bankroll.admin = ctx.accounts.admin.key();
+    bankroll.mint = ctx.accounts.mint.key();
+    bankroll.vault = ctx.accounts.vault.key();
+    bankroll.share_mint = ctx.accounts.share_mint.key();
+    bankroll.liabilities = 0;
+    bankroll.max_utilization_bps = max_utilization_bps;
+    bankroll.bump = *ctx.bumps.get("bankroll").unwrap();
+
+    msg!("Bankroll created for mint {:?}", bankroll.mint);
+    Ok(())
+}
+
+pub(crate) fn deposit_liquidity(ctx: Context<Liquidity>, amount: u64) -> Result<()> {
+    require!(amount > 0, BettingError::InvalidLiquidityAmount);
+
+    let bankroll = &ctx.accounts.bankroll;
+    let shares = convert_to_shares(
+        amount,
+        bankroll.net_assets(ctx.accounts.vault.amount),
+        ctx.accounts.share_mint.supply,
+        Rounding::Down,
+    )
+    .ok_or(CommonError::Overflow)?;
+    require!(shares > 0, BettingError::InvalidLiquidityAmount);
+
+    token::transfer(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.provider_token_account.to_account_info(),
+                to: ctx.accounts.vault.to_account_info(),
+                authority: ctx.accounts.provider.to_account_info(),
+            },
+        ),
+        amount,
+    )?;
+
+    let mint = bankroll.mint;
+    let seeds = &[BANKROLL_SEED, mint.as_ref(), &[bankroll.bump]];
+    token::mint_to(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            MintTo {
+                mint: ctx.accounts.share_mint.to_account_info(),
+                to: ctx.accounts.provider_share_account.to_account_info(),
+                authority: bankroll.to_account_info(),
+            },
+            &[&seeds[..]],
+        ),
+        shares,
+    )?;
+
+    msg!("Deposited {} for {} shares", amount, shares);
+    Ok(())
+}
+
+pub(crate) fn withdraw_liquidity(ctx: Context<Liquidity>, shares: u64) -> Result<()> {
+    require!(shares > 0, BettingError::InvalidLiquidityAmount);
+
+    let bankroll = &ctx.accounts.bankroll;
+    let vault_balance = ctx.accounts.vault.amount;
+    let amount = convert_to_assets(
+        shares,
+        bankroll.net_assets(vault_balance),
+        ctx.accounts.share_mint.supply,
+        Rounding::Down,
+    )
+    .ok_or(CommonError::Overflow)?;
+    require!(
+        bankroll.within_utilization(
+            bankroll.liabilities,
+            vault_balance.checked_sub(amount).ok_or(CommonError::Overflow)?
+        ),
+        BettingError::UtilizationExceeded
+    );
+
+    token::burn(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            Burn {
+                mint: ctx.accounts.share_mint.to_account_info(),
+                from: ctx.accounts.provider_share_account.to_account_info(),
+                authority: ctx.accounts.provider.to_account_info(),
+            },
+        ),
+        shares,
+    )?;
+
+    let mint = bankroll.mint;
+    let seeds = &[BANKROLL_SEED, mint.as_ref(), &[bankroll.bump]];
+    token::transfer(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.vault.to_account_info(),
+                to: ctx.accounts.provider_token_account.to_account_info(),
+                authority: bankroll.to_account_info(),
+            },
+            &[&seeds[..]],
+        ),
+        amount,
+    )?;
+
+    msg!("Withdrew {} for {} shares", amount, shares);
+    Ok(())
+}
+
+pub(crate) fn link_bankroll(ctx: Context<LinkBankroll>) -> Result<()> {
+    let bet_pool = &mut ctx.accounts.bet_pool;
+    // Bets placed before linking have no reserved payout
+    require!(bet_pool.bets.is_empty(), BettingError::PoolHasOpenBets);
+
+    bet_pool.bankroll = ctx.accounts.bankroll.key();
+    // A price feed was chosen for the previous token
+    bet_pool.price_feed = Pubkey::default();
+    bet_pool.token_decimals = 0;
+    msg!("Pool {:?} backed by bankroll {:?}", bet_pool.key(), bet_pool.bankroll);
+    Ok(())
+}
+
+#[account]
+pub struct Bankroll {
+    pub version: u8,
+    pub admin: Pubkey,
+    pub mint: Pubkey,
+    pub vault: Pubkey,
+    pub share_mint: Pubkey,
+    pub liabilities: u64, // full payouts reserved for open bets
+    pub max_utilization_bps: u64, // cap on liabilities as a share of the vault balance
+    pub bump: u8,
+}
+
+impl Bankroll {
+    pub const LEN: usize = 1 + 32 + 32 + 32 + 32 + 8 + 8 + 1;
+
+    /// Vault balance if every open bet won; LP shares are priced against this.
+    pub fn net_assets(&self, vault_balance: u64) -> u64 {
+        vault_balance.saturating_sub(self.liabilities)
+    }
+
+    pub fn within_utilization(&self, liabilities: u64, vault_balance: u64) -> bool {
+        liabilities as u128 * BPS_DENOMINATOR as u128
+            <= vault_balance as u128 * self.max_utilization_bps as u128
+    }
+
+    /// Reserve a new bet's payout; `vault_balance` must already include its stake.
+    pub fn reserve(&mut self, payout: u64, vault_balance: u64) -> Result<()> {
+        let liabilities = self.liabilities.checked_add(payout).ok_or(CommonError::Overflow)?;
+        require!(
+            self.within_utilization(liabilities, vault_balance),
+            BettingError::UtilizationExceeded
+        );
+        self.liabilities = liabilities;
+        Ok(())
+    }
+
+    pub fn release(&mut self, payout: u64) {
+        self.liabilities = self.liabilities.saturating_sub(payout);
+    }
+
+    /// Keep `amount` of resolved winnings reserved until its bettor claims it.
+    pub fn hold(&mut self, amount: u64) -> Result<()> {
+        self.liabilities = self.liabilities.checked_add(amount).ok_or(CommonError::Overflow)?;
+        Ok(())
+    }
+}
+
+impl Versioned for Bankroll {
+    fn version(&self) -> u8 {
+        self.version
+    }
+}
+
+#[derive(Accounts)]
+pub struct CreateBankroll<'info> {
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + Bankroll::LEN,
+        seeds = [BANKROLL_SEED, mint.key().as_ref()],
+        bump
+    )]
+    pub bankroll: Account<'info, Bankroll>,
+    pub mint: Account<'info, Mint>,
+    #[account(
+        init,
+        payer = admin,
+        token::mint = mint,
+        token::authority = bankroll,
+        seeds = [b"bankroll_vault", bankroll.key().as_ref()],
+        bump
+    )]
+    pub vault: Account<'info, TokenAccount>,
+    #[account(
+        init,
+        payer = admin,
+        mint::decimals = mint.decimals,
+        mint::authority = bankroll,
+        seeds = [b"bankroll_shares", bankroll.key().as_ref()],
+        bump
+    )]
+    pub share_mint: Account<'info, Mint>,
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+/// Shared by deposits and withdrawals.
+#[derive(Accounts)]
+pub struct Liquidity<'info> {
+    pub provider: Signer<'info>,
+    #[account(has_one = vault, has_one = share_mint)]
+    pub bankroll: Account<'info, Bankroll>,
+    #[account(mut)]
+    pub vault: Account<'info, TokenAccount>,
+    #[account(mut)]
+    pub share_mint: Account<'info, Mint>,
+    #[account(mut, token::mint = bankroll.mint, token::authority = provider)]
+    pub provider_token_account: Account<'info, TokenAccount>,
+    #[account(mut, token::mint = share_mint, token::authority = provider)]
+    pub provider_share_account: Account<'info, TokenAccount>,
+    #[account(
+        seeds = [REGISTRY_SEED],
+        bump = pause_registry.bump,
+        seeds::program = pause_registry::ID,
+        constraint = !pause_registry.is_paused(BETTING) @ CommonError::Paused
+    )]
+    pub pause_registry: Account<'info, PauseRegistry>,
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct LinkBankroll<'info> {
+    pub admin: Signer<'info>,
+    #[account(mut, has_one = admin @ CommonError::Unauthorized)]
+    pub bet_pool: Account<'info, BetPool>,
+    #[account(has_one = admin @ CommonError::Unauthorized)]
+    pub bankroll: Account<'info, Bankroll>,
+}
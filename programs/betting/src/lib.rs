@@ -0,0 +1,1470 @@
+use anchor_lang::prelude::*;
+use anchor_lang::Discriminator;
+use anchor_spl::token::{self, Transfer, Token, TokenAccount};
+use errors::CommonError;
+use fixed_math::{scale_bps, side_pots, Rounding, BPS_DENOMINATOR};
+use guards::{require_owner, require_token_account};
+use pause_registry::{PauseRegistry, BETTING, REGISTRY_SEED};
+use versioning::{insert_after, migrate, rewrite_account, Migration, Versioned};
+
+declare_id!("CneD5bxnm2Q56k1bwE1sDTjRXwzpzfCPVSsWytfZ7sQB");
+
+pub mod affiliates;
+pub mod bankroll;
+pub mod makers;
+pub mod metadata;
+pub mod poker_markets;
+pub mod pricing;
+pub mod rules;
+
+pub use affiliates::*;
+pub use bankroll::*;
+pub use makers::*;
+pub use metadata::*;
+pub use poker_markets::*;
+pub use pricing::*;
+pub use rules::*;
+
+/// How long a loosened limit waits before it takes effect.
+pub const LIMIT_COOLDOWN: i64 = 7 * 24 * 60 * 60;
+const SECONDS_PER_DAY: i64 = 24 * 60 * 60;
+/// Open bets a pool can hold until it resolves.
+pub const MAX_POOL_BETS: usize = 32;
+/// Most recent bets a user profile records; older ones are dropped.
+pub const MAX_HISTORY: usize = 64;
+
+#[program]
+pub mod betting {
+    use super::*;
+
+    /// Create a new user profile.
+    pub fn create_user_profile(ctx: Context<CreateUserProfile>) -> Result<()> {
+        let user_profile = &mut ctx.accounts.user_profile;
+        user_profile.version = UserProfile::CURRENT_VERSION;
+        user_profile.user_id = ctx.accounts.user.key();
+        user_profile.total_bets = 0;
+        user_profile.total_wins = 0;
+        user_profile.betting_history = Vec::new();
+        user_profile.limits = BettingLimits::default();
+        user_profile.bet_count = 0;
+
+        msg!("User profile created for {:?}", user_profile.user_id);
+        Ok(())
+    }
+
+    /// Update a user's betting history.
+    pub fn update_betting_history(ctx: Context<UpdateBettingHistory>, bet: Bet) -> Result<()> {
+        let user_profile = &mut ctx.accounts.user_profile;
+        require_owner!(ctx.accounts.user, user_profile.user_id, CommonError::Unauthorized);
+        require_keys_eq!(bet.user_id, user_profile.user_id, CommonError::Unauthorized);
+        require!(bet.outcome.len() <= MAX_LABEL_LEN, BettingError::InvalidOutcome);
+
+        user_profile.total_bets += bet.amount;
+        user_profile.record(bet);
+
+        msg!("Betting history updated for user {:?}", user_profile.user_id);
+        Ok(())
+    }
+
+    /// Create a new betting pool.
+    pub fn create_betting_pool(ctx: Context<CreateBettingPool>, outcome: String) -> Result<()> {
+        require!(outcome.len() <= MAX_LABEL_LEN, BettingError::InvalidOutcome);
+        let bet_pool = &mut ctx.accounts.bet_pool;
+
+        bet_pool.version = BetPool::CURRENT_VERSION;
+        bet_pool.admin = ctx.accounts.admin.key();
+        bet_pool.total_bets = 0;
+        bet_pool.odds_bps = BPS_DENOMINATOR; // Default odds, 1.00x
+        bet_pool.outcome = outcome.clone();
+        bet_pool.bets = Vec::new();
+        bet_pool.bankroll = Pubkey::default();
+        bet_pool.max_payout_per_user = 0;
+        bet_pool.resolution_deadline = 0;
+        bet_pool.price_feed = Pubkey::default();
+        bet_pool.token_decimals = 0;
+        bet_pool.resolved_outcome = String::new();
+        bet_pool.unclaimed = Vec::new();
+
+        msg!("Betting pool created with outcome: {}", outcome);
+        Ok(())
+    }
+
+    /// Place a bet in a betting pool.
+    /// The bet is rejected if the pool's odds have dropped below `min_odds_bps` by the time it lands.
+    pub fn place_bet(ctx: Context<PlaceBet>, amount: u64, min_odds_bps: u64) -> Result<()> {
+        let bet_pool = &mut ctx.accounts.bet_pool;
+        let user = &ctx.accounts.user;
+
+        require!(amount > 0, BettingError::InvalidBetAmount);
+        require!(bet_pool.odds_bps >= min_odds_bps, BettingError::OddsBelowMinimum);
+        // Owed winnings share the bets' room, so a resolution can always record what it can't pay
+        require!(
+            bet_pool.bets.len() + bet_pool.unclaimed.len() < MAX_POOL_BETS,
+            BettingError::TooManyBets
+        );
+        require_owner!(user, ctx.accounts.user_profile.user_id, CommonError::Unauthorized);
+        let now = Clock::get()?.unix_timestamp;
+        require!(!bet_pool.is_expired(now), BettingError::MarketExpired);
+        ctx.accounts.user_profile.limits.record_wager(amount, now)?;
+        require_token_account!(
+            ctx.accounts.user_token_account,
+            ctx.accounts.bet_pool_token_account.mint,
+            user.key(),
+            CommonError::InvalidTokenAccount
+        );
+
+        let bet = Bet {
+            user_id: user.key(),
+            amount,
+            outcome: bet_pool.outcome.clone(),
+            odds_bps: bet_pool.odds_bps,
+        };
+        bet_pool.check_payout_cap(&bet)?;
+        let usd_value = pricing::usd_value(bet_pool, ctx.accounts.price_feed.as_deref(), amount, now)?;
+
+        // Bankroll-backed pools take the stake and reserve the full payout up front
+        let commission = if bet_pool.is_backed() {
+            let bankroll = ctx.accounts.bankroll.as_mut().ok_or(BettingError::BankrollMismatch)?;
+            let vault = &ctx.accounts.bet_pool_token_account;
+            require_keys_eq!(bankroll.key(), bet_pool.bankroll, BettingError::BankrollMismatch);
+            require_keys_eq!(vault.key(), bankroll.vault, BettingError::BankrollMismatch);
+
+            // A referrer's commission, capped at `MAX_COMMISSION_BPS`, is the house's fee on the
+            // stake and never reaches the bankroll
+            let commission = match ctx.accounts.affiliate.as_mut() {
+                Some(affiliate) => {
+                    require_keys_eq!(affiliate.bankroll, bankroll.key(), BettingError::AffiliateMismatch);
+                    require_keys_neq!(affiliate.wallet, user.key(), BettingError::SelfReferral);
+                    affiliate.accrue(amount)?
+                }
+                None => 0,
+            };
+            let house_stake = amount - commission;
+
+            let payout = bet.payout().ok_or(CommonError::Overflow)?;
+            let vault_balance = vault.amount.checked_add(house_stake).ok_or(CommonError::Overflow)?;
+            bankroll.reserve(payout, vault_balance)?;
+
+            token::transfer(
+                CpiContext::new(
+                    ctx.accounts.token_program.to_account_info(),
+                    Transfer {
+                        from: ctx.accounts.user_token_account.to_account_info(),
+                        to: vault.to_account_info(),
+                        authority: user.to_account_info(),
+                    },
+                ),
+                house_stake,
+            )?;
+
+            if let (Some(affiliate), true) = (ctx.accounts.affiliate.as_ref(), commission > 0) {
+                let affiliate_vault = ctx
+                    .accounts
+                    .affiliate_vault
+                    .as_ref()
+                    .ok_or(BettingError::AffiliateMismatch)?;
+                require_keys_eq!(affiliate_vault.key(), affiliate.vault, BettingError::AffiliateMismatch);
+
+                token::transfer(
+                    CpiContext::new(
+                        ctx.accounts.token_program.to_account_info(),
+                        Transfer {
+                            from: ctx.accounts.user_token_account.to_account_info(),
+                            to: affiliate_vault.to_account_info(),
+                            authority: user.to_account_info(),
+                        },
+                    ),
+                    commission,
+                )?;
+
+                emit!(AffiliateCommission {
+                    affiliate: affiliate.wallet,
+                    bettor: user.key(),
+                    pool: bet_pool.key(),
+                    amount: commission,
+                });
+            }
+            commission
+        } else {
+            // Unbacked pools never take custody of stakes, so there is nothing to share
+            require!(ctx.accounts.affiliate.is_none(), BettingError::AffiliateMismatch);
+            0
+        };
+
+        let receipt = &mut ctx.accounts.bet_receipt;
+        receipt.version = BetReceipt::CURRENT_VERSION;
+        receipt.owner = user.key();
+        receipt.pool = bet_pool.key();
+        receipt.status = BetStatus::Open;
+        receipt.amount = amount;
+        receipt.odds_bps = bet_pool.odds_bps;
+        receipt.placed_at = now;
+        receipt.bump = *ctx.bumps.get("bet_receipt").unwrap();
+        receipt.usd_value = usd_value;
+        receipt.commission = commission;
+
+        // Add bet to user's history and pool
+        let user_profile = &mut ctx.accounts.user_profile;
+        user_profile.total_bets += amount;
+        user_profile.bet_count += 1;
+        user_profile.record(bet.clone());
+
+        emit!(BetPlaced {
+            pool: bet_pool.key(),
+            bettor: user.key(),
+            amount,
+            usd_value,
+            odds_bps: bet.odds_bps,
+            timestamp: now,
+        });
+
+        bet_pool.bets.push(bet);
+        bet_pool.total_bets += amount;
+
+        msg!(
+            "Bet placed by {:?} with amount {} in pool {:?}",
+            user.key(),
+            amount,
+            bet_pool.key()
+        );
+        Ok(())
+    }
+
+    /// Resolve bets and distribute payouts based on the winning outcome.
+    /// Remaining accounts are the winning bets' token accounts, one per bet on the winning
+    /// outcome in the pool's bet order, followed by any of the pool's `BetReceipt`s to mark settled.
+    /// Backed pools pay from their bankroll's vault; others from the admin's token account.
+    /// Pools with a committed poker deal can only be resolved from its cards.
+    pub fn resolve_bets<'info>(
+        ctx: Context<'_, '_, '_, 'info, ResolveBets<'info>>,
+        winning_outcome: String,
+    ) -> Result<()> {
+        require!(ctx.accounts.settle.bet_pool.outcome == winning_outcome, BettingError::InvalidOutcome);
+        poker_markets::require_no_deal(&ctx.accounts.poker_deal)?;
+        let admin = ctx.accounts.admin.to_account_info();
+        settle_pool(&mut ctx.accounts.settle, Some(admin), ctx.remaining_accounts, &winning_outcome)
+    }
+
+    /// Collect winnings a resolution couldn't pay because the bettor's token account was closed
+    /// or frozen. Unbacked pools pay from the admin's token account, so their admin co-signs.
+    pub fn claim_winnings(ctx: Context<ClaimWinnings>) -> Result<()> {
+        let bet_pool = &mut ctx.accounts.bet_pool;
+        let user = ctx.accounts.user.key();
+        let index = bet_pool
+            .unclaimed
+            .iter()
+            .position(|owed| owed.bettor == user)
+            .ok_or(BettingError::NothingToClaim)?;
+        let amount = bet_pool.unclaimed.remove(index).amount;
+
+        let (authority, bankroll_mint, bankroll_bump) = if bet_pool.is_backed() {
+            let bankroll = ctx.accounts.bankroll.as_mut().ok_or(BettingError::BankrollMismatch)?;
+            require_keys_eq!(bankroll.key(), bet_pool.bankroll, BettingError::BankrollMismatch);
+            require_keys_eq!(
+                ctx.accounts.bet_pool_token_account.key(),
+                bankroll.vault,
+                BettingError::BankrollMismatch
+            );
+            bankroll.release(amount);
+            (bankroll.to_account_info(), bankroll.mint, bankroll.bump)
+        } else {
+            let admin = ctx.accounts.admin.as_ref().ok_or(CommonError::Unauthorized)?;
+            require_keys_eq!(admin.key(), bet_pool.admin, CommonError::Unauthorized);
+            require_keys_eq!(
+                ctx.accounts.bet_pool_token_account.owner,
+                admin.key(),
+                CommonError::InvalidTokenAccount
+            );
+            (admin.to_account_info(), Pubkey::default(), 0)
+        };
+        let bankroll_seeds: &[&[u8]] = &[BANKROLL_SEED, bankroll_mint.as_ref(), &[bankroll_bump]];
+        let bankroll_signer = [bankroll_seeds];
+        let signer: &[&[&[u8]]] = if bet_pool.is_backed() { &bankroll_signer } else { &[] };
+
+        token::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.bet_pool_token_account.to_account_info(),
+                    to: ctx.accounts.user_token_account.to_account_info(),
+                    authority,
+                },
+                signer,
+            ),
+            amount,
+        )?;
+
+        emit!(WinningsClaimed { pool: bet_pool.key(), bettor: user, amount });
+        Ok(())
+    }
+
+    /// Set the odds for future bets; bets already placed keep their locked odds.
+    pub fn update_odds(ctx: Context<UpdateOdds>, odds_bps: u64) -> Result<()> {
+        require!(odds_bps > 0, BettingError::InvalidOdds);
+
+        let bet_pool = &mut ctx.accounts.bet_pool;
+        let previous_odds_bps = bet_pool.odds_bps;
+        bet_pool.odds_bps = odds_bps;
+
+        emit!(OddsUpdated {
+            pool: bet_pool.key(),
+            previous_odds_bps,
+            odds_bps,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        Ok(())
+    }
+
+    /// Set the user's daily wager cap (0 for none) and self-exclusion end. Tighter limits apply
+    /// immediately; looser ones are queued and take effect after `LIMIT_COOLDOWN`.
+    pub fn set_limits(
+        ctx: Context<SetLimits>,
+        max_daily_wager: u64,
+        self_excluded_until: i64,
+    ) -> Result<()> {
+        let user_profile = &mut ctx.accounts.user_profile;
+        require_owner!(ctx.accounts.user, user_profile.user_id, CommonError::Unauthorized);
+
+        let now = Clock::get()?.unix_timestamp;
+        if user_profile.limits.request(max_daily_wager, self_excluded_until, now) {
+            msg!("Limits updated for user {:?}", user_profile.user_id);
+        } else {
+            msg!(
+                "Limits for user {:?} loosen at {}",
+                user_profile.user_id,
+                user_profile.limits.pending_effective_at
+            );
+        }
+        Ok(())
+    }
+
+    /// Create a house bankroll for `mint` that LPs can fund.
+    pub fn create_bankroll(ctx: Context<CreateBankroll>, max_utilization_bps: u64) -> Result<()> {
+        bankroll::create_bankroll(ctx, max_utilization_bps)
+    }
+
+    /// Deposit into a bankroll in exchange for share tokens.
+    pub fn deposit_liquidity(ctx: Context<Liquidity>, amount: u64) -> Result<()> {
+        bankroll::deposit_liquidity(ctx, amount)
+    }
+
+    /// Burn share tokens for their current value, within the bankroll's utilization limit.
+    pub fn withdraw_liquidity(ctx: Context<Liquidity>, shares: u64) -> Result<()> {
+        bankroll::withdraw_liquidity(ctx, shares)
+    }
+
+    /// Back a pool with a bankroll; both must share the same admin. Clears the pool's price feed.
+    pub fn link_bankroll(ctx: Context<LinkBankroll>) -> Result<()> {
+        bankroll::link_bankroll(ctx)
+    }
+
+    /// Register a referrer earning `commission_bps`, at most `MAX_COMMISSION_BPS`, of the stakes it
+    /// refers to a bankroll's pools.
+    pub fn register_affiliate(ctx: Context<RegisterAffiliate>, commission_bps: u64) -> Result<()> {
+        affiliates::register_affiliate(ctx, commission_bps)
+    }
+
+    /// Change an affiliate's commission for future bets.
+    pub fn set_commission(ctx: Context<SetCommission>, commission_bps: u64) -> Result<()> {
+        affiliates::set_commission(ctx, commission_bps)
+    }
+
+    /// Withdraw an affiliate's accrued commissions.
+    pub fn claim_affiliate_fees(ctx: Context<ClaimAffiliateFees>) -> Result<()> {
+        affiliates::claim_affiliate_fees(ctx)
+    }
+
+    /// Set a pool's outcome labels, description and tags; only while it has no bets and before
+    /// its first resolution.
+    pub fn set_market_metadata(
+        ctx: Context<SetMarketMetadata>,
+        outcome_labels: Vec<String>,
+        description: String,
+        tags: Vec<String>,
+    ) -> Result<()> {
+        metadata::set_market_metadata(ctx, outcome_labels, description, tags)
+    }
+
+    /// Cap each user's total payout in a pool (0 for none) and set the time by which it must be
+    /// resolved (0 for none); only while it has no bets.
+    pub fn set_market_rules(
+        ctx: Context<SetMarketRules>,
+        max_payout_per_user: u64,
+        resolution_deadline: i64,
+    ) -> Result<()> {
+        rules::set_market_rules(ctx, max_payout_per_user, resolution_deadline)
+    }
+
+    /// Value a backed pool's bets in USD through a Pyth price feed for its bankroll's token, or
+    /// stop doing so.
+    pub fn set_price_feed(ctx: Context<SetPriceFeed>) -> Result<()> {
+        pricing::set_price_feed(ctx)
+    }
+
+    /// Register a market maker on a pool.
+    pub fn register_maker(ctx: Context<RegisterMaker>) -> Result<()> {
+        makers::register_maker(ctx)
+    }
+
+    /// Replace a maker's quotes and set the collateral backing new matches.
+    pub fn provide_quotes(ctx: Context<ProvideQuotes>, outcome_odds: Vec<u64>, size: u64) -> Result<()> {
+        makers::provide_quotes(ctx, outcome_odds, size)
+    }
+
+    /// Back an outcome against a maker's quote, at no less than `min_odds_bps`.
+    pub fn take_quote(
+        ctx: Context<TakeQuote>,
+        outcome: u8,
+        amount: u64,
+        min_odds_bps: u64,
+    ) -> Result<()> {
+        makers::take_quote(ctx, outcome, amount, min_odds_bps)
+    }
+
+    /// Name the winning outcome, an index into the market's labels, that makers settle against.
+    pub fn resolve_makers(ctx: Context<ResolveMakers>, winning_outcome: u8) -> Result<()> {
+        makers::resolve_makers(ctx, winning_outcome)
+    }
+
+    /// Settle a maker's open positions against the outcome the market resolved to.
+    pub fn settle_maker<'info>(ctx: Context<'_, '_, '_, 'info, SettleMaker<'info>>) -> Result<()> {
+        makers::settle_maker(ctx)
+    }
+
+    /// Refund a maker's open positions on a pool left unresolved past its deadline.
+    pub fn void_maker<'info>(ctx: Context<'_, '_, '_, 'info, VoidMaker<'info>>) -> Result<()> {
+        makers::void_maker(ctx)
+    }
+
+    /// Record the seated players' shuffle commitments for a poker-hand market.
+    pub fn commit_poker_deal(ctx: Context<CommitPokerDeal>, commitments: Vec<[u8; 32]>) -> Result<()> {
+        poker_markets::commit_poker_deal(ctx, commitments)
+    }
+
+    /// Resolve a poker-hand market from the players' revealed secrets.
+    pub fn resolve_poker_market<'info>(
+        ctx: Context<'_, '_, '_, 'info, ResolvePokerMarket<'info>>,
+        secrets: Vec<[u8; 32]>,
+    ) -> Result<()> {
+        poker_markets::resolve_poker_market(ctx, secrets)
+    }
+
+    /// Void one's own open bet in a pool left unresolved past its deadline.
+    pub fn void_bet(ctx: Context<VoidBet>) -> Result<()> {
+        rules::void_bet(ctx)
+    }
+
+    /// Bring a program account up to the current schema version.
+    pub fn migrate_account(ctx: Context<MigrateAccount>) -> Result<()> {
+        let target = ctx.accounts.target.to_account_info();
+        let data = target.try_borrow_data()?.to_vec();
+        require!(data.len() >= 8, CommonError::UnknownAccountType);
+
+        // Migrated accounts grow to the full current size, so their vectors have room to fill
+        let (migrated, len) = match &data[..8] {
+            d if d == UserProfile::DISCRIMINATOR => (migrate::<UserProfile>(&data), UserProfile::LEN),
+            d if d == BetPool::DISCRIMINATOR => (migrate::<BetPool>(&data), BetPool::LEN),
+            d if d == BetReceipt::DISCRIMINATOR => (migrate::<BetReceipt>(&data), BetReceipt::LEN),
+            d if d == Bankroll::DISCRIMINATOR => (migrate::<Bankroll>(&data), Bankroll::LEN),
+            d if d == Affiliate::DISCRIMINATOR => (migrate::<Affiliate>(&data), Affiliate::LEN),
+            d if d == MarketMetadata::DISCRIMINATOR => (migrate::<MarketMetadata>(&data), MarketMetadata::LEN),
+            d if d == Maker::DISCRIMINATOR => (migrate::<Maker>(&data), Maker::LEN),
+            d if d == PokerDeal::DISCRIMINATOR => (migrate::<PokerDeal>(&data), PokerDeal::LEN),
+            _ => return err!(CommonError::UnknownAccountType),
+        };
+        let migrated = migrated.map_err(|_| CommonError::MigrationFailed)?;
+
+        if let Some(migrated) = migrated {
+            rewrite_account(
+                &target,
+                &ctx.accounts.payer.to_account_info(),
+                &ctx.accounts.system_program.to_account_info(),
+                &migrated,
+                8 + len,
+            )?;
+            msg!("Account {:?} migrated", target.key());
+        }
+
+        Ok(())
+    }
+}
+
+/// Pays the pool's bets on `winning_outcome` and resets the pool; every other bet loses.
+/// `remaining` holds one token account per winning bet, in bet order, owned by that bet's
+/// bettor; any accounts after them are the pool's open `BetReceipt`s to mark settled. A winner
+/// whose account is closed or frozen isn't paid: their share stays in the paying account and is
+/// recorded in `unclaimed` for `claim_winnings`.
+/// `admin` signs for payouts from an unbacked pool and may be `None` for backed ones.
+pub(crate) fn settle_pool<'info>(
+    accounts: &mut SettlePool<'info>,
+    admin: Option<AccountInfo<'info>>,
+    remaining: &[AccountInfo<'info>],
+    winning_outcome: &str,
+) -> Result<()> {
+    let bet_pool = &mut accounts.bet_pool;
+    let now = Clock::get()?.unix_timestamp;
+
+    // A pool holding only maker positions still resolves, with nothing to pay here
+    require!(!bet_pool.is_expired(now), BettingError::MarketExpired);
+    let winner_count = bet_pool.bets.iter().filter(|bet| bet.outcome == winning_outcome).count();
+    require!(remaining.len() >= winner_count, BettingError::WinnerAccountMismatch);
+    let (winners, receipts) = remaining.split_at(winner_count);
+
+    let (authority, bankroll_mint, bankroll_bump) = if bet_pool.is_backed() {
+        let bankroll = accounts.bankroll.as_ref().ok_or(BettingError::BankrollMismatch)?;
+        require_keys_eq!(bankroll.key(), bet_pool.bankroll, BettingError::BankrollMismatch);
+        require_keys_eq!(
+            accounts.bet_pool_token_account.key(),
+            bankroll.vault,
+            BettingError::BankrollMismatch
+        );
+        (bankroll.to_account_info(), bankroll.mint, bankroll.bump)
+    } else {
+        let admin = admin.ok_or(CommonError::Unauthorized)?;
+        require_keys_eq!(
+            accounts.bet_pool_token_account.owner,
+            admin.key(),
+            CommonError::InvalidTokenAccount
+        );
+        (admin, Pubkey::default(), 0)
+    };
+    let bankroll_seeds: &[&[u8]] = &[BANKROLL_SEED, bankroll_mint.as_ref(), &[bankroll_bump]];
+    let bankroll_signer = [bankroll_seeds];
+    let signer: &[&[&[u8]]] = if bet_pool.is_backed() { &bankroll_signer } else { &[] };
+
+    // Winners share what the paying account holds; a shortfall is split like poker side pots
+    let mut claims = Vec::new();
+    for bet in &bet_pool.bets {
+        // Calculate payout at the odds locked when the bet was placed
+        let payout = bet.payout().ok_or(CommonError::Overflow)?;
+        if bet_pool.is_backed() {
+            if let Some(bankroll) = accounts.bankroll.as_mut() {
+                bankroll.release(payout);
+            }
+        }
+        claims.push(if bet.outcome == winning_outcome { payout } else { 0 });
+    }
+    let available = accounts.bet_pool_token_account.amount;
+    let paid = side_pots::allocate(&claims, available).ok_or(CommonError::Overflow)?;
+
+    let mut winners = winners.iter();
+    let mut allocations = Vec::new();
+    let mut held = Vec::new();
+    for ((bet, &claim), &payout) in bet_pool.bets.iter().zip(&claims).zip(&paid) {
+        if bet.outcome != winning_outcome {
+            continue;
+        }
+        let winner = winners.next().ok_or(BettingError::WinnerAccountMismatch)?;
+        // One bettor closing or freezing their account must not hold up everyone else's payout
+        let payable = match Account::<TokenAccount>::try_from(winner) {
+            Ok(token_account) => {
+                require_token_account!(
+                    token_account,
+                    accounts.bet_pool_token_account.mint,
+                    bet.user_id,
+                    BettingError::WinnerAccountMismatch
+                );
+                !token_account.is_frozen()
+            }
+            Err(_) => false,
+        };
+        if claim == 0 {
+            continue;
+        }
+        // Payouts are valued at the price when they settle, not when the bet was placed
+        let paid_usd = pricing::usd_value(bet_pool, accounts.price_feed.as_deref(), payout, now)?;
+        allocations.push(Allocation { bettor: bet.user_id, claim, paid: payout, paid_usd });
+        if payout == 0 {
+            continue;
+        }
+        if !payable {
+            held.push(Unclaimed { bettor: bet.user_id, amount: payout });
+            continue;
+        }
+
+        // Distribute payout to the winning user
+        token::transfer(
+            CpiContext::new_with_signer(
+                accounts.token_program.to_account_info(),
+                Transfer {
+                    from: accounts.bet_pool_token_account.to_account_info(),
+                    to: winner.clone(),
+                    authority: authority.clone(),
+                },
+                signer,
+            ),
+            payout,
+        )?;
+
+        msg!("Payout of {} transferred to user {:?}", payout, bet.user_id);
+    }
+
+    emit!(SettlementAllocated {
+        pool: bet_pool.key(),
+        available,
+        allocations,
+    });
+
+    // Held winnings stay reserved against the bankroll until they're claimed
+    for owed in held {
+        if let Some(bankroll) = accounts.bankroll.as_mut().filter(|_| bet_pool.is_backed()) {
+            bankroll.hold(owed.amount)?;
+        }
+        emit!(WinningsHeld { pool: bet_pool.key(), bettor: owed.bettor, amount: owed.amount });
+        bet_pool.unclaimed.push(owed);
+    }
+
+    for account in receipts {
+        let mut receipt = Account::<BetReceipt>::try_from(account)?;
+        require_keys_eq!(receipt.pool, bet_pool.key(), BettingError::ReceiptPoolMismatch);
+        require!(receipt.status == BetStatus::Open, BettingError::BetNotOpen);
+        receipt.status = BetStatus::Settled;
+        receipt.exit(&crate::ID)?;
+    }
+
+    // Reset the betting pool
+    bet_pool.bets.clear();
+    bet_pool.total_bets = 0;
+
+    msg!("Betting pool resolved with outcome: {}", winning_outcome);
+    Ok(())
+}
+
+/// Define contexts for each function
+#[derive(Accounts)]
+pub struct CreateUserProfile<'info> {
+    #[account(init, payer = user, space = 8 + UserProfile::LEN)]
+    pub user_profile: Account<'info, UserProfile>,
+    #[account(mut)]
+    pub user: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateBettingHistory<'info> {
+    pub user: Signer<'info>,
+    #[account(mut)]
+    pub user_profile: Account<'info, UserProfile>,
+}
+
+#[derive(Accounts)]
+pub struct SetLimits<'info> {
+    pub user: Signer<'info>,
+    #[account(mut)]
+    pub user_profile: Account<'info, UserProfile>,
+}
+
+#[derive(Accounts)]
+pub struct CreateBettingPool<'info> {
+    #[account(init, payer = admin, space = 8 + BetPool::LEN)]
+    pub bet_pool: Account<'info, BetPool>,
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct PlaceBet<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+    #[account(mut)]
+    pub user_profile: Account<'info, UserProfile>,
+    #[account(mut)]
+    pub bet_pool: Account<'info, BetPool>,
+    /// One receipt per bet, numbered by the user's bet count so the seeds never repeat.
+    #[account(
+        init,
+        payer = user,
+        space = 8 + BetReceipt::LEN,
+        seeds = [b"bet", user.key().as_ref(), &user_profile.bet_count.to_le_bytes()],
+        bump
+    )]
+    pub bet_receipt: Account<'info, BetReceipt>,
+    #[account(mut)]
+    pub user_token_account: Account<'info, TokenAccount>,
+    #[account(mut)]
+    pub bet_pool_token_account: Account<'info, TokenAccount>,
+    /// Required when the pool is backed by a bankroll.
+    #[account(mut)]
+    pub bankroll: Option<Account<'info, Bankroll>>,
+    /// Referrer of this bet, only accepted on backed pools.
+    #[account(mut)]
+    pub affiliate: Option<Account<'info, Affiliate>>,
+    #[account(mut)]
+    pub affiliate_vault: Option<Account<'info, TokenAccount>>,
+    /// CHECK: Required when the pool is priced; matched against `bet_pool.price_feed`
+    pub price_feed: Option<UncheckedAccount<'info>>,
+    #[account(
+        seeds = [REGISTRY_SEED],
+        bump = pause_registry.bump,
+        seeds::program = pause_registry::ID,
+        constraint = !pause_registry.is_paused(BETTING) @ CommonError::Paused
+    )]
+    pub pause_registry: Account<'info, PauseRegistry>,
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ResolveBets<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    #[account(constraint = settle.bet_pool.admin == admin.key() @ CommonError::Unauthorized)]
+    pub settle: SettlePool<'info>,
+    /// CHECK: The pool's poker deal, if one was ever committed; see `poker_markets::require_no_deal`
+    #[account(seeds = [POKER_DEAL_SEED, settle.bet_pool.key().as_ref()], bump)]
+    pub poker_deal: UncheckedAccount<'info>,
+}
+
+/// The accounts `settle_pool` pays a pool's winners from.
+#[derive(Accounts)]
+pub struct SettlePool<'info> {
+    #[account(mut)]
+    pub bet_pool: Account<'info, BetPool>,
+    /// The admin's token account, or the bankroll's vault for backed pools.
+    #[account(mut)]
+    pub bet_pool_token_account: Account<'info, TokenAccount>,
+    /// Required when the pool is backed by a bankroll.
+    #[account(mut)]
+    pub bankroll: Option<Account<'info, Bankroll>>,
+    /// CHECK: Required when the pool is priced; matched against `bet_pool.price_feed`
+    pub price_feed: Option<UncheckedAccount<'info>>,
+    #[account(
+        seeds = [REGISTRY_SEED],
+        bump = pause_registry.bump,
+        seeds::program = pause_registry::ID,
+        constraint = !pause_registry.is_paused(BETTING) @ CommonError::Paused
+    )]
+    pub pause_registry: Account<'info, PauseRegistry>,
+    pub token_program: Program<'info, Token>,
+}
+
+/// Deliberately not gated by the pause registry: owed winnings must not depend on any admin,
+/// beyond the co-signature an unbacked pool's token account needs.
+#[derive(Accounts)]
+pub struct ClaimWinnings<'info> {
+    pub user: Signer<'info>,
+    #[account(mut)]
+    pub bet_pool: Account<'info, BetPool>,
+    /// The admin's token account, or the bankroll's vault for backed pools.
+    #[account(mut)]
+    pub bet_pool_token_account: Account<'info, TokenAccount>,
+    /// Required when the pool is backed by a bankroll.
+    #[account(mut)]
+    pub bankroll: Option<Account<'info, Bankroll>>,
+    /// Required when the pool isn't backed.
+    pub admin: Option<Signer<'info>>,
+    #[account(mut, token::mint = bet_pool_token_account.mint, token::authority = user)]
+    pub user_token_account: Account<'info, TokenAccount>,
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateOdds<'info> {
+    pub admin: Signer<'info>,
+    #[account(mut, has_one = admin @ CommonError::Unauthorized)]
+    pub bet_pool: Account<'info, BetPool>,
+}
+
+#[derive(Accounts)]
+pub struct MigrateAccount<'info> {
+    /// CHECK: Owned by this program; type is identified by its discriminator before rewriting
+    #[account(mut, owner = crate::ID)]
+    pub target: UncheckedAccount<'info>,
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Define data structures
+#[account]
+pub struct UserProfile {
+    pub version: u8,
+    pub user_id: Pubkey,
+    pub total_bets: u64,
+    pub total_wins: u64, // not updated by resolution, which pays each bettor directly
+    pub betting_history: Vec<Bet>,
+    pub limits: BettingLimits, // added in v3
+    pub bet_count: u64, // added in v4; bets placed, the nonce of the next receipt
+}
+
+impl UserProfile {
+    pub const LEN: usize = 1 + 32 + 8 + 8 + (4 + MAX_HISTORY * Bet::LEN) + BettingLimits::LEN + 8;
+
+    /// Append to the history, dropping the oldest entry once it holds `MAX_HISTORY`.
+    fn record(&mut self, bet: Bet) {
+        if self.betting_history.len() >= MAX_HISTORY {
+            self.betting_history.remove(0);
+        }
+        self.betting_history.push(bet);
+    }
+}
+
+/// Responsible-gaming limits a user sets on themselves.
+#[derive(Clone, Default, AnchorSerialize, AnchorDeserialize)]
+pub struct BettingLimits {
+    pub max_daily_wager: u64, // 0 = no cap
+    pub self_excluded_until: i64,
+    pub pending_max_daily_wager: u64,
+    pub pending_self_excluded_until: i64,
+    pub pending_effective_at: i64, // 0 = nothing pending
+    pub wager_day: i64,            // unix day `wagered_today` counts towards
+    pub wagered_today: u64,
+}
+
+impl BettingLimits {
+    pub const LEN: usize = 8 * 7;
+
+    /// True if `(max_daily_wager, self_excluded_until)` is at least as strict as the active limits.
+    fn is_tighter(&self, max_daily_wager: u64, self_excluded_until: i64) -> bool {
+        let cap_tighter = max_daily_wager != 0
+            && (self.max_daily_wager == 0 || max_daily_wager <= self.max_daily_wager);
+        let cap_unchanged = max_daily_wager == self.max_daily_wager;
+        (cap_tighter || cap_unchanged) && self_excluded_until >= self.self_excluded_until
+    }
+
+    /// Promote queued limits whose cooldown has passed.
+    fn apply_pending(&mut self, now: i64) {
+        if self.pending_effective_at != 0 && now >= self.pending_effective_at {
+            self.max_daily_wager = self.pending_max_daily_wager;
+            self.self_excluded_until = self.pending_self_excluded_until;
+            self.pending_effective_at = 0;
+        }
+    }
+
+    /// Returns true if the limits applied immediately, false if they were queued.
+    fn request(&mut self, max_daily_wager: u64, self_excluded_until: i64, now: i64) -> bool {
+        self.apply_pending(now);
+        if self.is_tighter(max_daily_wager, self_excluded_until) {
+            self.max_daily_wager = max_daily_wager;
+            self.self_excluded_until = self_excluded_until;
+            self.pending_effective_at = 0;
+            true
+        } else {
+            self.pending_max_daily_wager = max_daily_wager;
+            self.pending_self_excluded_until = self_excluded_until;
+            self.pending_effective_at = now + LIMIT_COOLDOWN;
+            false
+        }
+    }
+
+    /// Check a new wager against the limits and count it towards today's total.
+    fn record_wager(&mut self, amount: u64, now: i64) -> Result<()> {
+        self.apply_pending(now);
+        require!(now >= self.self_excluded_until, BettingError::SelfExcluded);
+
+        let day = now / SECONDS_PER_DAY;
+        if day != self.wager_day {
+            self.wager_day = day;
+            self.wagered_today = 0;
+        }
+        let wagered = self.wagered_today.checked_add(amount).ok_or(CommonError::Overflow)?;
+        if self.max_daily_wager != 0 {
+            require!(wagered <= self.max_daily_wager, BettingError::DailyLimitExceeded);
+        }
+        self.wagered_today = wagered;
+        Ok(())
+    }
+}
+
+#[account]
+pub struct BetPool {
+    pub version: u8,
+    pub total_bets: u64,
+    pub bets: Vec<Bet>,
+    pub odds_bps: u64, // payout multiplier in basis points, 10_000 = 1.00x (f64 before v3)
+    pub outcome: String,
+    pub admin: Pubkey, // added in v2; pools migrated from v1 have no admin and cannot be resolved
+    pub bankroll: Pubkey, // added in v5; zero key if the admin banks the pool
+    pub max_payout_per_user: u64, // added in v6; 0 = no cap
+    pub resolution_deadline: i64, // added in v6; 0 = never expires
+    pub price_feed: Pubkey, // added in v7; zero key if bets aren't valued in USD
+    pub token_decimals: u8, // added in v7; decimals of the pool's token, for USD conversion
+    pub resolved_outcome: String, // added in v8; label makers settle against, empty until resolved
+    pub unclaimed: Vec<Unclaimed>, // added in v9; winnings a resolution couldn't pay out
+}
+
+impl BetPool {
+    pub const LEN: usize = 1
+        + 8
+        + (4 + MAX_POOL_BETS * Bet::LEN)
+        + 8
+        + (4 + MAX_LABEL_LEN)
+        + 32
+        + 32
+        + 8
+        + 8
+        + 32
+        + 1
+        + (4 + MAX_LABEL_LEN)
+        + (4 + MAX_POOL_BETS * Unclaimed::LEN);
+
+    pub fn is_backed(&self) -> bool {
+        self.bankroll != Pubkey::default()
+    }
+
+    /// True if bets are valued in USD through a price feed.
+    pub fn is_priced(&self) -> bool {
+        self.price_feed != Pubkey::default()
+    }
+
+    /// True once the outcome makers settle against has been set.
+    pub fn is_resolved(&self) -> bool {
+        !self.resolved_outcome.is_empty()
+    }
+
+    /// True once the deadline has passed without the pool being resolved.
+    pub fn is_expired(&self, now: i64) -> bool {
+        self.resolution_deadline != 0 && now > self.resolution_deadline
+    }
+
+    /// Fails if `bet` would take its bettor's open payouts in this pool past the cap.
+    pub fn check_payout_cap(&self, bet: &Bet) -> Result<()> {
+        self.check_user_payout(&bet.user_id, bet.payout().ok_or(CommonError::Overflow)?)
+    }
+
+    /// Fails if `payout` on top of `user`'s open bets in this pool would take them past the cap.
+    pub fn check_user_payout(&self, user: &Pubkey, mut payout: u64) -> Result<()> {
+        if self.max_payout_per_user == 0 {
+            return Ok(());
+        }
+        for open in self.bets.iter().filter(|open| open.user_id == *user) {
+            let open_payout = open.payout().ok_or(CommonError::Overflow)?;
+            payout = payout.checked_add(open_payout).ok_or(CommonError::Overflow)?;
+        }
+        require!(payout <= self.max_payout_per_user, BettingError::PayoutCapExceeded);
+        Ok(())
+    }
+}
+
+/// Per-bet record laid out for `getProgramAccounts` memcmp filters: the fields below sit at
+/// fixed offsets (`RECEIPT_*_OFFSET`) right after the discriminator and version byte.
+#[account]
+pub struct BetReceipt {
+    pub version: u8,
+    pub owner: Pubkey,
+    pub pool: Pubkey,
+    pub status: BetStatus,
+    pub amount: u64,
+    pub odds_bps: u64, // locked at placement
+    pub placed_at: i64,
+    pub bump: u8,
+    pub usd_value: u64, // added in v2; stake in USD at placement, 0 if the pool isn't priced
+    pub commission: u64, // added in v3; affiliate's cut of the stake, not refunded on a void
+}
+
+impl BetReceipt {
+    pub const LEN: usize = 1 + 32 + 32 + 1 + 8 + 8 + 8 + 1 + 8 + 8;
+}
+
+pub const RECEIPT_OWNER_OFFSET: usize = 8 + 1;
+pub const RECEIPT_POOL_OFFSET: usize = RECEIPT_OWNER_OFFSET + 32;
+pub const RECEIPT_STATUS_OFFSET: usize = RECEIPT_POOL_OFFSET + 32;
+
+#[derive(Clone, Copy, PartialEq, Eq, AnchorSerialize, AnchorDeserialize)]
+#[repr(u8)]
+pub enum BetStatus {
+    Open,
+    Settled,
+    Voided,
+}
+
+#[derive(Clone, AnchorSerialize, AnchorDeserialize)]
+pub struct Bet {
+    pub user_id: Pubkey,
+    pub amount: u64,
+    pub outcome: String,
+    pub odds_bps: u64, // locked at placement
+}
+
+impl Bet {
+    pub const LEN: usize = 32 + 8 + (4 + MAX_LABEL_LEN) + 8;
+
+    /// Total returned to the bettor if the bet wins, stake included.
+    pub fn payout(&self) -> Option<u64> {
+        scale_bps(self.amount, self.odds_bps, Rounding::Down)
+    }
+}
+
+/// Odds history for a pool, one event per change made by its maker.
+#[event]
+pub struct OddsUpdated {
+    pub pool: Pubkey,
+    pub previous_odds_bps: u64,
+    pub odds_bps: u64,
+    pub timestamp: i64,
+}
+
+/// A stake as it entered a pool, valued in USD when the pool has a price feed.
+#[event]
+pub struct BetPlaced {
+    pub pool: Pubkey,
+    pub bettor: Pubkey,
+    pub amount: u64,
+    pub usd_value: u64, // `pricing::USD_DECIMALS` decimals, 0 if the pool isn't priced
+    pub odds_bps: u64,
+    pub timestamp: i64,
+}
+
+/// Winnings left in the pool's paying account for `bettor` to claim.
+#[derive(Clone, AnchorSerialize, AnchorDeserialize)]
+pub struct Unclaimed {
+    pub bettor: Pubkey,
+    pub amount: u64,
+}
+
+impl Unclaimed {
+    pub const LEN: usize = 32 + 8;
+}
+
+/// What one winning bet was owed at resolution and what it received.
+#[derive(Clone, AnchorSerialize, AnchorDeserialize)]
+pub struct Allocation {
+    pub bettor: Pubkey,
+    pub claim: u64,
+    pub paid: u64, // below `claim` only when the paying account ran short
+    pub paid_usd: u64, // `paid` at the resolution price, 0 if the pool isn't priced
+}
+
+/// Per-bet payouts of a resolution, in the pool's bet order.
+#[event]
+pub struct SettlementAllocated {
+    pub pool: Pubkey,
+    pub available: u64,
+    pub allocations: Vec<Allocation>,
+}
+
+/// A winning bet's allocation that couldn't be paid and waits in `unclaimed`.
+#[event]
+pub struct WinningsHeld {
+    pub pool: Pubkey,
+    pub bettor: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+pub struct WinningsClaimed {
+    pub pool: Pubkey,
+    pub bettor: Pubkey,
+    pub amount: u64,
+}
+
+/// Define error handling; shared failures use `errors::CommonError`.
+#[error_code(offset = 7100)]
+pub enum BettingError {
+    #[msg("Invalid bet amount.")]
+    InvalidBetAmount,
+    #[msg("No bets found in the pool.")]
+    NoBetsInPool,
+    #[msg("Invalid outcome.")]
+    InvalidOutcome,
+    #[msg("Bet receipt belongs to a different pool.")]
+    ReceiptPoolMismatch,
+    #[msg("Odds must be positive.")]
+    InvalidOdds,
+    #[msg("User is self-excluded from betting.")]
+    SelfExcluded,
+    #[msg("Bet exceeds the user's daily wager limit.")]
+    DailyLimitExceeded,
+    #[msg("Bankroll does not match the pool.")]
+    BankrollMismatch,
+    #[msg("Bankroll utilization limit exceeded.")]
+    UtilizationExceeded,
+    #[msg("Utilization limit must be between 1 and 10000 bps.")]
+    InvalidUtilization,
+    #[msg("Invalid liquidity amount.")]
+    InvalidLiquidityAmount,
+    #[msg("Pool has open bets.")]
+    PoolHasOpenBets,
+    #[msg("Commission must not exceed 500 bps.")]
+    InvalidCommission,
+    #[msg("Affiliate does not match the pool's bankroll.")]
+    AffiliateMismatch,
+    #[msg("Bettors cannot refer themselves.")]
+    SelfReferral,
+    #[msg("Nothing to claim.")]
+    NothingToClaim,
+    #[msg("Market metadata is empty or too long.")]
+    InvalidMetadata,
+    #[msg("Bet exceeds the pool's per-user payout cap.")]
+    PayoutCapExceeded,
+    #[msg("Market passed its resolution deadline.")]
+    MarketExpired,
+    #[msg("Market has not passed its resolution deadline.")]
+    MarketNotExpired,
+    #[msg("Bet is not open.")]
+    BetNotOpen,
+    #[msg("Price feed does not match the pool.")]
+    PriceFeedMismatch,
+    #[msg("Price is unavailable or stale.")]
+    PriceUnavailable,
+    #[msg("Maker does not belong to the pool.")]
+    MakerMismatch,
+    #[msg("Maker has no quote for this outcome.")]
+    NoQuote,
+    #[msg("Bet exceeds the maker's free collateral.")]
+    MakerLiquidityExceeded,
+    #[msg("Maker has too many open positions.")]
+    TooManyPositions,
+    #[msg("Winning positions' token accounts are missing or out of order.")]
+    MakerPositionMismatch,
+    #[msg("Poker deal does not match the market's seats or commitments.")]
+    InvalidPokerDeal,
+    #[msg("Winning bets' token accounts are missing, out of order or not the bettors'.")]
+    WinnerAccountMismatch,
+    #[msg("Odds dropped below the bettor's minimum.")]
+    OddsBelowMinimum,
+    #[msg("Market has already been resolved.")]
+    MarketResolved,
+    #[msg("Market has not been resolved.")]
+    MarketNotResolved,
+    #[msg("Pool has too many open bets.")]
+    TooManyBets,
+    #[msg("User's betting history is full.")]
+    HistoryFull,
+    #[msg("Pool has a committed poker deal; resolve it from the cards.")]
+    PokerDealCommitted,
+}
+
+impl BettingError {
+    pub const ALL: [BettingError; 36] = [
+        BettingError::InvalidBetAmount,
+        BettingError::NoBetsInPool,
+        BettingError::InvalidOutcome,
+        BettingError::ReceiptPoolMismatch,
+        BettingError::InvalidOdds,
+        BettingError::SelfExcluded,
+        BettingError::DailyLimitExceeded,
+        BettingError::BankrollMismatch,
+        BettingError::UtilizationExceeded,
+        BettingError::InvalidUtilization,
+        BettingError::InvalidLiquidityAmount,
+        BettingError::PoolHasOpenBets,
+        BettingError::InvalidCommission,
+        BettingError::AffiliateMismatch,
+        BettingError::SelfReferral,
+        BettingError::NothingToClaim,
+        BettingError::InvalidMetadata,
+        BettingError::PayoutCapExceeded,
+        BettingError::MarketExpired,
+        BettingError::MarketNotExpired,
+        BettingError::BetNotOpen,
+        BettingError::PriceFeedMismatch,
+        BettingError::PriceUnavailable,
+        BettingError::MakerMismatch,
+        BettingError::NoQuote,
+        BettingError::MakerLiquidityExceeded,
+        BettingError::TooManyPositions,
+        BettingError::MakerPositionMismatch,
+        BettingError::InvalidPokerDeal,
+        BettingError::WinnerAccountMismatch,
+        BettingError::OddsBelowMinimum,
+        BettingError::MarketResolved,
+        BettingError::MarketNotResolved,
+        BettingError::TooManyBets,
+        BettingError::HistoryFull,
+        BettingError::PokerDealCommitted,
+    ];
+}
+
+impl Versioned for UserProfile {
+    const MIGRATIONS: &'static [Migration] =
+        &[legacy::user_profile_v1_to_v2, legacy::user_profile_v2_to_v3, legacy::user_profile_v3_to_v4];
+    // Allocated as 8 + size_of: user_id, two u64s and a Vec
+    const UNVERSIONED_LEN: Option<usize> = Some(8 + 32 + 8 + 8 + 24);
+
+    fn version(&self) -> u8 {
+        self.version
+    }
+}
+
+impl Versioned for BetReceipt {
+    const MIGRATIONS: &'static [Migration] = &[legacy::bet_receipt_v1_to_v2, legacy::bet_receipt_v2_to_v3];
+
+    fn version(&self) -> u8 {
+        self.version
+    }
+}
+
+impl Versioned for BetPool {
+    const MIGRATIONS: &'static [Migration] = &[
+        legacy::bet_pool_v1_to_v2,
+        legacy::bet_pool_v2_to_v3,
+        legacy::bet_pool_v3_to_v4,
+        legacy::bet_pool_v4_to_v5,
+        legacy::bet_pool_v5_to_v6,
+        legacy::bet_pool_v6_to_v7,
+        legacy::bet_pool_v7_to_v8,
+        legacy::bet_pool_v8_to_v9,
+    ];
+    // Allocated as 8 + size_of: a u64, a Vec, an f64 and a String. Version 1 pools padded to
+    // the same length, which `migrate` tells apart by what parses.
+    const UNVERSIONED_LEN: Option<usize> = Some(8 + 8 + 24 + 8 + 24);
+
+    fn version(&self) -> u8 {
+        self.version
+    }
+}
+
+/// Layouts of earlier account versions, kept for `migrate_account`.
+#[allow(dead_code)] // some layouts are only parsed to find where they end
+mod legacy {
+    use super::*;
+
+    /// `Bet` before odds were locked per bet.
+    #[derive(AnchorSerialize, AnchorDeserialize)]
+    pub struct BetV1 {
+        pub user_id: Pubkey,
+        pub amount: u64,
+        pub outcome: String,
+    }
+
+    impl BetV1 {
+        fn with_odds(self, odds_bps: u64) -> Bet {
+            Bet {
+                user_id: self.user_id,
+                amount: self.amount,
+                outcome: self.outcome,
+                odds_bps,
+            }
+        }
+    }
+
+    #[derive(AnchorDeserialize)]
+    pub struct BetPoolV1 {
+        pub total_bets: u64,
+        pub bets: Vec<BetV1>,
+        pub odds: f64,
+        pub outcome: String,
+    }
+
+    pub fn bet_pool_v1_to_v2(body: &[u8]) -> Option<Vec<u8>> {
+        insert_after::<BetPoolV1>(body, Pubkey::default().as_ref())
+    }
+
+    #[derive(AnchorDeserialize)]
+    pub struct BetPoolV2 {
+        pub total_bets: u64,
+        pub bets: Vec<BetV1>,
+        pub odds: f64,
+        pub outcome: String,
+        pub admin: Pubkey,
+    }
+
+    /// Converts float odds to basis points; the field keeps its 8-byte width.
+    pub fn bet_pool_v2_to_v3(body: &[u8]) -> Option<Vec<u8>> {
+        let mut rest = body;
+        let old = BetPoolV2::deserialize(&mut rest).ok()?;
+        if !old.odds.is_finite() || old.odds < 0.0 {
+            return None;
+        }
+
+        let mut migrated = Vec::with_capacity(body.len());
+        old.total_bets.serialize(&mut migrated).ok()?;
+        old.bets.serialize(&mut migrated).ok()?;
+        ((old.odds * BPS_DENOMINATOR as f64).round() as u64).serialize(&mut migrated).ok()?;
+        old.outcome.serialize(&mut migrated).ok()?;
+        old.admin.serialize(&mut migrated).ok()?;
+        migrated.extend_from_slice(rest);
+        Some(migrated)
+    }
+
+    #[derive(AnchorDeserialize)]
+    pub struct BetPoolV3 {
+        pub total_bets: u64,
+        pub bets: Vec<BetV1>,
+        pub odds_bps: u64,
+        pub outcome: String,
+        pub admin: Pubkey,
+    }
+
+    /// Open bets were always paid at the pool's current odds, so that is what they lock in.
+    pub fn bet_pool_v3_to_v4(body: &[u8]) -> Option<Vec<u8>> {
+        let mut rest = body;
+        let old = BetPoolV3::deserialize(&mut rest).ok()?;
+        let bets: Vec<Bet> = old.bets.into_iter().map(|bet| bet.with_odds(old.odds_bps)).collect();
+
+        let mut migrated = Vec::with_capacity(body.len() + 8 * bets.len());
+        old.total_bets.serialize(&mut migrated).ok()?;
+        bets.serialize(&mut migrated).ok()?;
+        old.odds_bps.serialize(&mut migrated).ok()?;
+        old.outcome.serialize(&mut migrated).ok()?;
+        old.admin.serialize(&mut migrated).ok()?;
+        migrated.extend_from_slice(rest);
+        Some(migrated)
+    }
+
+    #[derive(AnchorDeserialize)]
+    pub struct UserProfileV1 {
+        pub user_id: Pubkey,
+        pub total_bets: u64,
+        pub total_wins: u64,
+        pub betting_history: Vec<BetV1>,
+    }
+
+    /// Past bets' odds weren't recorded; history entries get 1.00x.
+    pub fn user_profile_v1_to_v2(body: &[u8]) -> Option<Vec<u8>> {
+        let mut rest = body;
+        let old = UserProfileV1::deserialize(&mut rest).ok()?;
+        let history: Vec<Bet> = old
+            .betting_history
+            .into_iter()
+            .map(|bet| bet.with_odds(BPS_DENOMINATOR))
+            .collect();
+
+        let mut migrated = Vec::with_capacity(body.len() + 8 * history.len());
+        old.user_id.serialize(&mut migrated).ok()?;
+        old.total_bets.serialize(&mut migrated).ok()?;
+        old.total_wins.serialize(&mut migrated).ok()?;
+        history.serialize(&mut migrated).ok()?;
+        migrated.extend_from_slice(rest);
+        Some(migrated)
+    }
+
+    #[derive(AnchorDeserialize)]
+    pub struct UserProfileV2 {
+        pub user_id: Pubkey,
+        pub total_bets: u64,
+        pub total_wins: u64,
+        pub betting_history: Vec<Bet>,
+    }
+
+    /// Existing profiles start with no limits.
+    pub fn user_profile_v2_to_v3(body: &[u8]) -> Option<Vec<u8>> {
+        insert_after::<UserProfileV2>(body, &[0u8; BettingLimits::LEN])
+    }
+
+    #[derive(AnchorDeserialize)]
+    pub struct UserProfileV3 {
+        pub user_id: Pubkey,
+        pub total_bets: u64,
+        pub total_wins: u64,
+        pub betting_history: Vec<Bet>,
+        pub limits: BettingLimits,
+    }
+
+    /// Receipts were numbered by history length, which only grew until now, so counting
+    /// resumes there and never reuses a nonce.
+    pub fn user_profile_v3_to_v4(body: &[u8]) -> Option<Vec<u8>> {
+        let old = UserProfileV3::deserialize(&mut &body[..]).ok()?;
+        insert_after::<UserProfileV3>(body, &(old.betting_history.len() as u64).to_le_bytes())
+    }
+
+    #[derive(AnchorDeserialize)]
+    pub struct BetPoolV4 {
+        pub total_bets: u64,
+        pub bets: Vec<Bet>,
+        pub odds_bps: u64,
+        pub outcome: String,
+        pub admin: Pubkey,
+    }
+
+    /// Existing pools stay banked by their admin.
+    pub fn bet_pool_v4_to_v5(body: &[u8]) -> Option<Vec<u8>> {
+        insert_after::<BetPoolV4>(body, Pubkey::default().as_ref())
+    }
+
+    #[derive(AnchorDeserialize)]
+    pub struct BetPoolV5 {
+        pub total_bets: u64,
+        pub bets: Vec<Bet>,
+        pub odds_bps: u64,
+        pub outcome: String,
+        pub admin: Pubkey,
+        pub bankroll: Pubkey,
+    }
+
+    /// Existing pools get no payout cap and no deadline.
+    pub fn bet_pool_v5_to_v6(body: &[u8]) -> Option<Vec<u8>> {
+        insert_after::<BetPoolV5>(body, &[0u8; 8 + 8])
+    }
+
+    #[derive(AnchorDeserialize)]
+    pub struct BetPoolV6 {
+        pub total_bets: u64,
+        pub bets: Vec<Bet>,
+        pub odds_bps: u64,
+        pub outcome: String,
+        pub admin: Pubkey,
+        pub bankroll: Pubkey,
+        pub max_payout_per_user: u64,
+        pub resolution_deadline: i64,
+    }
+
+    /// Existing pools aren't priced.
+    pub fn bet_pool_v6_to_v7(body: &[u8]) -> Option<Vec<u8>> {
+        insert_after::<BetPoolV6>(body, &[0u8; 32 + 1])
+    }
+
+    #[derive(AnchorDeserialize)]
+    pub struct BetPoolV7 {
+        pub total_bets: u64,
+        pub bets: Vec<Bet>,
+        pub odds_bps: u64,
+        pub outcome: String,
+        pub admin: Pubkey,
+        pub bankroll: Pubkey,
+        pub max_payout_per_user: u64,
+        pub resolution_deadline: i64,
+        pub price_feed: Pubkey,
+        pub token_decimals: u8,
+    }
+
+    /// Existing pools count as unresolved: an empty string, length prefix only.
+    pub fn bet_pool_v7_to_v8(body: &[u8]) -> Option<Vec<u8>> {
+        insert_after::<BetPoolV7>(body, &0u32.to_le_bytes())
+    }
+
+    #[derive(AnchorDeserialize)]
+    pub struct BetPoolV8 {
+        pub total_bets: u64,
+        pub bets: Vec<Bet>,
+        pub odds_bps: u64,
+        pub outcome: String,
+        pub admin: Pubkey,
+        pub bankroll: Pubkey,
+        pub max_payout_per_user: u64,
+        pub resolution_deadline: i64,
+        pub price_feed: Pubkey,
+        pub token_decimals: u8,
+        pub resolved_outcome: String,
+    }
+
+    /// Resolutions before v9 paid every winner or failed, so nothing is owed.
+    pub fn bet_pool_v8_to_v9(body: &[u8]) -> Option<Vec<u8>> {
+        insert_after::<BetPoolV8>(body, &0u32.to_le_bytes())
+    }
+
+    #[derive(AnchorDeserialize)]
+    pub struct BetReceiptV1 {
+        pub owner: Pubkey,
+        pub pool: Pubkey,
+        pub status: BetStatus,
+        pub amount: u64,
+        pub odds_bps: u64,
+        pub placed_at: i64,
+        pub bump: u8,
+    }
+
+    /// Bets placed before pricing have no recorded USD value.
+    pub fn bet_receipt_v1_to_v2(body: &[u8]) -> Option<Vec<u8>> {
+        insert_after::<BetReceiptV1>(body, &[0u8; 8])
+    }
+
+    #[derive(AnchorDeserialize)]
+    pub struct BetReceiptV2 {
+        pub owner: Pubkey,
+        pub pool: Pubkey,
+        pub status: BetStatus,
+        pub amount: u64,
+        pub odds_bps: u64,
+        pub placed_at: i64,
+        pub bump: u8,
+        pub usd_value: u64,
+    }
+
+    /// Earlier bets keep the full refund they were placed under.
+    pub fn bet_receipt_v2_to_v3(body: &[u8]) -> Option<Vec<u8>> {
+        insert_after::<BetReceiptV2>(body, &[0u8; 8])
+    }
+}
@@ -0,0 +1,501 @@
+//! Market makers laying odds against bettors.
+//!
+//! A bankroll-backed pool's admin registers makers. Each maker quotes odds per outcome (indices
+//! into the market's `outcome_labels`) and backs them with collateral in its own vault, in the
+//! bankroll's token. Bettors who take a quote stake into that vault and lock the maker's side of
+//! the payout. Matched positions never enter the pool's parimutuel `bets`. Quotes close once the
+//! admin names the winning label with `resolve_makers` (poker markets name it from the cards).
+//! The admin then settles each maker against that label, and the maker's running PnL is kept
+//! apart from the pot. If the pool expires unresolved, anyone can void a maker's positions:
+//! bettors get their stakes back and the locked risk returns to the maker's free collateral.
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Mint, Token, TokenAccount, Transfer};
+use errors::CommonError;
+use fixed_math::{scale_bps, Rounding, BPS_DENOMINATOR};
+use guards::require_owner;
+use pause_registry::{PauseRegistry, BETTING, REGISTRY_SEED};
+use versioning::Versioned;
+
+use crate::bankroll::Bankroll;
+use crate::poker_markets::{self, POKER_DEAL_SEED};
+use crate::{BetPool, BettingError, MarketMetadata, UserProfile, MARKET_METADATA_SEED, MAX_OUTCOMES};
+
+pub const MAKER_SEED: &[u8] = b"maker";
+/// Open positions one maker can carry before it must be settled.
+pub const MAX_POSITIONS: usize = 32;
+
+pub(crate) fn register_maker(ctx: Context<RegisterMaker>) -> Result<()> {
+    let maker = &mut ctx.accounts.maker;
+    maker.version = Maker::CURRENT_VERSION;
+    maker.bet_pool = ctx.accounts.bet_pool.key();
+    maker.wallet = ctx.accounts.wallet.key();
+    maker.vault = ctx.accounts.vault.key();
+    maker.quotes = Vec::new();
+    maker.free = 0;
+    maker.locked = 0;
+    maker.realized_pnl = 0;
+    maker.positions = Vec::new();
+    maker.bump = *ctx.bumps.get("maker").unwrap();
+
+    msg!("Maker {:?} registered on pool {:?}", maker.wallet, maker.bet_pool);
+    Ok(())
+}
+
+/// Replace the maker's quotes and move collateral so exactly `size` is free to back new matches.
+/// Odds of 0 leave an outcome unquoted; an empty list with a zero `size` withdraws entirely.
+pub(crate) fn provide_quotes(ctx: Context<ProvideQuotes>, outcome_odds: Vec<u64>, size: u64) -> Result<()> {
+    require!(outcome_odds.len() <= MAX_OUTCOMES, BettingError::InvalidOutcome);
+    // Laying below 1.00x would pay winners less than their stake
+    require!(
+        outcome_odds.iter().all(|&odds| odds == 0 || odds >= BPS_DENOMINATOR),
+        BettingError::InvalidOdds
+    );
+
+    let maker = &mut ctx.accounts.maker;
+    if size > maker.free {
+        token::transfer(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.wallet_token_account.to_account_info(),
+                    to: ctx.accounts.vault.to_account_info(),
+                    authority: ctx.accounts.wallet.to_account_info(),
+                },
+            ),
+            size - maker.free,
+        )?;
+    } else if size < maker.free {
+        let (bet_pool, wallet) = (maker.bet_pool, maker.wallet);
+        let seeds = &[MAKER_SEED, bet_pool.as_ref(), wallet.as_ref(), &[maker.bump]];
+        token::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.vault.to_account_info(),
+                    to: ctx.accounts.wallet_token_account.to_account_info(),
+                    authority: maker.to_account_info(),
+                },
+                &[&seeds[..]],
+            ),
+            maker.free - size,
+        )?;
+    }
+    maker.free = size;
+    maker.quotes = outcome_odds;
+
+    emit!(QuotesProvided {
+        pool: maker.bet_pool,
+        maker: maker.wallet,
+        outcome_odds: maker.quotes.clone(),
+        size,
+    });
+    Ok(())
+}
+
+/// Back `outcome` for `amount` at the maker's quoted odds. Fails if the maker has moved the
+/// quote below `min_odds_bps` before the transaction lands.
+pub(crate) fn take_quote(
+    ctx: Context<TakeQuote>,
+    outcome: u8,
+    amount: u64,
+    min_odds_bps: u64,
+) -> Result<()> {
+    let user = &ctx.accounts.user;
+    require!(amount > 0, BettingError::InvalidBetAmount);
+    require_owner!(user, ctx.accounts.user_profile.user_id, CommonError::Unauthorized);
+    let now = Clock::get()?.unix_timestamp;
+    require!(!ctx.accounts.bet_pool.is_expired(now), BettingError::MarketExpired);
+    require!(!ctx.accounts.bet_pool.is_resolved(), BettingError::MarketResolved);
+    ctx.accounts.user_profile.limits.record_wager(amount, now)?;
+
+    let maker = &mut ctx.accounts.maker;
+    let odds_bps = maker
+        .quotes
+        .get(outcome as usize)
+        .copied()
+        .filter(|&odds| odds > 0)
+        .ok_or(BettingError::NoQuote)?;
+    require!(odds_bps >= min_odds_bps, BettingError::OddsBelowMinimum);
+    let payout = scale_bps(amount, odds_bps, Rounding::Down).ok_or(CommonError::Overflow)?;
+    // What the maker adds to the stake if the bettor wins
+    let risk = payout.saturating_sub(amount);
+    require!(risk <= maker.free, BettingError::MakerLiquidityExceeded);
+    require!(maker.positions.len() < MAX_POSITIONS, BettingError::TooManyPositions);
+
+    // The pool's cap covers the bettor's positions with this maker alongside their pool bets
+    let mut total = payout;
+    for position in maker.positions.iter().filter(|position| position.bettor == user.key()) {
+        let open = position.payout().ok_or(CommonError::Overflow)?;
+        total = total.checked_add(open).ok_or(CommonError::Overflow)?;
+    }
+    ctx.accounts.bet_pool.check_user_payout(&user.key(), total)?;
+
+    token::transfer(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.user_token_account.to_account_info(),
+                to: ctx.accounts.vault.to_account_info(),
+                authority: user.to_account_info(),
+            },
+        ),
+        amount,
+    )?;
+
+    maker.free -= risk;
+    maker.locked = maker.locked.checked_add(payout).ok_or(CommonError::Overflow)?;
+    maker.positions.push(Position {
+        bettor: user.key(),
+        token_account: ctx.accounts.user_token_account.key(),
+        outcome,
+        stake: amount,
+        odds_bps,
+    });
+
+    emit!(QuoteTaken {
+        pool: maker.bet_pool,
+        maker: maker.wallet,
+        bettor: user.key(),
+        outcome,
+        amount,
+        odds_bps,
+    });
+    Ok(())
+}
+
+/// Close quotes on the pool and name the label at `winning_outcome` as the one makers settle
+/// against. Poker markets set it themselves when their deal is resolved.
+pub(crate) fn resolve_makers(ctx: Context<ResolveMakers>, winning_outcome: u8) -> Result<()> {
+    poker_markets::require_no_deal(&ctx.accounts.poker_deal)?;
+    let bet_pool = &mut ctx.accounts.bet_pool;
+    require!(!bet_pool.is_resolved(), BettingError::MarketResolved);
+    require!(
+        !bet_pool.is_expired(Clock::get()?.unix_timestamp),
+        BettingError::MarketExpired
+    );
+    let label = ctx
+        .accounts
+        .metadata
+        .outcome_labels
+        .get(winning_outcome as usize)
+        .ok_or(BettingError::InvalidOutcome)?;
+    bet_pool.resolved_outcome = label.clone();
+
+    msg!("Makers on pool {:?} settle against {:?}", bet_pool.key(), label);
+    Ok(())
+}
+
+/// Settle every open position of one maker against the pool's resolved outcome, located among
+/// the market's labels. The winning positions' token accounts are passed as remaining accounts,
+/// in position order.
+pub(crate) fn settle_maker<'info>(ctx: Context<'_, '_, '_, 'info, SettleMaker<'info>>) -> Result<()> {
+    let resolved = &ctx.accounts.bet_pool.resolved_outcome;
+    require!(!resolved.is_empty(), BettingError::MarketNotResolved);
+    // A pool resolved to an outcome no label names leaves every position losing
+    let labels = &ctx.accounts.metadata.outcome_labels;
+    let winning_outcome = labels.iter().position(|label| label == resolved).map(|i| i as u8);
+
+    let maker_info = ctx.accounts.maker.to_account_info();
+    let maker = &mut ctx.accounts.maker;
+    let (bet_pool, wallet) = (maker.bet_pool, maker.wallet);
+    let seeds = &[MAKER_SEED, bet_pool.as_ref(), wallet.as_ref(), &[maker.bump]];
+
+    let mut winners = ctx.remaining_accounts.iter();
+    let mut pnl: i64 = 0;
+    for position in std::mem::take(&mut maker.positions) {
+        let payout = position.payout().ok_or(CommonError::Overflow)?;
+        if Some(position.outcome) == winning_outcome {
+            let token_account = winners.next().ok_or(BettingError::MakerPositionMismatch)?;
+            require_keys_eq!(
+                token_account.key(),
+                position.token_account,
+                BettingError::MakerPositionMismatch
+            );
+            token::transfer(
+                CpiContext::new_with_signer(
+                    ctx.accounts.token_program.to_account_info(),
+                    Transfer {
+                        from: ctx.accounts.vault.to_account_info(),
+                        to: token_account.clone(),
+                        authority: maker_info.clone(),
+                    },
+                    &[&seeds[..]],
+                ),
+                payout,
+            )?;
+            let loss = i64::try_from(payout - position.stake).map_err(|_| CommonError::Overflow)?;
+            pnl = pnl.checked_sub(loss).ok_or(CommonError::Overflow)?;
+        } else {
+            // Stake and the locked risk both return to the maker's free collateral
+            maker.free = maker.free.checked_add(payout).ok_or(CommonError::Overflow)?;
+            let gain = i64::try_from(position.stake).map_err(|_| CommonError::Overflow)?;
+            pnl = pnl.checked_add(gain).ok_or(CommonError::Overflow)?;
+        }
+    }
+    maker.locked = 0;
+    maker.realized_pnl = maker.realized_pnl.checked_add(pnl).ok_or(CommonError::Overflow)?;
+
+    emit!(MakerSettled {
+        pool: bet_pool,
+        maker: wallet,
+        winning_outcome,
+        pnl,
+        realized_pnl: maker.realized_pnl,
+    });
+    Ok(())
+}
+
+/// Refund every open position of one maker on a pool that expired without being resolved.
+/// Every position's token account is passed as a remaining account, in position order.
+pub(crate) fn void_maker<'info>(ctx: Context<'_, '_, '_, 'info, VoidMaker<'info>>) -> Result<()> {
+    let bet_pool = &ctx.accounts.bet_pool;
+    require!(
+        bet_pool.is_expired(Clock::get()?.unix_timestamp),
+        BettingError::MarketNotExpired
+    );
+    require!(!bet_pool.is_resolved(), BettingError::MarketResolved);
+
+    let maker_info = ctx.accounts.maker.to_account_info();
+    let maker = &mut ctx.accounts.maker;
+    let (bet_pool, wallet) = (maker.bet_pool, maker.wallet);
+    let seeds = &[MAKER_SEED, bet_pool.as_ref(), wallet.as_ref(), &[maker.bump]];
+
+    let mut token_accounts = ctx.remaining_accounts.iter();
+    let mut refunded: u64 = 0;
+    for position in std::mem::take(&mut maker.positions) {
+        let token_account = token_accounts.next().ok_or(BettingError::MakerPositionMismatch)?;
+        require_keys_eq!(
+            token_account.key(),
+            position.token_account,
+            BettingError::MakerPositionMismatch
+        );
+        token::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.vault.to_account_info(),
+                    to: token_account.clone(),
+                    authority: maker_info.clone(),
+                },
+                &[&seeds[..]],
+            ),
+            position.stake,
+        )?;
+
+        // Only the maker's side of the payout was ever collateral
+        let payout = position.payout().ok_or(CommonError::Overflow)?;
+        maker.free = maker
+            .free
+            .checked_add(payout - position.stake)
+            .ok_or(CommonError::Overflow)?;
+        refunded = refunded.checked_add(position.stake).ok_or(CommonError::Overflow)?;
+    }
+    maker.locked = 0;
+
+    emit!(MakerVoided {
+        pool: bet_pool,
+        maker: wallet,
+        refunded,
+    });
+    Ok(())
+}
+
+#[account]
+pub struct Maker {
+    pub version: u8,
+    pub bet_pool: Pubkey,
+    pub wallet: Pubkey,
+    pub vault: Pubkey,
+    pub quotes: Vec<u64>, // odds in bps per outcome index, 0 = not quoted
+    pub free: u64,        // collateral in the vault not backing any position
+    pub locked: u64,      // full payouts of open positions, stakes included
+    pub realized_pnl: i64,
+    pub positions: Vec<Position>,
+    pub bump: u8,
+}
+
+impl Maker {
+    pub const LEN: usize =
+        1 + 32 + 32 + 32 + (4 + 8 * MAX_OUTCOMES) + 8 + 8 + 8 + (4 + MAX_POSITIONS * Position::LEN) + 1;
+}
+
+impl Versioned for Maker {
+    fn version(&self) -> u8 {
+        self.version
+    }
+}
+
+/// A bettor's match against a maker's quote.
+#[derive(Clone, AnchorSerialize, AnchorDeserialize)]
+pub struct Position {
+    pub bettor: Pubkey,
+    pub token_account: Pubkey, // paid on a win
+    pub outcome: u8,
+    pub stake: u64,
+    pub odds_bps: u64, // locked when the quote was taken
+}
+
+impl Position {
+    pub const LEN: usize = 32 + 32 + 1 + 8 + 8;
+
+    pub fn payout(&self) -> Option<u64> {
+        scale_bps(self.stake, self.odds_bps, Rounding::Down)
+    }
+}
+
+#[derive(Accounts)]
+pub struct RegisterMaker<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    #[account(has_one = admin @ CommonError::Unauthorized)]
+    pub bet_pool: Account<'info, BetPool>,
+    /// Makers only run on backed pools, whose bankroll fixes the token.
+    #[account(address = bet_pool.bankroll @ BettingError::BankrollMismatch)]
+    pub bankroll: Account<'info, Bankroll>,
+    /// CHECK: Maker's wallet; only recorded
+    pub wallet: UncheckedAccount<'info>,
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + Maker::LEN,
+        seeds = [MAKER_SEED, bet_pool.key().as_ref(), wallet.key().as_ref()],
+        bump
+    )]
+    pub maker: Account<'info, Maker>,
+    /// The bankroll's token.
+    #[account(address = bankroll.mint @ BettingError::BankrollMismatch)]
+    pub mint: Account<'info, Mint>,
+    #[account(
+        init,
+        payer = admin,
+        token::mint = mint,
+        token::authority = maker,
+        seeds = [b"maker_vault", maker.key().as_ref()],
+        bump
+    )]
+    pub vault: Account<'info, TokenAccount>,
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+#[derive(Accounts)]
+pub struct ProvideQuotes<'info> {
+    pub wallet: Signer<'info>,
+    #[account(mut, has_one = wallet @ CommonError::Unauthorized, has_one = vault)]
+    pub maker: Account<'info, Maker>,
+    #[account(mut)]
+    pub vault: Account<'info, TokenAccount>,
+    #[account(mut, token::mint = vault.mint, token::authority = wallet)]
+    pub wallet_token_account: Account<'info, TokenAccount>,
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct TakeQuote<'info> {
+    pub user: Signer<'info>,
+    #[account(mut)]
+    pub user_profile: Account<'info, UserProfile>,
+    pub bet_pool: Account<'info, BetPool>,
+    #[account(mut, has_one = bet_pool @ BettingError::MakerMismatch, has_one = vault)]
+    pub maker: Account<'info, Maker>,
+    #[account(mut)]
+    pub vault: Account<'info, TokenAccount>,
+    #[account(mut, token::mint = vault.mint, token::authority = user)]
+    pub user_token_account: Account<'info, TokenAccount>,
+    #[account(
+        seeds = [REGISTRY_SEED],
+        bump = pause_registry.bump,
+        seeds::program = pause_registry::ID,
+        constraint = !pause_registry.is_paused(BETTING) @ CommonError::Paused
+    )]
+    pub pause_registry: Account<'info, PauseRegistry>,
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct ResolveMakers<'info> {
+    pub admin: Signer<'info>,
+    #[account(mut, has_one = admin @ CommonError::Unauthorized)]
+    pub bet_pool: Account<'info, BetPool>,
+    #[account(seeds = [MARKET_METADATA_SEED, bet_pool.key().as_ref()], bump = metadata.bump)]
+    pub metadata: Account<'info, MarketMetadata>,
+    /// CHECK: The pool's poker deal, if one was ever committed; see `poker_markets::require_no_deal`
+    #[account(seeds = [POKER_DEAL_SEED, bet_pool.key().as_ref()], bump)]
+    pub poker_deal: UncheckedAccount<'info>,
+    #[account(
+        seeds = [REGISTRY_SEED],
+        bump = pause_registry.bump,
+        seeds::program = pause_registry::ID,
+        constraint = !pause_registry.is_paused(BETTING) @ CommonError::Paused
+    )]
+    pub pause_registry: Account<'info, PauseRegistry>,
+}
+
+#[derive(Accounts)]
+pub struct SettleMaker<'info> {
+    pub admin: Signer<'info>,
+    #[account(has_one = admin @ CommonError::Unauthorized)]
+    pub bet_pool: Account<'info, BetPool>,
+    #[account(seeds = [MARKET_METADATA_SEED, bet_pool.key().as_ref()], bump = metadata.bump)]
+    pub metadata: Account<'info, MarketMetadata>,
+    #[account(mut, has_one = bet_pool @ BettingError::MakerMismatch, has_one = vault)]
+    pub maker: Account<'info, Maker>,
+    #[account(mut)]
+    pub vault: Account<'info, TokenAccount>,
+    #[account(
+        seeds = [REGISTRY_SEED],
+        bump = pause_registry.bump,
+        seeds::program = pause_registry::ID,
+        constraint = !pause_registry.is_paused(BETTING) @ CommonError::Paused
+    )]
+    pub pause_registry: Account<'info, PauseRegistry>,
+    pub token_program: Program<'info, Token>,
+}
+
+/// Deliberately not gated by the pause registry: refunds must not depend on any admin.
+#[derive(Accounts)]
+pub struct VoidMaker<'info> {
+    pub bet_pool: Account<'info, BetPool>,
+    #[account(mut, has_one = bet_pool @ BettingError::MakerMismatch, has_one = vault)]
+    pub maker: Account<'info, Maker>,
+    #[account(mut)]
+    pub vault: Account<'info, TokenAccount>,
+    pub token_program: Program<'info, Token>,
+}
+
+#[event]
+pub struct QuotesProvided {
+    pub pool: Pubkey,
+    pub maker: Pubkey,
+    pub outcome_odds: Vec<u64>,
+    pub size: u64,
+}
+
+#[event]
+pub struct QuoteTaken {
+    pub pool: Pubkey,
+    pub maker: Pubkey,
+    pub bettor: Pubkey,
+    pub outcome: u8,
+    pub amount: u64,
+    pub odds_bps: u64,
+}
+
+/// One maker's result for a settlement, separate from the pool's parimutuel payouts.
+#[event]
+pub struct MakerSettled {
+    pub pool: Pubkey,
+    pub maker: Pubkey,
+    pub winning_outcome: Option<u8>, // `None` if no label matches the resolved outcome
+    pub pnl: i64,
+    pub realized_pnl: i64,
+}
+
+/// A maker's positions refunded after its pool expired unresolved.
+#[event]
+pub struct MakerVoided {
+    pub pool: Pubkey,
+    pub maker: Pubkey,
+    pub refunded: u64, // stakes returned to bettors
+}
@@ -0,0 +1,97 @@
+//! Display metadata for betting pools.
+//!
+//! Labels, descriptions and tags live in a `MarketMetadata` account beside the pool so `BetPool`
+//! stays small. The pool's admin can rewrite it while the pool has no bets; once one lands the
+//! text bettors saw is frozen until the pool is resolved. Makers settle by label position, so
+//! after the first resolution it stays frozen for good.
+use anchor_lang::prelude::*;
+use errors::CommonError;
+use versioning::Versioned;
+
+use crate::{BetPool, BettingError};
+
+pub const MARKET_METADATA_SEED: &[u8] = b"market_metadata";
+
+pub const MAX_OUTCOMES: usize = 8;
+pub const MAX_LABEL_LEN: usize = 32;
+pub const MAX_DESCRIPTION_LEN: usize = 256;
+pub const MAX_TAGS: usize = 4;
+pub const MAX_TAG_LEN: usize = 16;
+
+pub(crate) fn set_market_metadata(
+    ctx: Context<SetMarketMetadata>,
+    outcome_labels: Vec<String>,
+    description: String,
+    tags: Vec<String>,
+) -> Result<()> {
+    require!(ctx.accounts.bet_pool.bets.is_empty(), BettingError::PoolHasOpenBets);
+    // Makers settle by label position, so labels are fixed once the pool resolves
+    require!(!ctx.accounts.bet_pool.is_resolved(), BettingError::MarketResolved);
+    require!(
+        !outcome_labels.is_empty() && within(&outcome_labels, MAX_OUTCOMES, MAX_LABEL_LEN),
+        BettingError::InvalidMetadata
+    );
+    require!(
+        !description.is_empty() && description.len() <= MAX_DESCRIPTION_LEN,
+        BettingError::InvalidMetadata
+    );
+    require!(within(&tags, MAX_TAGS, MAX_TAG_LEN), BettingError::InvalidMetadata);
+
+    let metadata = &mut ctx.accounts.metadata;
+    metadata.version = MarketMetadata::CURRENT_VERSION;
+    metadata.pool = ctx.accounts.bet_pool.key();
+    metadata.outcome_labels = outcome_labels;
+    metadata.description = description;
+    metadata.tags = tags;
+    metadata.bump = *ctx.bumps.get("metadata").unwrap();
+
+    msg!("Metadata set for pool {:?}", metadata.pool);
+    Ok(())
+}
+
+/// At most `max` non-empty entries of at most `max_len` bytes each.
+fn within(entries: &[String], max: usize, max_len: usize) -> bool {
+    entries.len() <= max && entries.iter().all(|e| !e.is_empty() && e.len() <= max_len)
+}
+
+#[account]
+pub struct MarketMetadata {
+    pub version: u8,
+    pub pool: Pubkey,
+    pub outcome_labels: Vec<String>,
+    pub description: String,
+    pub tags: Vec<String>,
+    pub bump: u8,
+}
+
+impl MarketMetadata {
+    pub const LEN: usize = 1
+        + 32
+        + 4 + MAX_OUTCOMES * (4 + MAX_LABEL_LEN)
+        + 4 + MAX_DESCRIPTION_LEN
+        + 4 + MAX_TAGS * (4 + MAX_TAG_LEN)
+        + 1;
+}
+
+impl Versioned for MarketMetadata {
+    fn version(&self) -> u8 {
+        self.version
+    }
+}
+
+#[derive(Accounts)]
+pub struct SetMarketMetadata<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    #[account(has_one = admin @ CommonError::Unauthorized)]
+    pub bet_pool: Account<'info, BetPool>,
+    #[account(
+        init_if_needed,
+        payer = admin,
+        space = 8 + MarketMetadata::LEN,
+        seeds = [MARKET_METADATA_SEED, bet_pool.key().as_ref()],
+        bump
+    )]
+    pub metadata: Account<'info, MarketMetadata>,
+    pub system_program: Program<'info, System>,
+}
@@ -0,0 +1,174 @@
+//! Markets on a poker hand, resolved from the cards rather than the admin's word.
+//!
+//! Each seat is one of the market's `outcome_labels`, and the pool backs the seat named by its
+//! `outcome`. Before any bet, every seated player's commitment (see `poker::shuffle`) is
+//! recorded. At resolution the players' secrets are submitted. The program re-derives the deck,
+//! deals five cards per seat in seat order, and settles the pool from `poker::winning_hands`.
+//! Nobody can choose the winner: the deal depends on every secret, and the commitments were fixed
+//! before money came in. A seat that ties for the best hand counts as a win.
+//!
+//! Only bankroll-backed pools take poker deals, so payouts never need the admin's signature:
+//! anyone holding every secret can resolve the market. While a deal is committed, `resolve_bets`
+//! and `resolve_makers` refuse the pool, so the admin can't name a winner around the cards. A pool
+//! that is never resolved can be voided after its deadline.
+//!
+//! Players should hand their secrets to the resolver rather than publish them. Once all secrets
+//! are public the result is known, and bets could still be placed until the pool resolves.
+use anchor_lang::prelude::*;
+use errors::CommonError;
+use poker::shuffle;
+use versioning::Versioned;
+
+// The derive on `ResolvePokerMarket` needs the client modules of the nested `SettlePool`
+use crate::{__client_accounts_settle_pool, __cpi_client_accounts_settle_pool};
+use crate::{settle_pool, BetPool, BettingError, MarketMetadata, SettlePool, MARKET_METADATA_SEED, MAX_OUTCOMES};
+
+pub const POKER_DEAL_SEED: &[u8] = b"poker_deal";
+/// Cards dealt to each seat.
+pub const HAND_SIZE: usize = 5;
+
+pub(crate) fn commit_poker_deal(ctx: Context<CommitPokerDeal>, commitments: Vec<[u8; 32]>) -> Result<()> {
+    let bet_pool = &ctx.accounts.bet_pool;
+    let labels = &ctx.accounts.metadata.outcome_labels;
+    require!(bet_pool.bets.is_empty(), BettingError::PoolHasOpenBets);
+    // Resolution is permissionless, so payouts must come from the bankroll rather than the admin
+    require!(bet_pool.is_backed(), BettingError::BankrollMismatch);
+    require!(
+        commitments.len() >= 2 && commitments.len() == labels.len(),
+        BettingError::InvalidPokerDeal
+    );
+    require!(labels.contains(&bet_pool.outcome), BettingError::InvalidOutcome);
+
+    let deal = &mut ctx.accounts.poker_deal;
+    // A deal stays committed until the pool it backs is resolved
+    require!(deal.commitments.is_empty(), BettingError::InvalidPokerDeal);
+    deal.version = PokerDeal::CURRENT_VERSION;
+    deal.bet_pool = bet_pool.key();
+    deal.commitments = commitments;
+    deal.bump = *ctx.bumps.get("poker_deal").unwrap();
+
+    msg!("Poker deal committed for pool {:?}", deal.bet_pool);
+    Ok(())
+}
+
+/// `secrets` are the players' revealed secrets in seat order. Anyone may send them; the
+/// winner is whatever the deal they produce says.
+pub(crate) fn resolve_poker_market<'info>(
+    ctx: Context<'_, '_, '_, 'info, ResolvePokerMarket<'info>>,
+    secrets: Vec<[u8; 32]>,
+) -> Result<()> {
+    let labels = &ctx.accounts.metadata.outcome_labels;
+    let deal = &mut ctx.accounts.poker_deal;
+    require!(labels.len() == deal.commitments.len(), BettingError::InvalidPokerDeal);
+
+    let deck = shuffle::audit(&deal.commitments, &secrets).map_err(|_| BettingError::InvalidPokerDeal)?;
+    let hands: Vec<String> = deck.chunks(HAND_SIZE).take(labels.len()).map(|hand| hand.join(" ")).collect();
+    let hand_refs: Vec<&str> = hands.iter().map(String::as_str).collect();
+    let winners = poker::winning_hands(&hand_refs);
+    let winning_seats: Vec<&String> = labels
+        .iter()
+        .zip(&hand_refs)
+        .filter(|(_, hand)| winners.contains(*hand))
+        .map(|(label, _)| label)
+        .collect();
+
+    emit!(PokerHandDealt {
+        pool: deal.bet_pool,
+        hands: labels
+            .iter()
+            .zip(&hands)
+            .map(|(seat, cards)| SeatHand { seat: seat.clone(), cards: cards.clone() })
+            .collect(),
+        winners: winning_seats.iter().map(|label| label.to_string()).collect(),
+    });
+
+    // The pool's seat wins outright or on a tie; otherwise every bet loses
+    let backed = &ctx.accounts.settle.bet_pool.outcome;
+    let winning_outcome = match winning_seats.iter().find(|&&label| label == backed) {
+        Some(label) => label.to_string(),
+        None => winning_seats[0].to_string(),
+    };
+    deal.commitments.clear();
+    // Makers settle against the first deal resolved on the pool
+    let bet_pool = &mut ctx.accounts.settle.bet_pool;
+    if !bet_pool.is_resolved() {
+        bet_pool.resolved_outcome = winning_outcome.clone();
+    }
+    settle_pool(&mut ctx.accounts.settle, None, ctx.remaining_accounts, &winning_outcome)
+}
+
+/// Fails while `poker_deal` holds a committed deal, so the pool can't be resolved around its cards.
+pub(crate) fn require_no_deal(poker_deal: &AccountInfo) -> Result<()> {
+    // Never committed: nothing this program owns lives at the address yet
+    if poker_deal.owner != &crate::ID || poker_deal.data_is_empty() {
+        return Ok(());
+    }
+    let deal = PokerDeal::try_deserialize(&mut &poker_deal.data.borrow()[..])?;
+    require!(deal.commitments.is_empty(), BettingError::PokerDealCommitted);
+    Ok(())
+}
+
+#[account]
+pub struct PokerDeal {
+    pub version: u8,
+    pub bet_pool: Pubkey,
+    pub commitments: Vec<[u8; 32]>, // one per seat, empty once the deal is resolved
+    pub bump: u8,
+}
+
+impl PokerDeal {
+    pub const LEN: usize = 1 + 32 + 4 + MAX_OUTCOMES * 32 + 1;
+}
+
+impl Versioned for PokerDeal {
+    fn version(&self) -> u8 {
+        self.version
+    }
+}
+
+#[derive(Accounts)]
+pub struct CommitPokerDeal<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    #[account(has_one = admin @ CommonError::Unauthorized)]
+    pub bet_pool: Account<'info, BetPool>,
+    #[account(seeds = [MARKET_METADATA_SEED, bet_pool.key().as_ref()], bump = metadata.bump)]
+    pub metadata: Account<'info, MarketMetadata>,
+    #[account(
+        init_if_needed,
+        payer = admin,
+        space = 8 + PokerDeal::LEN,
+        seeds = [POKER_DEAL_SEED, bet_pool.key().as_ref()],
+        bump
+    )]
+    pub poker_deal: Account<'info, PokerDeal>,
+    pub system_program: Program<'info, System>,
+}
+
+/// The pool's payout accounts, plus the market's seats and committed deal. Needs no signer.
+#[derive(Accounts)]
+pub struct ResolvePokerMarket<'info> {
+    pub settle: SettlePool<'info>,
+    #[account(seeds = [MARKET_METADATA_SEED, settle.bet_pool.key().as_ref()], bump = metadata.bump)]
+    pub metadata: Account<'info, MarketMetadata>,
+    #[account(
+        mut,
+        seeds = [POKER_DEAL_SEED, settle.bet_pool.key().as_ref()],
+        bump = poker_deal.bump
+    )]
+    pub poker_deal: Account<'info, PokerDeal>,
+}
+
+/// Every seat's hand as dealt, so anyone can check the resolution against the revealed secrets.
+#[event]
+pub struct PokerHandDealt {
+    pub pool: Pubkey,
+    pub hands: Vec<SeatHand>,
+    pub winners: Vec<String>,
+}
+
+#[derive(Clone, AnchorSerialize, AnchorDeserialize)]
+pub struct SeatHand {
+    pub seat: String,
+    pub cards: String, // e.g. "AS KD TH 4C 4S"
+}
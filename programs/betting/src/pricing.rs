@@ -0,0 +1,82 @@
+//! USD bookkeeping for pools in volatile tokens.
+//!
+//! A bankroll-backed pool can name a Pyth price account for its bankroll's token; unbacked pools
+//! have no recorded token to price. Bets on such a pool record the stake's USD
+//! value at placement on the receipt and in `BetPlaced`, and resolutions value payouts at the
+//! price when they settle, so PnL reports don't depend on historical price lookups.
+use anchor_lang::prelude::*;
+use anchor_spl::token::Mint;
+use errors::CommonError;
+use fixed_math::{mul_div, Rounding};
+use pyth_sdk_solana::state::SolanaPriceAccount;
+
+use crate::{Bankroll, BetPool, BettingError};
+
+/// USD figures are fixed-point with this many decimals.
+pub const USD_DECIMALS: i32 = 6;
+/// Oldest price, in seconds, a bet or resolution will use.
+pub const MAX_PRICE_AGE: u64 = 60;
+
+pub(crate) fn set_price_feed(ctx: Context<SetPriceFeed>) -> Result<()> {
+    let bet_pool = &mut ctx.accounts.bet_pool;
+    // Receipts in one pool are all valued the same way
+    require!(bet_pool.bets.is_empty(), BettingError::PoolHasOpenBets);
+
+    match ctx.accounts.price_feed.as_ref() {
+        Some(price_feed) => {
+            let bankroll = ctx.accounts.bankroll.as_ref().ok_or(BettingError::BankrollMismatch)?;
+            require_keys_eq!(ctx.accounts.mint.key(), bankroll.mint, BettingError::BankrollMismatch);
+            // Fails early on anything that isn't a Pyth price account
+            SolanaPriceAccount::account_info_to_feed(&price_feed.to_account_info())
+                .map_err(|_| BettingError::PriceUnavailable)?;
+            bet_pool.price_feed = price_feed.key();
+            bet_pool.token_decimals = ctx.accounts.mint.decimals;
+        }
+        None => {
+            bet_pool.price_feed = Pubkey::default();
+            bet_pool.token_decimals = 0;
+        }
+    }
+
+    msg!("Pool {:?} priced by {:?}", bet_pool.key(), bet_pool.price_feed);
+    Ok(())
+}
+
+/// USD value of `amount` base units of the pool's token, 0 if the pool has no price feed.
+pub fn usd_value(bet_pool: &BetPool, price_feed: Option<&AccountInfo>, amount: u64, now: i64) -> Result<u64> {
+    if !bet_pool.is_priced() {
+        return Ok(0);
+    }
+    let price_feed = price_feed.ok_or(BettingError::PriceFeedMismatch)?;
+    require_keys_eq!(price_feed.key(), bet_pool.price_feed, BettingError::PriceFeedMismatch);
+
+    let price = SolanaPriceAccount::account_info_to_feed(price_feed)
+        .map_err(|_| BettingError::PriceUnavailable)?
+        .get_price_no_older_than(now, MAX_PRICE_AGE)
+        .ok_or(BettingError::PriceUnavailable)?;
+    let unit_price = u64::try_from(price.price).map_err(|_| BettingError::PriceUnavailable)?;
+
+    // amount * price * 10^(expo + USD_DECIMALS - token_decimals)
+    let exponent = price.expo + USD_DECIMALS - bet_pool.token_decimals as i32;
+    let scale = 10u64.checked_pow(exponent.unsigned_abs()).ok_or(CommonError::Overflow)?;
+    let value = if exponent >= 0 {
+        mul_div(amount, unit_price, 1, Rounding::Down).and_then(|v| v.checked_mul(scale))
+    } else {
+        mul_div(amount, unit_price, scale, Rounding::Down)
+    };
+    Ok(value.ok_or(CommonError::Overflow)?)
+}
+
+#[derive(Accounts)]
+pub struct SetPriceFeed<'info> {
+    pub admin: Signer<'info>,
+    #[account(mut, has_one = admin @ CommonError::Unauthorized)]
+    pub bet_pool: Account<'info, BetPool>,
+    /// CHECK: Parsed as a Pyth price account; omit to stop pricing the pool
+    pub price_feed: Option<UncheckedAccount<'info>>,
+    /// Required when setting a feed.
+    #[account(address = bet_pool.bankroll @ BettingError::BankrollMismatch)]
+    pub bankroll: Option<Account<'info, Bankroll>>,
+    /// The bankroll's token, for its decimals.
+    pub mint: Account<'info, Mint>,
+}
@@ -0,0 +1,137 @@
+//! Per-market payout caps and the void path for markets that are never resolved.
+//!
+//! A pool's admin acts as its oracle. If `resolve_bets` hasn't run by the pool's
+//! `resolution_deadline`, the market is expired: it takes no more bets, can no longer be resolved,
+//! and each bettor can void their own bet and, on backed pools, reclaim the stake from the bankroll.
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+use errors::CommonError;
+
+use crate::{Bankroll, BetPool, BetReceipt, BetStatus, BettingError, BANKROLL_SEED};
+
+pub(crate) fn set_market_rules(
+    ctx: Context<SetMarketRules>,
+    max_payout_per_user: u64,
+    resolution_deadline: i64,
+) -> Result<()> {
+    let bet_pool = &mut ctx.accounts.bet_pool;
+    // Bettors see the rules before committing to them
+    require!(bet_pool.bets.is_empty(), BettingError::PoolHasOpenBets);
+    if resolution_deadline != 0 {
+        require!(resolution_deadline > Clock::get()?.unix_timestamp, CommonError::InvalidDuration);
+    }
+
+    bet_pool.max_payout_per_user = max_payout_per_user;
+    bet_pool.resolution_deadline = resolution_deadline;
+
+    msg!(
+        "Pool {:?} rules: max payout {} per user, resolve by {}",
+        bet_pool.key(),
+        max_payout_per_user,
+        resolution_deadline
+    );
+    Ok(())
+}
+
+pub(crate) fn void_bet(ctx: Context<VoidBet>) -> Result<()> {
+    let bet_pool = &mut ctx.accounts.bet_pool;
+    let receipt = &mut ctx.accounts.bet_receipt;
+    require!(
+        bet_pool.is_expired(Clock::get()?.unix_timestamp),
+        BettingError::MarketNotExpired
+    );
+    require!(receipt.status == BetStatus::Open, BettingError::BetNotOpen);
+
+    let index = bet_pool
+        .bets
+        .iter()
+        .position(|bet| {
+            bet.user_id == receipt.owner
+                && bet.amount == receipt.amount
+                && bet.odds_bps == receipt.odds_bps
+        })
+        .ok_or(BettingError::BetNotOpen)?;
+    let bet = bet_pool.bets.remove(index);
+    bet_pool.total_bets = bet_pool.total_bets.saturating_sub(bet.amount);
+    receipt.status = BetStatus::Voided;
+
+    // Only backed pools hold stakes. The bankroll refunds what it received: the affiliate keeps
+    // their cut.
+    if bet_pool.is_backed() {
+        let bankroll = ctx.accounts.bankroll.as_mut().ok_or(BettingError::BankrollMismatch)?;
+        let vault = ctx.accounts.vault.as_ref().ok_or(BettingError::BankrollMismatch)?;
+        let user_token_account = ctx
+            .accounts
+            .user_token_account
+            .as_ref()
+            .ok_or(CommonError::InvalidTokenAccount)?;
+        require_keys_eq!(bankroll.key(), bet_pool.bankroll, BettingError::BankrollMismatch);
+        require_keys_eq!(vault.key(), bankroll.vault, BettingError::BankrollMismatch);
+        require_keys_eq!(user_token_account.owner, receipt.owner, CommonError::InvalidTokenAccount);
+        require_keys_eq!(user_token_account.mint, bankroll.mint, CommonError::InvalidTokenAccount);
+
+        bankroll.release(bet.payout().ok_or(CommonError::Overflow)?);
+
+        let mint = bankroll.mint;
+        let seeds = &[BANKROLL_SEED, mint.as_ref(), &[bankroll.bump]];
+        token::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: vault.to_account_info(),
+                    to: user_token_account.to_account_info(),
+                    authority: bankroll.to_account_info(),
+                },
+                &[&seeds[..]],
+            ),
+            bet.amount.saturating_sub(receipt.commission),
+        )?;
+    }
+
+    emit!(BetVoided {
+        pool: bet_pool.key(),
+        bettor: receipt.owner,
+        amount: bet.amount,
+        usd_value: receipt.usd_value,
+        refunded: bet_pool.is_backed(),
+    });
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SetMarketRules<'info> {
+    pub admin: Signer<'info>,
+    #[account(mut, has_one = admin @ CommonError::Unauthorized)]
+    pub bet_pool: Account<'info, BetPool>,
+}
+
+/// Deliberately not gated by the pause registry: refunds must not depend on any admin.
+#[derive(Accounts)]
+pub struct VoidBet<'info> {
+    pub user: Signer<'info>,
+    #[account(mut)]
+    pub bet_pool: Account<'info, BetPool>,
+    #[account(
+        mut,
+        constraint = bet_receipt.owner == user.key() @ CommonError::Unauthorized,
+        constraint = bet_receipt.pool == bet_pool.key() @ BettingError::ReceiptPoolMismatch
+    )]
+    pub bet_receipt: Account<'info, BetReceipt>,
+    /// The rest are required when the pool is backed by a bankroll.
+    #[account(mut)]
+    pub bankroll: Option<Account<'info, Bankroll>>,
+    #[account(mut)]
+    pub vault: Option<Account<'info, TokenAccount>>,
+    #[account(mut)]
+    pub user_token_account: Option<Account<'info, TokenAccount>>,
+    pub token_program: Program<'info, Token>,
+}
+
+#[event]
+pub struct BetVoided {
+    pub pool: Pubkey,
+    pub bettor: Pubkey,
+    pub amount: u64,
+    pub usd_value: u64, // as recorded at placement
+    pub refunded: bool, // false on unbacked pools, which never held the stake
+}
@@ -0,0 +1,153 @@
+//! Owner-only and admin-only instructions reject a signer other than the one the account records.
+//! Runs the program natively under solana-program-test: `cargo test -p betting`.
+use anchor_lang::solana_program::account_info::AccountInfo;
+use anchor_lang::solana_program::entrypoint::ProgramResult;
+use anchor_lang::{InstructionData, ToAccountMetas};
+use betting::{accounts, instruction, Bet};
+use errors::CommonError;
+use solana_program_test::{processor, BanksClientError, ProgramTest, ProgramTestContext};
+use solana_sdk::instruction::{Instruction, InstructionError};
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::{Keypair, Signer};
+use solana_sdk::system_program;
+use solana_sdk::transaction::{Transaction, TransactionError};
+
+fn process(program_id: &Pubkey, accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    // Anchor's entrypoint wants the slice to live as long as the accounts in it
+    let accounts = Box::leak(Box::new(accounts.to_vec()));
+    betting::entry(program_id, accounts, data)
+}
+
+async fn start() -> ProgramTestContext {
+    ProgramTest::new("betting", betting::ID, processor!(process))
+        .start_with_context()
+        .await
+}
+
+// The context's payer pays the fee and signs alongside `signers`
+async fn send(
+    ctx: &mut ProgramTestContext,
+    ix: Instruction,
+    signers: &[&Keypair],
+) -> Result<(), BanksClientError> {
+    let mut all = vec![&ctx.payer];
+    all.extend_from_slice(signers);
+    let blockhash = ctx.banks_client.get_latest_blockhash().await?;
+    let tx = Transaction::new_signed_with_payer(&[ix], Some(&ctx.payer.pubkey()), &all, blockhash);
+    ctx.banks_client.process_transaction(tx).await
+}
+
+fn assert_unauthorized(result: Result<(), BanksClientError>) {
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(u32::from(CommonError::Unauthorized))
+        )
+    );
+}
+
+// A profile owned by the context's payer
+async fn create_profile(ctx: &mut ProgramTestContext) -> Pubkey {
+    let profile = Keypair::new();
+    let ix = Instruction {
+        program_id: betting::ID,
+        accounts: accounts::CreateUserProfile {
+            user_profile: profile.pubkey(),
+            user: ctx.payer.pubkey(),
+            system_program: system_program::ID,
+        }
+        .to_account_metas(None),
+        data: instruction::CreateUserProfile {}.data(),
+    };
+    send(ctx, ix, &[&profile]).await.unwrap();
+    profile.pubkey()
+}
+
+// A pool administered by the context's payer
+async fn create_pool(ctx: &mut ProgramTestContext) -> Pubkey {
+    let pool = Keypair::new();
+    let ix = Instruction {
+        program_id: betting::ID,
+        accounts: accounts::CreateBettingPool {
+            bet_pool: pool.pubkey(),
+            admin: ctx.payer.pubkey(),
+            system_program: system_program::ID,
+        }
+        .to_account_metas(None),
+        data: instruction::CreateBettingPool { outcome: "home".to_string() }.data(),
+    };
+    send(ctx, ix, &[&pool]).await.unwrap();
+    pool.pubkey()
+}
+
+fn set_limits(user: Pubkey, user_profile: Pubkey) -> Instruction {
+    Instruction {
+        program_id: betting::ID,
+        accounts: accounts::SetLimits { user, user_profile }.to_account_metas(None),
+        data: instruction::SetLimits { max_daily_wager: 1_000, self_excluded_until: 0 }.data(),
+    }
+}
+
+fn update_betting_history(user: Pubkey, user_profile: Pubkey, bettor: Pubkey) -> Instruction {
+    Instruction {
+        program_id: betting::ID,
+        accounts: accounts::UpdateBettingHistory { user, user_profile }.to_account_metas(None),
+        data: instruction::UpdateBettingHistory {
+            bet: Bet {
+                user_id: bettor,
+                amount: 10,
+                outcome: "home".to_string(),
+                odds_bps: 10_000,
+            },
+        }
+        .data(),
+    }
+}
+
+fn update_odds(admin: Pubkey, bet_pool: Pubkey, odds_bps: u64) -> Instruction {
+    Instruction {
+        program_id: betting::ID,
+        accounts: accounts::UpdateOdds { admin, bet_pool }.to_account_metas(None),
+        data: instruction::UpdateOdds { odds_bps }.data(),
+    }
+}
+
+#[tokio::test]
+async fn set_limits_rejects_someone_elses_profile() {
+    let mut ctx = start().await;
+    let profile = create_profile(&mut ctx).await;
+    let stranger = Keypair::new();
+
+    let result = send(&mut ctx, set_limits(stranger.pubkey(), profile), &[&stranger]).await;
+    assert_unauthorized(result);
+
+    let owner = ctx.payer.pubkey();
+    send(&mut ctx, set_limits(owner, profile), &[]).await.unwrap();
+}
+
+#[tokio::test]
+async fn update_betting_history_rejects_someone_elses_profile() {
+    let mut ctx = start().await;
+    let profile = create_profile(&mut ctx).await;
+    let stranger = Keypair::new();
+
+    let owner = ctx.payer.pubkey();
+    let ix = update_betting_history(stranger.pubkey(), profile, owner);
+    assert_unauthorized(send(&mut ctx, ix, &[&stranger]).await);
+
+    send(&mut ctx, update_betting_history(owner, profile, owner), &[]).await.unwrap();
+}
+
+#[tokio::test]
+async fn update_odds_rejects_a_non_admin() {
+    let mut ctx = start().await;
+    let pool = create_pool(&mut ctx).await;
+    let stranger = Keypair::new();
+
+    let result = send(&mut ctx, update_odds(stranger.pubkey(), pool, 1), &[&stranger]).await;
+    assert_unauthorized(result);
+
+    let admin = ctx.payer.pubkey();
+    send(&mut ctx, update_odds(admin, pool, 20_000), &[]).await.unwrap();
+}
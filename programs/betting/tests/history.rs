@@ -0,0 +1,72 @@
+//! A user profile keeps its most recent bets, dropping the oldest once the history is full.
+//! Runs the program natively under solana-program-test: `cargo test -p betting`.
+use anchor_lang::solana_program::account_info::AccountInfo;
+use anchor_lang::solana_program::entrypoint::ProgramResult;
+use anchor_lang::{AccountDeserialize, InstructionData, ToAccountMetas};
+use betting::{accounts, instruction, Bet, UserProfile, MAX_HISTORY};
+use solana_program_test::{processor, BanksClientError, ProgramTest, ProgramTestContext};
+use solana_sdk::instruction::Instruction;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::{Keypair, Signer};
+use solana_sdk::system_program;
+use solana_sdk::transaction::Transaction;
+
+fn process(program_id: &Pubkey, accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    // Anchor's entrypoint wants the slice to live as long as the accounts in it
+    let accounts = Box::leak(Box::new(accounts.to_vec()));
+    betting::entry(program_id, accounts, data)
+}
+
+// The context's payer pays the fee and signs alongside `signers`
+async fn send(
+    ctx: &mut ProgramTestContext,
+    ix: Instruction,
+    signers: &[&Keypair],
+) -> Result<(), BanksClientError> {
+    let mut all = vec![&ctx.payer];
+    all.extend_from_slice(signers);
+    let blockhash = ctx.banks_client.get_latest_blockhash().await?;
+    let tx = Transaction::new_signed_with_payer(&[ix], Some(&ctx.payer.pubkey()), &all, blockhash);
+    ctx.banks_client.process_transaction(tx).await
+}
+
+#[tokio::test]
+async fn full_history_drops_the_oldest_bet() {
+    let mut ctx = ProgramTest::new("betting", betting::ID, processor!(process))
+        .start_with_context()
+        .await;
+    let user = ctx.payer.pubkey();
+
+    let profile = Keypair::new();
+    let ix = Instruction {
+        program_id: betting::ID,
+        accounts: accounts::CreateUserProfile {
+            user_profile: profile.pubkey(),
+            user,
+            system_program: system_program::ID,
+        }
+        .to_account_metas(None),
+        data: instruction::CreateUserProfile {}.data(),
+    };
+    send(&mut ctx, ix, &[&profile]).await.unwrap();
+
+    for amount in 1..=MAX_HISTORY as u64 + 1 {
+        let ix = Instruction {
+            program_id: betting::ID,
+            accounts: accounts::UpdateBettingHistory { user, user_profile: profile.pubkey() }
+                .to_account_metas(None),
+            data: instruction::UpdateBettingHistory {
+                bet: Bet { user_id: user, amount, outcome: "home".to_string(), odds_bps: 10_000 },
+            }
+            .data(),
+        };
+        send(&mut ctx, ix, &[]).await.unwrap();
+    }
+
+    let account = ctx.banks_client.get_account(profile.pubkey()).await.unwrap().unwrap();
+    let profile = UserProfile::try_deserialize(&mut &account.data[..]).unwrap();
+    let amounts: Vec<u64> = profile.betting_history.iter().map(|bet| bet.amount).collect();
+    assert_eq!(amounts, (2..=MAX_HISTORY as u64 + 1).collect::<Vec<_>>());
+    // Totals still count every bet
+    assert_eq!(profile.total_bets, (1..=MAX_HISTORY as u64 + 1).sum::<u64>());
+}
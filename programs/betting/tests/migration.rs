@@ -0,0 +1,101 @@
+//! Accounts migrated by `migrate_account` have room for the current layout's vectors.
+//! Runs the program natively under solana-program-test: `cargo test -p betting`.
+use anchor_lang::solana_program::account_info::AccountInfo;
+use anchor_lang::solana_program::entrypoint::ProgramResult;
+use anchor_lang::{AccountDeserialize, Discriminator, InstructionData, ToAccountMetas};
+use betting::{accounts, instruction, Bet, UserProfile};
+use solana_program_test::{processor, BanksClientError, ProgramTest, ProgramTestContext};
+use solana_sdk::account::Account;
+use solana_sdk::instruction::Instruction;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::rent::Rent;
+use solana_sdk::signature::{Keypair, Signer};
+use solana_sdk::system_program;
+use solana_sdk::transaction::Transaction;
+
+fn process(program_id: &Pubkey, accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    // Anchor's entrypoint wants the slice to live as long as the accounts in it
+    let accounts = Box::leak(Box::new(accounts.to_vec()));
+    betting::entry(program_id, accounts, data)
+}
+
+// The context's payer pays the fee and signs alongside `signers`
+async fn send(
+    ctx: &mut ProgramTestContext,
+    ix: Instruction,
+    signers: &[&Keypair],
+) -> Result<(), BanksClientError> {
+    let mut all = vec![&ctx.payer];
+    all.extend_from_slice(signers);
+    let blockhash = ctx.banks_client.get_latest_blockhash().await?;
+    let tx = Transaction::new_signed_with_payer(&[ix], Some(&ctx.payer.pubkey()), &all, blockhash);
+    ctx.banks_client.process_transaction(tx).await
+}
+
+// A profile as written before versioning: 8 + size_of, with an empty history and zero padding
+fn unversioned_profile(user: &Pubkey) -> Account {
+    let mut data = UserProfile::DISCRIMINATOR.to_vec();
+    data.extend_from_slice(user.as_ref());
+    data.extend_from_slice(&5u64.to_le_bytes()); // total_bets
+    data.extend_from_slice(&0u64.to_le_bytes()); // total_wins
+    data.extend_from_slice(&0u32.to_le_bytes()); // betting_history
+    data.resize(8 + 32 + 8 + 8 + 24, 0);
+
+    Account {
+        lamports: Rent::default().minimum_balance(data.len()),
+        data,
+        owner: betting::ID,
+        executable: false,
+        rent_epoch: 0,
+    }
+}
+
+#[tokio::test]
+async fn migrated_profile_has_room_for_its_history() {
+    let user = Keypair::new();
+    let profile = Pubkey::new_unique();
+    let mut test = ProgramTest::new("betting", betting::ID, processor!(process));
+    test.add_account(profile, unversioned_profile(&user.pubkey()));
+    let mut ctx = test.start_with_context().await;
+
+    let ix = Instruction {
+        program_id: betting::ID,
+        accounts: accounts::MigrateAccount {
+            target: profile,
+            payer: ctx.payer.pubkey(),
+            system_program: system_program::ID,
+        }
+        .to_account_metas(None),
+        data: instruction::MigrateAccount {}.data(),
+    };
+    send(&mut ctx, ix, &[]).await.unwrap();
+
+    let account = ctx.banks_client.get_account(profile).await.unwrap().unwrap();
+    assert_eq!(account.data.len(), 8 + UserProfile::LEN);
+
+    // Pushing into the migrated history needs the space the account was grown to
+    for amount in [10, 20, 30] {
+        let ix = Instruction {
+            program_id: betting::ID,
+            accounts: accounts::UpdateBettingHistory { user: user.pubkey(), user_profile: profile }
+                .to_account_metas(None),
+            data: instruction::UpdateBettingHistory {
+                bet: Bet {
+                    user_id: user.pubkey(),
+                    amount,
+                    outcome: "home".to_string(),
+                    odds_bps: 20_000,
+                },
+            }
+            .data(),
+        };
+        send(&mut ctx, ix, &[&user]).await.unwrap();
+    }
+
+    let account = ctx.banks_client.get_account(profile).await.unwrap().unwrap();
+    let migrated = UserProfile::try_deserialize(&mut &account.data[..]).unwrap();
+    assert_eq!(migrated.user_id, user.pubkey());
+    assert_eq!(migrated.total_bets, 65);
+    assert_eq!(migrated.betting_history.len(), 3);
+    assert_eq!(migrated.bet_count, 0); // its history was empty, so no receipt nonce was used
+}
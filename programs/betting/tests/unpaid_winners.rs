@@ -0,0 +1,186 @@
+//! A winner whose token account can't be paid doesn't block the others; their share waits for
+//! `claim_winnings`. Runs the program natively under solana-program-test: `cargo test -p betting`.
+use anchor_lang::solana_program::account_info::AccountInfo;
+use anchor_lang::solana_program::entrypoint::ProgramResult;
+use anchor_lang::solana_program::program_option::COption;
+use anchor_lang::solana_program::program_pack::Pack;
+use anchor_lang::{AccountDeserialize, AccountSerialize, InstructionData, ToAccountMetas};
+use anchor_spl::token::spl_token;
+use betting::{accounts, instruction, Bet, BetPool, POKER_DEAL_SEED};
+use pause_registry::{PauseRegistry, REGISTRY_SEED};
+use solana_program_test::{processor, BanksClientError, ProgramTest, ProgramTestContext};
+use solana_sdk::account::Account;
+use solana_sdk::instruction::{AccountMeta, Instruction};
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::rent::Rent;
+use solana_sdk::signature::{Keypair, Signer};
+use solana_sdk::transaction::Transaction;
+use versioning::Versioned;
+
+fn process(program_id: &Pubkey, accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    // Anchor's entrypoint wants the slice to live as long as the accounts in it
+    let accounts = Box::leak(Box::new(accounts.to_vec()));
+    betting::entry(program_id, accounts, data)
+}
+
+// The context's payer pays the fee and signs alongside `signers`
+async fn send(
+    ctx: &mut ProgramTestContext,
+    ix: Instruction,
+    signers: &[&Keypair],
+) -> Result<(), BanksClientError> {
+    let mut all = vec![&ctx.payer];
+    all.extend_from_slice(signers);
+    let blockhash = ctx.banks_client.get_latest_blockhash().await?;
+    let tx = Transaction::new_signed_with_payer(&[ix], Some(&ctx.payer.pubkey()), &all, blockhash);
+    ctx.banks_client.process_transaction(tx).await
+}
+
+fn account(owner: Pubkey, data: Vec<u8>) -> Account {
+    Account {
+        lamports: Rent::default().minimum_balance(data.len()),
+        data,
+        owner,
+        executable: false,
+        rent_epoch: 0,
+    }
+}
+
+fn token_account(mint: Pubkey, owner: Pubkey, amount: u64) -> Account {
+    let mut data = vec![0; spl_token::state::Account::LEN];
+    spl_token::state::Account {
+        mint,
+        owner,
+        amount,
+        delegate: COption::None,
+        state: spl_token::state::AccountState::Initialized,
+        is_native: COption::None,
+        delegated_amount: 0,
+        close_authority: COption::None,
+    }
+    .pack_into_slice(&mut data);
+    account(spl_token::ID, data)
+}
+
+fn pause_registry() -> (Pubkey, Account) {
+    let (address, bump) = Pubkey::find_program_address(&[REGISTRY_SEED], &pause_registry::ID);
+    let registry = PauseRegistry {
+        version: PauseRegistry::CURRENT_VERSION,
+        guardian: Pubkey::new_unique(),
+        global_paused: false,
+        paused_programs: 0,
+        exempt_programs: 0,
+        bump,
+    };
+    let mut data = Vec::new();
+    registry.try_serialize(&mut data).unwrap();
+    (address, account(pause_registry::ID, data))
+}
+
+// An unbacked pool with one 2x bet on "home" per bettor
+fn pool(admin: Pubkey, bettors: &[Pubkey]) -> Account {
+    let pool = BetPool {
+        version: BetPool::CURRENT_VERSION,
+        total_bets: 100 * bettors.len() as u64,
+        bets: bettors
+            .iter()
+            .map(|&user_id| Bet { user_id, amount: 100, outcome: "home".to_string(), odds_bps: 20_000 })
+            .collect(),
+        odds_bps: 20_000,
+        outcome: "home".to_string(),
+        admin,
+        bankroll: Pubkey::default(),
+        max_payout_per_user: 0,
+        resolution_deadline: 0,
+        price_feed: Pubkey::default(),
+        token_decimals: 0,
+        resolved_outcome: String::new(),
+        unclaimed: Vec::new(),
+    };
+    let mut data = Vec::new();
+    pool.try_serialize(&mut data).unwrap();
+    data.resize(8 + BetPool::LEN, 0);
+    account(betting::ID, data)
+}
+
+async fn token_balance(ctx: &mut ProgramTestContext, address: Pubkey) -> u64 {
+    let account = ctx.banks_client.get_account(address).await.unwrap().unwrap();
+    spl_token::state::Account::unpack(&account.data).unwrap().amount
+}
+
+async fn fetch_pool(ctx: &mut ProgramTestContext, address: Pubkey) -> BetPool {
+    let account = ctx.banks_client.get_account(address).await.unwrap().unwrap();
+    BetPool::try_deserialize(&mut &account.data[..]).unwrap()
+}
+
+#[tokio::test]
+async fn closed_winner_account_is_held_for_claim() {
+    let admin = Keypair::new();
+    let (alice, bob) = (Keypair::new(), Keypair::new());
+    let mint = Pubkey::new_unique();
+    let (bet_pool, admin_tokens, alice_tokens, bob_tokens) =
+        (Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique());
+    // Bob's account at resolution was closed; he claims to a new one
+    let bob_closed = Pubkey::new_unique();
+    let (registry, registry_account) = pause_registry();
+
+    let mut test = ProgramTest::new("betting", betting::ID, processor!(process));
+    test.add_account(bet_pool, pool(admin.pubkey(), &[alice.pubkey(), bob.pubkey()]));
+    test.add_account(admin_tokens, token_account(mint, admin.pubkey(), 1_000));
+    test.add_account(alice_tokens, token_account(mint, alice.pubkey(), 0));
+    test.add_account(bob_tokens, token_account(mint, bob.pubkey(), 0));
+    test.add_account(registry, registry_account);
+    let mut ctx = test.start_with_context().await;
+
+    let mut metas = accounts::ResolveBets {
+        admin: admin.pubkey(),
+        settle: accounts::SettlePool {
+            bet_pool,
+            bet_pool_token_account: admin_tokens,
+            bankroll: None,
+            price_feed: None,
+            pause_registry: registry,
+            token_program: spl_token::ID,
+        },
+        poker_deal: Pubkey::find_program_address(&[POKER_DEAL_SEED, bet_pool.as_ref()], &betting::ID).0,
+    }
+    .to_account_metas(None);
+    metas.push(AccountMeta::new(alice_tokens, false));
+    metas.push(AccountMeta::new(bob_closed, false));
+    let resolve = Instruction {
+        program_id: betting::ID,
+        accounts: metas,
+        data: instruction::ResolveBets { winning_outcome: "home".to_string() }.data(),
+    };
+    send(&mut ctx, resolve, &[&admin]).await.unwrap();
+
+    assert_eq!(token_balance(&mut ctx, alice_tokens).await, 200);
+    assert_eq!(token_balance(&mut ctx, admin_tokens).await, 800);
+    let resolved = fetch_pool(&mut ctx, bet_pool).await;
+    assert!(resolved.bets.is_empty());
+    assert_eq!(resolved.unclaimed.len(), 1);
+    assert_eq!(resolved.unclaimed[0].bettor, bob.pubkey());
+    assert_eq!(resolved.unclaimed[0].amount, 200);
+
+    let claim = |admin: Option<Pubkey>| Instruction {
+        program_id: betting::ID,
+        accounts: accounts::ClaimWinnings {
+            user: bob.pubkey(),
+            bet_pool,
+            bet_pool_token_account: admin_tokens,
+            bankroll: None,
+            admin,
+            user_token_account: bob_tokens,
+            token_program: spl_token::ID,
+        }
+        .to_account_metas(None),
+        data: instruction::ClaimWinnings {}.data(),
+    };
+    // Unbacked pools pay from the admin's account, so the admin has to co-sign
+    assert!(send(&mut ctx, claim(None), &[&bob]).await.is_err());
+    send(&mut ctx, claim(Some(admin.pubkey())), &[&bob, &admin]).await.unwrap();
+
+    assert_eq!(token_balance(&mut ctx, bob_tokens).await, 200);
+    assert_eq!(token_balance(&mut ctx, admin_tokens).await, 600);
+    assert!(fetch_pool(&mut ctx, bet_pool).await.unclaimed.is_empty());
+}
@@ -0,0 +1,154 @@
+use anchor_lang::prelude::*;
+use errors::CommonError;
+use versioning::Versioned;
+
+declare_id!("8dJ98cDpEhP31g327Sh8uXvvNEsz95YNvkTmKokEw2LX");
+
+/// Bit for each program that consults the registry.
+pub const STAKING: u32 = 1 << 0;
+pub const VOTING: u32 = 1 << 1;
+pub const VESTING: u32 = 1 << 2;
+pub const BETTING: u32 = 1 << 3;
+
+pub const REGISTRY_SEED: &[u8] = b"pause_registry";
+
+#[program]
+pub mod pause_registry {
+    use super::*;
+
+    /// Create the registry. Only the program's upgrade authority can, and it becomes the guardian;
+    /// otherwise anyone could front-run the deployment and take the kill switch.
+    pub fn initialize(ctx: Context<Initialize>) -> Result<()> {
+        let registry = &mut ctx.accounts.registry;
+        registry.version = PauseRegistry::CURRENT_VERSION;
+        registry.guardian = ctx.accounts.guardian.key();
+        registry.global_paused = false;
+        registry.paused_programs = 0;
+        registry.exempt_programs = 0;
+        registry.bump = *ctx.bumps.get("registry").unwrap();
+
+        msg!("Pause registry initialized with guardian {:?}", registry.guardian);
+        Ok(())
+    }
+
+    /// Pause or resume every program that isn't exempt.
+    pub fn set_global_pause(ctx: Context<GuardianAction>, paused: bool) -> Result<()> {
+        let registry = &mut ctx.accounts.registry;
+        registry.global_paused = paused;
+
+        emit!(PauseChanged {
+            global_paused: registry.global_paused,
+            paused_programs: registry.paused_programs,
+            exempt_programs: registry.exempt_programs,
+        });
+        Ok(())
+    }
+
+    /// Pause or resume specific programs regardless of the global flag.
+    pub fn set_programs_paused(ctx: Context<GuardianAction>, programs: u32, paused: bool) -> Result<()> {
+        let registry = &mut ctx.accounts.registry;
+        if paused {
+            registry.paused_programs |= programs;
+        } else {
+            registry.paused_programs &= !programs;
+        }
+
+        emit!(PauseChanged {
+            global_paused: registry.global_paused,
+            paused_programs: registry.paused_programs,
+            exempt_programs: registry.exempt_programs,
+        });
+        Ok(())
+    }
+
+    /// Keep specific programs running through a global pause.
+    pub fn set_programs_exempt(ctx: Context<GuardianAction>, programs: u32, exempt: bool) -> Result<()> {
+        let registry = &mut ctx.accounts.registry;
+        if exempt {
+            registry.exempt_programs |= programs;
+        } else {
+            registry.exempt_programs &= !programs;
+        }
+
+        emit!(PauseChanged {
+            global_paused: registry.global_paused,
+            paused_programs: registry.paused_programs,
+            exempt_programs: registry.exempt_programs,
+        });
+        Ok(())
+    }
+
+    /// Hand the guardian role to another wallet.
+    pub fn set_guardian(ctx: Context<GuardianAction>, new_guardian: Pubkey) -> Result<()> {
+        ctx.accounts.registry.guardian = new_guardian;
+
+        msg!("Guardian changed to {:?}", new_guardian);
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct Initialize<'info> {
+    #[account(
+        init,
+        payer = guardian,
+        space = 8 + PauseRegistry::LEN,
+        seeds = [REGISTRY_SEED],
+        bump
+    )]
+    pub registry: Account<'info, PauseRegistry>,
+    #[account(mut)]
+    pub guardian: Signer<'info>,
+    #[account(constraint = program.programdata_address()? == Some(program_data.key()))]
+    pub program: Program<'info, crate::program::PauseRegistry>,
+    #[account(
+        constraint = program_data.upgrade_authority_address == Some(guardian.key()) @ CommonError::Unauthorized
+    )]
+    pub program_data: Account<'info, ProgramData>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct GuardianAction<'info> {
+    #[account(
+        mut,
+        seeds = [REGISTRY_SEED],
+        bump = registry.bump,
+        has_one = guardian @ CommonError::Unauthorized
+    )]
+    pub registry: Account<'info, PauseRegistry>,
+    pub guardian: Signer<'info>,
+}
+
+/// Consumers take this account with `seeds::program = pause_registry::ID` and a
+/// `constraint = !registry.is_paused(<their bit>)`.
+#[account]
+pub struct PauseRegistry {
+    pub version: u8,
+    pub guardian: Pubkey,
+    pub global_paused: bool,
+    pub paused_programs: u32,
+    pub exempt_programs: u32,
+    pub bump: u8,
+}
+
+impl PauseRegistry {
+    pub const LEN: usize = 1 + 32 + 1 + 4 + 4 + 1;
+
+    pub fn is_paused(&self, program: u32) -> bool {
+        (self.global_paused && self.exempt_programs & program == 0) || self.paused_programs & program != 0
+    }
+}
+
+impl Versioned for PauseRegistry {
+    fn version(&self) -> u8 {
+        self.version
+    }
+}
+
+#[event]
+pub struct PauseChanged {
+    pub global_paused: bool,
+    pub paused_programs: u32,
+    pub exempt_programs: u32,
+}
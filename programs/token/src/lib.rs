@@ -2,7 +2,9 @@ use solana_program::{
     account_info::{next_account_info, AccountInfo},
     entrypoint,
     entrypoint::ProgramResult,
+    program::invoke,
     program_error::ProgramError,
+    program_option::COption,
     pubkey::Pubkey,
     msg,
     sysvar::{rent::Rent, Sysvar},
@@ -10,7 +12,7 @@ use solana_program::{
 };
 use spl_token::{
     instruction::{initialize_mint, mint_to, burn, transfer},
-    state::{Account, Mint},
+    state::Mint,
 };
 use spl_token::error::TokenError;
 use borsh::{BorshSerialize, BorshDeserialize};
@@ -45,7 +47,7 @@ pub fn process_instruction(
 
 // Initialize token mint with metadata
 fn initialize_token(
-    program_id: &Pubkey,
+    _program_id: &Pubkey,
     accounts: &[AccountInfo],
     data: &[u8],
 ) -> ProgramResult {
@@ -67,16 +69,19 @@ fn initialize_token(
     
     // Validate tax rate
     if metadata.tax_rate > 20 {
-        return Err(TokenError::InvalidMintAuthority.into());
+        return Err(ProgramError::InvalidArgument);
     }
     
     // Initialize mint
-    initialize_mint(
-        token_program.key,
-        mint_account,
-        payer.key,      // Mint authority
-        Some(payer.key),// Freeze authority
-        9,              // Decimals
+    invoke(
+        &initialize_mint(
+            token_program.key,
+            mint_account.key,
+            payer.key,      // Mint authority
+            Some(payer.key),// Freeze authority
+            9,              // Decimals
+        )?,
+        &[mint_account.clone(), rent.clone()],
     )?;
     
     // Create and save metadata
@@ -101,7 +106,7 @@ fn initialize_token(
 
 // Mint new tokens with authority check
 fn mint_tokens(
-    program_id: &Pubkey,
+    _program_id: &Pubkey,
     accounts: &[AccountInfo],
     data: &[u8],
 ) -> ProgramResult {
@@ -121,7 +126,7 @@ fn mint_tokens(
     let amount = u64::from_le_bytes(data[..8].try_into().unwrap());
     
     // Load metadata to verify authority
-    let metadata = TokenMetadata::try_from_slice(&metadata_account.try_borrow_data()?)?;
+    let _metadata = TokenMetadata::try_from_slice(&metadata_account.try_borrow_data()?)?;
     
     // Check if authority is valid
     let mint_data = Mint::unpack(&mint_account.try_borrow_data()?)?;
@@ -129,13 +134,16 @@ fn mint_tokens(
         return Err(TokenError::OwnerMismatch.into());
     }
     
-    mint_to(
-        token_program.key,
-        mint_account,
-        token_account,
-        authority,
-        &[],
-        amount,
+    invoke(
+        &mint_to(
+            token_program.key,
+            mint_account.key,
+            token_account.key,
+            authority.key,
+            &[],
+            amount,
+        )?,
+        &[mint_account.clone(), token_account.clone(), authority.clone()],
     )?;
     
     msg!("Minted {} tokens", amount);
@@ -144,7 +152,7 @@ fn mint_tokens(
 
 // Transfer tokens with tax mechanism
 fn transfer_tokens(
-    program_id: &Pubkey,
+    _program_id: &Pubkey,
     accounts: &[AccountInfo],
     data: &[u8],
 ) -> ProgramResult {
@@ -152,7 +160,7 @@ fn transfer_tokens(
     let source = next_account_info(accounts_iter)?;
     let destination = next_account_info(accounts_iter)?;
     let owner = next_account_info(accounts_iter)?;
-    let mint = next_account_info(accounts_iter)?;
+    let _mint = next_account_info(accounts_iter)?;
     let treasury = next_account_info(accounts_iter)?;
     let metadata_account = next_account_info(accounts_iter)?;
     let token_program = next_account_info(accounts_iter)?;
@@ -173,24 +181,30 @@ fn transfer_tokens(
     let net_amount = amount - tax_amount;
     
     // Transfer net amount to recipient
-    transfer(
-        token_program.key,
-        source,
-        destination,
-        owner,
-        &[],
-        net_amount,
+    invoke(
+        &transfer(
+            token_program.key,
+            source.key,
+            destination.key,
+            owner.key,
+            &[],
+            net_amount,
+        )?,
+        &[source.clone(), destination.clone(), owner.clone()],
     )?;
     
     // Transfer tax to treasury
     if tax_amount > 0 {
-        transfer(
-            token_program.key,
-            source,
-            treasury,
-            owner,
-            &[],
-            tax_amount,
+        invoke(
+            &transfer(
+                token_program.key,
+                source.key,
+                treasury.key,
+                owner.key,
+                &[],
+                tax_amount,
+            )?,
+            &[source.clone(), treasury.clone(), owner.clone()],
         )?;
     }
     
@@ -204,7 +218,7 @@ fn transfer_tokens(
 
 // Burn tokens
 fn burn_tokens(
-    program_id: &Pubkey,
+    _program_id: &Pubkey,
     accounts: &[AccountInfo],
     data: &[u8],
 ) -> ProgramResult {
@@ -222,13 +236,16 @@ fn burn_tokens(
     // Get amount from instruction data
     let amount = u64::from_le_bytes(data[..8].try_into().unwrap());
     
-    burn(
-        token_program.key,
-        account,
-        mint,
-        authority,
-        &[],
-        amount,
+    invoke(
+        &burn(
+            token_program.key,
+            account.key,
+            mint.key,
+            authority.key,
+            &[],
+            amount,
+        )?,
+        &[account.clone(), mint.clone(), authority.clone()],
     )?;
     
     msg!("Burned {} tokens", amount);
@@ -237,7 +254,7 @@ fn burn_tokens(
 
 // Update token metadata
 fn update_metadata(
-    program_id: &Pubkey,
+    _program_id: &Pubkey,
     accounts: &[AccountInfo],
     data: &[u8],
 ) -> ProgramResult {
@@ -0,0 +1,210 @@
+// Schedule amendments that need both sides to agree.
+//
+// The admin proposes new terms for one grant; they only take effect once the grant's beneficiary
+// accepts them. Either side can withdraw a pending proposal. The accepted change is emitted with
+// the old and new terms so it can be audited from chain data alone.
+use anchor_lang::prelude::*;
+use anchor_spl::token::TokenAccount;
+use errors::CommonError;
+use versioning::Versioned;
+
+use crate::{Beneficiary, ErrorCode, VestingState};
+
+pub(crate) fn propose_amendment(
+    ctx: Context<ProposeAmendment>,
+    allocation: u64,
+    cliff_duration: i64,
+    vesting_duration: i64,
+) -> Result<()> {
+    let beneficiary = &ctx.accounts.beneficiary;
+    require!(allocation > 0 && allocation >= beneficiary.released, ErrorCode::InvalidAllocation);
+    require!(cliff_duration >= 0, ErrorCode::InvalidCliff);
+    require!(vesting_duration > 0, CommonError::InvalidDuration);
+    require!(cliff_duration < vesting_duration, ErrorCode::InvalidCliffDuration);
+
+    let amendment = &mut ctx.accounts.amendment;
+    amendment.version = Amendment::CURRENT_VERSION;
+    amendment.beneficiary = beneficiary.key();
+    amendment.allocation = allocation;
+    amendment.cliff_duration = cliff_duration;
+    amendment.vesting_duration = vesting_duration;
+    amendment.proposed_at = Clock::get()?.unix_timestamp;
+    amendment.bump = *ctx.bumps.get("amendment").unwrap();
+
+    Ok(())
+}
+
+pub(crate) fn accept_amendment(ctx: Context<AcceptAmendment>) -> Result<()> {
+    let state = &mut ctx.accounts.state;
+    let beneficiary = &mut ctx.accounts.beneficiary;
+    let amendment = &ctx.accounts.amendment;
+    // Releases since the proposal may have overtaken a reduced allocation
+    require!(amendment.allocation >= beneficiary.released, ErrorCode::InvalidAllocation);
+
+    let before = Terms::of(beneficiary);
+    let mint = beneficiary.grant_mint(state);
+    if amendment.allocation > beneficiary.allocation {
+        state.commit(&mint, amendment.allocation - beneficiary.allocation)?;
+        // The treasury must still cover every unreleased grant in the mint
+        require!(
+            state.committed_for(&mint) <= ctx.accounts.treasury.amount,
+            ErrorCode::InsufficientSupply
+        );
+    } else {
+        state.uncommit(&mint, beneficiary.allocation - amendment.allocation);
+    }
+
+    beneficiary.allocation = amendment.allocation;
+    beneficiary.cliff_duration = amendment.cliff_duration;
+    beneficiary.vesting_duration = amendment.vesting_duration;
+
+    emit!(AmendmentAccepted {
+        beneficiary: beneficiary.user,
+        grant: beneficiary.key(),
+        before,
+        after: Terms::of(beneficiary),
+        proposed_at: amendment.proposed_at,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+// Either the admin or the beneficiary; rent returns to the admin who paid it
+pub(crate) fn cancel_amendment(ctx: Context<CancelAmendment>) -> Result<()> {
+    let signer = ctx.accounts.signer.key();
+    require!(
+        signer == ctx.accounts.state.admin || signer == ctx.accounts.beneficiary.user,
+        CommonError::Unauthorized
+    );
+    Ok(())
+}
+
+// Accounts
+#[account]
+pub struct Amendment {
+    pub version: u8,
+    pub beneficiary: Pubkey, // grant account being amended
+    pub allocation: u64,
+    pub cliff_duration: i64,
+    pub vesting_duration: i64,
+    pub proposed_at: i64,
+    pub bump: u8,
+}
+
+impl Amendment {
+    pub const LEN: usize = 1 + 32 + 8 + 8 + 8 + 8 + 1;
+}
+
+impl Versioned for Amendment {
+    fn version(&self) -> u8 {
+        self.version
+    }
+}
+
+// Schedule terms as recorded in `AmendmentAccepted`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy)]
+pub struct Terms {
+    pub allocation: u64,
+    pub cliff_duration: i64,
+    pub vesting_duration: i64,
+}
+
+impl Terms {
+    fn of(beneficiary: &Beneficiary) -> Self {
+        Terms {
+            allocation: beneficiary.allocation,
+            cliff_duration: beneficiary.cliff_duration,
+            vesting_duration: beneficiary.vesting_duration,
+        }
+    }
+}
+
+// Contexts
+#[derive(Accounts)]
+pub struct ProposeAmendment<'info> {
+    #[account(
+        has_one = admin @ CommonError::Unauthorized,
+        seeds = [b"state"],
+        bump
+    )]
+    pub state: Account<'info, VestingState>,
+
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    pub beneficiary: Account<'info, Beneficiary>,
+
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + Amendment::LEN,
+        seeds = [b"amendment", beneficiary.key().as_ref()],
+        bump
+    )]
+    pub amendment: Account<'info, Amendment>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct AcceptAmendment<'info> {
+    #[account(mut, seeds = [b"state"], bump)]
+    pub state: Account<'info, VestingState>,
+
+    /// CHECK: Receives the amendment's rent; must be the state's admin
+    #[account(mut, address = state.admin @ CommonError::Unauthorized)]
+    pub admin: AccountInfo<'info>,
+
+    pub user: Signer<'info>,
+
+    #[account(mut, constraint = beneficiary.user == user.key() @ CommonError::Unauthorized)]
+    pub beneficiary: Account<'info, Beneficiary>,
+
+    #[account(
+        mut,
+        close = admin,
+        seeds = [b"amendment", beneficiary.key().as_ref()],
+        bump = amendment.bump
+    )]
+    pub amendment: Account<'info, Amendment>,
+
+    #[account(
+        constraint = state.treasury_for(&beneficiary.grant_mint(&state)) == Some(treasury.key())
+            @ ErrorCode::UnknownVault
+    )]
+    pub treasury: Account<'info, TokenAccount>,
+}
+
+#[derive(Accounts)]
+pub struct CancelAmendment<'info> {
+    #[account(seeds = [b"state"], bump)]
+    pub state: Account<'info, VestingState>,
+
+    /// CHECK: Receives the amendment's rent; must be the state's admin
+    #[account(mut, address = state.admin @ CommonError::Unauthorized)]
+    pub admin: AccountInfo<'info>,
+
+    pub signer: Signer<'info>,
+
+    pub beneficiary: Account<'info, Beneficiary>,
+
+    #[account(
+        mut,
+        close = admin,
+        seeds = [b"amendment", beneficiary.key().as_ref()],
+        bump = amendment.bump
+    )]
+    pub amendment: Account<'info, Amendment>,
+}
+
+// Events
+#[event]
+pub struct AmendmentAccepted {
+    pub beneficiary: Pubkey,
+    pub grant: Pubkey,
+    pub before: Terms,
+    pub after: Terms,
+    pub proposed_at: i64,
+    pub timestamp: i64,
+}
@@ -0,0 +1,372 @@
+// Merkle airdrop claims paid out of the vesting treasury.
+//
+// The admin escrows a distribution's total from the treasury into its own vault and publishes
+// the Merkle root of (index, wallet, amount) leaves. Each index can be claimed once, tracked in
+// a bitmap; after expiry the admin closes the distribution and the remainder returns to the treasury.
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::keccak;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token::{self, CloseAccount, Token, TokenAccount, Transfer};
+use errors::CommonError;
+use pause_registry::{PauseRegistry, REGISTRY_SEED, VESTING};
+use versioning::Versioned;
+
+use crate::{ErrorCode, VestingState};
+
+// Keeps the bitmap (8 KiB) under the 10 KiB limit for accounts created through CPI
+pub const MAX_CLAIMS: u32 = 64 * 1024;
+
+pub(crate) fn create_distribution(
+    ctx: Context<CreateDistribution>,
+    id: u64,
+    merkle_root: [u8; 32],
+    total_amount: u64,
+    num_claims: u32,
+    expires_at: i64,
+) -> Result<()> {
+    require!(total_amount > 0, ErrorCode::InvalidAllocation);
+    require!(num_claims > 0 && num_claims <= MAX_CLAIMS, ErrorCode::TooManyClaims);
+    require!(expires_at > Clock::get()?.unix_timestamp, CommonError::InvalidDuration);
+    // What stays in the treasury must still cover every unreleased vesting allocation
+    let state = &ctx.accounts.state;
+    let remaining = ctx
+        .accounts
+        .treasury
+        .amount
+        .checked_sub(total_amount)
+        .ok_or(ErrorCode::TreasuryUnderfunded)?;
+    require!(remaining >= state.committed_for(&state.mint), ErrorCode::TreasuryUnderfunded);
+
+    let distribution = &mut ctx.accounts.distribution;
+    distribution.version = Distribution::CURRENT_VERSION;
+    distribution.id = id;
+    distribution.merkle_root = merkle_root;
+    distribution.vault = ctx.accounts.vault.key();
+    distribution.total_amount = total_amount;
+    distribution.claimed_amount = 0;
+    distribution.expires_at = expires_at;
+    distribution.bump = *ctx.bumps.get("distribution").unwrap();
+    distribution.claimed = vec![0; Distribution::bitmap_len(num_claims)];
+
+    // Escrow the full amount so claims never dip into what's left for vesting allocations
+    let seeds = &[b"authority".as_ref(), &[*ctx.bumps.get("authority").unwrap()]];
+    token::transfer(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.treasury.to_account_info(),
+                to: ctx.accounts.vault.to_account_info(),
+                authority: ctx.accounts.authority.to_account_info(),
+            },
+            &[&seeds[..]],
+        ),
+        total_amount,
+    )?;
+
+    emit!(DistributionCreated {
+        id,
+        merkle_root,
+        total_amount,
+        num_claims,
+        expires_at,
+    });
+    Ok(())
+}
+
+pub(crate) fn claim(ctx: Context<Claim>, index: u32, amount: u64, proof: Vec<[u8; 32]>) -> Result<()> {
+    let distribution = &mut ctx.accounts.distribution;
+    let claimant = ctx.accounts.claimant.key();
+
+    require!(
+        Clock::get()?.unix_timestamp < distribution.expires_at,
+        ErrorCode::ClaimExpired
+    );
+    require!(
+        (index as usize) < distribution.claimed.len() * 8,
+        ErrorCode::InvalidClaimIndex
+    );
+    require!(!distribution.is_claimed(index), ErrorCode::AlreadyClaimed);
+    require!(
+        verify_proof(&proof, distribution.merkle_root, leaf(index, &claimant, amount)),
+        ErrorCode::InvalidProof
+    );
+
+    distribution.set_claimed(index);
+    distribution.claimed_amount = distribution
+        .claimed_amount
+        .checked_add(amount)
+        .ok_or(CommonError::Overflow)?;
+
+    let seeds = &[b"authority".as_ref(), &[*ctx.bumps.get("authority").unwrap()]];
+    token::transfer(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.vault.to_account_info(),
+                to: ctx.accounts.claimant_token_account.to_account_info(),
+                authority: ctx.accounts.authority.to_account_info(),
+            },
+            &[&seeds[..]],
+        ),
+        amount,
+    )?;
+
+    emit!(Claimed {
+        distribution: distribution.id,
+        index,
+        claimant,
+        amount,
+    });
+    Ok(())
+}
+
+pub(crate) fn close_distribution(ctx: Context<CloseDistribution>) -> Result<()> {
+    require!(
+        Clock::get()?.unix_timestamp >= ctx.accounts.distribution.expires_at,
+        ErrorCode::DistributionNotExpired
+    );
+
+    let returned = ctx.accounts.vault.amount;
+    let seeds = &[b"authority".as_ref(), &[*ctx.bumps.get("authority").unwrap()]];
+    let signer = &[&seeds[..]];
+
+    if returned > 0 {
+        token::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.vault.to_account_info(),
+                    to: ctx.accounts.treasury.to_account_info(),
+                    authority: ctx.accounts.authority.to_account_info(),
+                },
+                signer,
+            ),
+            returned,
+        )?;
+    }
+
+    token::close_account(CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        CloseAccount {
+            account: ctx.accounts.vault.to_account_info(),
+            destination: ctx.accounts.admin.to_account_info(),
+            authority: ctx.accounts.authority.to_account_info(),
+        },
+        signer,
+    ))?;
+
+    emit!(DistributionClosed {
+        id: ctx.accounts.distribution.id,
+        claimed_amount: ctx.accounts.distribution.claimed_amount,
+        returned,
+    });
+    Ok(())
+}
+
+// Leaf for claim `index`; off-chain tree builders must hash the same bytes
+pub fn leaf(index: u32, claimant: &Pubkey, amount: u64) -> [u8; 32] {
+    keccak::hashv(&[&index.to_le_bytes(), claimant.as_ref(), &amount.to_le_bytes()]).to_bytes()
+}
+
+// Sorted-pair hashing, so proofs don't need left/right flags
+pub fn hash_pair(a: &[u8; 32], b: &[u8; 32]) -> [u8; 32] {
+    if a <= b {
+        keccak::hashv(&[a, b]).to_bytes()
+    } else {
+        keccak::hashv(&[b, a]).to_bytes()
+    }
+}
+
+pub fn verify_proof(proof: &[[u8; 32]], root: [u8; 32], leaf: [u8; 32]) -> bool {
+    proof.iter().fold(leaf, |node, sibling| hash_pair(&node, sibling)) == root
+}
+
+// Accounts
+#[account]
+pub struct Distribution {
+    pub version: u8,
+    pub id: u64,
+    pub merkle_root: [u8; 32],
+    pub vault: Pubkey,
+    pub total_amount: u64,
+    pub claimed_amount: u64,
+    pub expires_at: i64,
+    pub bump: u8,
+    pub claimed: Vec<u8>, // one bit per claim index
+}
+
+impl Distribution {
+    pub const BASE_LEN: usize = 1 + 8 + 32 + 32 + 8 + 8 + 8 + 1 + 4;
+
+    pub fn bitmap_len(num_claims: u32) -> usize {
+        (num_claims as usize).div_ceil(8)
+    }
+
+    pub fn is_claimed(&self, index: u32) -> bool {
+        self.claimed[index as usize / 8] & (1 << (index % 8)) != 0
+    }
+
+    fn set_claimed(&mut self, index: u32) {
+        self.claimed[index as usize / 8] |= 1 << (index % 8);
+    }
+}
+
+impl Versioned for Distribution {
+    fn version(&self) -> u8 {
+        self.version
+    }
+}
+
+// Contexts
+#[derive(Accounts)]
+#[instruction(id: u64, merkle_root: [u8; 32], total_amount: u64, num_claims: u32)]
+pub struct CreateDistribution<'info> {
+    #[account(
+        has_one = admin @ CommonError::Unauthorized,
+        has_one = treasury,
+        seeds = [b"state"],
+        bump
+    )]
+    pub state: Account<'info, VestingState>,
+
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + Distribution::BASE_LEN + Distribution::bitmap_len(num_claims),
+        seeds = [b"distribution", id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub distribution: Account<'info, Distribution>,
+
+    #[account(
+        init,
+        payer = admin,
+        token::mint = mint,
+        token::authority = authority,
+        seeds = [b"distribution_vault", distribution.key().as_ref()],
+        bump
+    )]
+    pub vault: Account<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub treasury: Account<'info, TokenAccount>,
+
+    #[account(address = state.mint)]
+    pub mint: Account<'info, anchor_spl::token::Mint>,
+
+    /// CHECK: PDA signing for the treasury and vault
+    #[account(seeds = [b"authority"], bump)]
+    pub authority: AccountInfo<'info>,
+
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+#[derive(Accounts)]
+pub struct Claim<'info> {
+    #[account(seeds = [b"state"], bump)]
+    pub state: Account<'info, VestingState>,
+
+    #[account(
+        mut,
+        seeds = [b"distribution", distribution.id.to_le_bytes().as_ref()],
+        bump = distribution.bump,
+        has_one = vault
+    )]
+    pub distribution: Account<'info, Distribution>,
+
+    #[account(mut)]
+    pub vault: Account<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub claimant: Signer<'info>,
+
+    #[account(
+        init_if_needed,
+        payer = claimant,
+        associated_token::mint = mint,
+        associated_token::authority = claimant
+    )]
+    pub claimant_token_account: Account<'info, TokenAccount>,
+
+    #[account(address = state.mint)]
+    pub mint: Account<'info, anchor_spl::token::Mint>,
+
+    /// CHECK: PDA signing for the vault
+    #[account(seeds = [b"authority"], bump)]
+    pub authority: AccountInfo<'info>,
+
+    #[account(
+        seeds = [REGISTRY_SEED],
+        bump = pause_registry.bump,
+        seeds::program = pause_registry::ID,
+        constraint = !pause_registry.is_paused(VESTING) @ CommonError::Paused
+    )]
+    pub pause_registry: Account<'info, PauseRegistry>,
+
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct CloseDistribution<'info> {
+    #[account(
+        has_one = admin @ CommonError::Unauthorized,
+        has_one = treasury,
+        seeds = [b"state"],
+        bump
+    )]
+    pub state: Account<'info, VestingState>,
+
+    #[account(
+        mut,
+        close = admin,
+        seeds = [b"distribution", distribution.id.to_le_bytes().as_ref()],
+        bump = distribution.bump,
+        has_one = vault
+    )]
+    pub distribution: Account<'info, Distribution>,
+
+    #[account(mut)]
+    pub vault: Account<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub treasury: Account<'info, TokenAccount>,
+
+    /// CHECK: PDA signing for the vault
+    #[account(seeds = [b"authority"], bump)]
+    pub authority: AccountInfo<'info>,
+
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    pub token_program: Program<'info, Token>,
+}
+
+// Events
+#[event]
+pub struct DistributionCreated {
+    pub id: u64,
+    pub merkle_root: [u8; 32],
+    pub total_amount: u64,
+    pub num_claims: u32,
+    pub expires_at: i64,
+}
+
+#[event]
+pub struct Claimed {
+    pub distribution: u64,
+    pub index: u32,
+    pub claimant: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+pub struct DistributionClosed {
+    pub id: u64,
+    pub claimed_amount: u64,
+    pub returned: u64,
+}
@@ -0,0 +1,96 @@
+// Emergency freeze of every release, for when the treasury or mint authority may be compromised.
+//
+// The pause registry's guardian freezes releases at once. Lifting the freeze takes two steps
+// separated by `UNFREEZE_DELAY`, so a stolen guardian key can't quietly undo a freeze and drain
+// the treasury in the same breath; a fresh freeze cancels any pending unfreeze.
+use anchor_lang::prelude::*;
+use errors::CommonError;
+use pause_registry::{PauseRegistry, REGISTRY_SEED};
+
+use crate::{ErrorCode, VestingState};
+
+// Seconds between requesting and applying an unfreeze
+pub const UNFREEZE_DELAY: i64 = 48 * 60 * 60;
+
+pub(crate) fn freeze_releases(ctx: Context<GuardianFreeze>) -> Result<()> {
+    let state = &mut ctx.accounts.state;
+    state.releases_frozen = true;
+    state.unfreeze_at = 0;
+
+    emit!(ReleasesFrozen {
+        guardian: ctx.accounts.guardian.key(),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+    Ok(())
+}
+
+pub(crate) fn request_unfreeze(ctx: Context<GuardianFreeze>) -> Result<()> {
+    let state = &mut ctx.accounts.state;
+    require!(state.releases_frozen, ErrorCode::ReleasesNotFrozen);
+
+    let now = Clock::get()?.unix_timestamp;
+    state.unfreeze_at = now.checked_add(UNFREEZE_DELAY).ok_or(CommonError::Overflow)?;
+
+    emit!(UnfreezeRequested {
+        guardian: ctx.accounts.guardian.key(),
+        unfreeze_at: state.unfreeze_at,
+        timestamp: now,
+    });
+    Ok(())
+}
+
+pub(crate) fn unfreeze_releases(ctx: Context<GuardianFreeze>) -> Result<()> {
+    let state = &mut ctx.accounts.state;
+    require!(state.releases_frozen, ErrorCode::ReleasesNotFrozen);
+
+    let now = Clock::get()?.unix_timestamp;
+    require!(
+        state.unfreeze_at != 0 && now >= state.unfreeze_at,
+        ErrorCode::UnfreezeNotReady
+    );
+    state.releases_frozen = false;
+    state.unfreeze_at = 0;
+
+    emit!(ReleasesUnfrozen {
+        guardian: ctx.accounts.guardian.key(),
+        timestamp: now,
+    });
+    Ok(())
+}
+
+// Contexts
+#[derive(Accounts)]
+pub struct GuardianFreeze<'info> {
+    #[account(mut, seeds = [b"state"], bump)]
+    pub state: Account<'info, VestingState>,
+
+    #[account(
+        seeds = [REGISTRY_SEED],
+        bump = pause_registry.bump,
+        seeds::program = pause_registry::ID,
+        has_one = guardian @ CommonError::Unauthorized
+    )]
+    pub pause_registry: Account<'info, PauseRegistry>,
+
+    pub guardian: Signer<'info>,
+}
+
+// Events
+#[event]
+pub struct ReleasesFrozen {
+    pub guardian: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct UnfreezeRequested {
+    pub guardian: Pubkey,
+    pub unfreeze_at: i64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ReleasesUnfrozen {
+    pub guardian: Pubkey,
+    pub timestamp: i64,
+}
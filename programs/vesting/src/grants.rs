@@ -0,0 +1,163 @@
+// Grants denominated in mints other than the program's own token.
+//
+// The admin registers one treasury vault per extra mint on `VestingState` and funds it directly
+// (these mints are not minted by the program). Grants in such a mint live at
+// `[b"beneficiary", user, mint]` and `release` pays them from the vault matching their mint.
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Mint, Token, TokenAccount};
+use errors::CommonError;
+use versioning::Versioned;
+
+use crate::{Beneficiary, ErrorCode, GrantCreated, UserType, VestingState};
+
+// Bounds `VestingState`'s size; the program's own mint doesn't count towards it
+pub const MAX_VAULTS: usize = 4;
+
+pub(crate) fn add_vault(ctx: Context<AddVault>) -> Result<()> {
+    let state = &mut ctx.accounts.state;
+    let mint = ctx.accounts.mint.key();
+    let treasury = ctx.accounts.treasury.key();
+
+    require!(state.treasury_for(&mint).is_none(), ErrorCode::VaultExists);
+    require!(state.vaults.len() < MAX_VAULTS, ErrorCode::TooManyVaults);
+    state.vaults.push(Vault { mint, treasury, committed: 0 });
+
+    emit!(VaultAdded { mint, treasury });
+    Ok(())
+}
+
+pub(crate) fn add_grant(
+    ctx: Context<AddGrant>,
+    beneficiary: Pubkey,
+    allocation: u64,
+    user_type: UserType,
+    min_claim_interval: Option<i64>,
+) -> Result<()> {
+    let state = &mut ctx.accounts.state;
+    let grant = &mut ctx.accounts.grant;
+
+    require!(allocation > 0, ErrorCode::InvalidAllocation);
+    let min_claim_interval = min_claim_interval.unwrap_or(0);
+    require!(min_claim_interval >= 0, CommonError::InvalidDuration);
+    require_keys_eq!(beneficiary, ctx.accounts.user.key(), CommonError::Unauthorized);
+    // The vault must cover this grant on top of every unreleased grant in its mint
+    let mint = ctx.accounts.mint.key();
+    let committed = state.committed_for(&mint).checked_add(allocation).ok_or(CommonError::Overflow)?;
+    require!(
+        committed <= ctx.accounts.treasury.amount,
+        ErrorCode::InsufficientSupply
+    );
+
+    grant.version = Beneficiary::CURRENT_VERSION;
+    grant.user = beneficiary;
+    grant.allocation = allocation;
+    grant.released = 0;
+    grant.user_type = user_type;
+    grant.start_time = state.start_time;
+    grant.cliff_duration = state.cliff_duration;
+    grant.vesting_duration = state.vesting_duration;
+    grant.min_claim_interval = min_claim_interval;
+    grant.last_release_time = 0;
+    grant.mint = ctx.accounts.mint.key();
+    grant.accelerated = 0;
+    grant.accrued = 0;
+    grant.accrued_through = 0;
+    state.commit(&grant.mint, allocation)?;
+
+    emit!(GrantCreated::of(grant, grant.mint));
+
+    Ok(())
+}
+
+// A treasury for one extra mint
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub struct Vault {
+    pub mint: Pubkey,
+    pub treasury: Pubkey,
+    pub committed: u64, // allocated but unreleased
+}
+
+impl Vault {
+    pub const LEN: usize = 32 + 32 + 8;
+}
+
+// Contexts
+#[derive(Accounts)]
+pub struct AddVault<'info> {
+    #[account(
+        mut,
+        has_one = admin @ CommonError::Unauthorized,
+        seeds = [b"state"],
+        bump,
+        // States migrated from v2 only carry an empty vault list
+        realloc = 8 + VestingState::LEN,
+        realloc::payer = admin,
+        realloc::zero = false
+    )]
+    pub state: Account<'info, VestingState>,
+
+    pub mint: Account<'info, Mint>,
+
+    #[account(
+        init,
+        payer = admin,
+        token::mint = mint,
+        token::authority = authority,
+        seeds = [b"vault", mint.key().as_ref()],
+        bump
+    )]
+    pub treasury: Account<'info, TokenAccount>,
+
+    /// CHECK: PDA owning the vault
+    #[account(seeds = [b"authority"], bump)]
+    pub authority: AccountInfo<'info>,
+
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+#[derive(Accounts)]
+pub struct AddGrant<'info> {
+    #[account(
+        mut,
+        has_one = admin @ CommonError::Unauthorized,
+        seeds = [b"state"],
+        bump
+    )]
+    pub state: Account<'info, VestingState>,
+
+    pub admin: Signer<'info>,
+
+    pub mint: Account<'info, Mint>,
+
+    #[account(
+        constraint = state.treasury_for(&mint.key()) == Some(treasury.key()) @ ErrorCode::UnknownVault
+    )]
+    pub treasury: Account<'info, TokenAccount>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + Beneficiary::LEN,
+        seeds = [b"beneficiary", user.key().as_ref(), mint.key().as_ref()],
+        bump
+    )]
+    pub grant: Account<'info, Beneficiary>,
+
+    /// CHECK: User wallet address
+    pub user: AccountInfo<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+// Events
+#[event]
+pub struct VaultAdded {
+    pub mint: Pubkey,
+    pub treasury: Pubkey,
+}
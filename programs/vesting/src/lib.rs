@@ -0,0 +1,931 @@
+use anchor_lang::prelude::*;
+use anchor_lang::Discriminator;
+use anchor_spl::token::{self, Mint, Token, TokenAccount, Transfer};
+use errors::CommonError;
+use fixed_math::accrual::VestingSchedule;
+use pause_registry::{PauseRegistry, REGISTRY_SEED, VESTING};
+use versioning::{insert_after, migrate, rewrite_account, Migration, Versioned};
+
+declare_id!("8A7eVGn477Jzy5Jxd9TGzC3RjDiGja13xqe4P1JV1NTP");
+
+pub mod amendments;
+pub mod claims;
+pub mod freeze;
+pub mod grants;
+pub mod reporting;
+
+pub use amendments::*;
+pub use claims::*;
+pub use freeze::*;
+pub use grants::*;
+pub use reporting::*;
+
+#[program]
+pub mod aivaxx {
+    use super::*;
+
+    // Initialize vesting program
+    pub fn initialize(
+        ctx: Context<Initialize>,
+        total_supply: u64,
+        cliff_duration: i64,
+        vesting_duration: i64,
+    ) -> Result<()> {
+        // Validate vesting parameters
+        require!(cliff_duration >= 0, ErrorCode::InvalidCliff);
+        require!(vesting_duration > 0, CommonError::InvalidDuration);
+        require!(cliff_duration < vesting_duration, ErrorCode::InvalidCliffDuration);
+
+        let state = &mut ctx.accounts.state;
+        let clock = Clock::get()?;
+        
+        // Set up global state
+        state.version = VestingState::CURRENT_VERSION;
+        state.mint = ctx.accounts.mint.key();
+        state.treasury = ctx.accounts.treasury.key();
+        state.authority = ctx.accounts.authority.key();
+        state.admin = ctx.accounts.payer.key();
+        state.total_supply = total_supply;
+        state.cliff_duration = cliff_duration;
+        state.vesting_duration = vesting_duration;
+        state.start_time = clock.unix_timestamp;
+        state.vaults = Vec::new();
+        state.committed = 0;
+        state.releases_frozen = false;
+        state.unfreeze_at = 0;
+
+        // Mint tokens to treasury
+        let seeds: &[&[u8]] = &[
+            b"authority", 
+            &[*ctx.bumps.get("authority").unwrap()]
+        ];
+        let signer = &[seeds];
+        
+        token::mint_to(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                token::MintTo {
+                    mint: ctx.accounts.mint.to_account_info(),
+                    to: ctx.accounts.treasury.to_account_info(),
+                    authority: ctx.accounts.authority.to_account_info(),
+                },
+                signer,
+            ),
+            total_supply,
+        )?;
+
+        Ok(())
+    }
+
+    // Add a new beneficiary to the vesting program
+    pub fn add_beneficiary(
+        ctx: Context<AddBeneficiary>,
+        beneficiary: Pubkey,
+        allocation: u64,
+        user_type: UserType,
+        min_claim_interval: Option<i64>,
+    ) -> Result<()> {
+        let state = &mut ctx.accounts.state;
+        let beneficiary_account = &mut ctx.accounts.beneficiary;
+        
+        // Validate allocation
+        require!(allocation > 0, ErrorCode::InvalidAllocation);
+        let min_claim_interval = min_claim_interval.unwrap_or(0);
+        require!(min_claim_interval >= 0, CommonError::InvalidDuration);
+        require_keys_eq!(beneficiary, ctx.accounts.user.key(), CommonError::Unauthorized);
+        // The treasury must cover this grant on top of every unreleased grant in the main mint
+        let mint = state.mint;
+        let committed = state.committed_for(&mint).checked_add(allocation).ok_or(CommonError::Overflow)?;
+        require!(
+            committed <= ctx.accounts.treasury.amount,
+            ErrorCode::InsufficientSupply
+        );
+
+        // Initialize beneficiary
+        beneficiary_account.version = Beneficiary::CURRENT_VERSION;
+        beneficiary_account.user = beneficiary;
+        beneficiary_account.allocation = allocation;
+        beneficiary_account.released = 0;
+        beneficiary_account.user_type = user_type;
+        beneficiary_account.start_time = state.start_time;
+        beneficiary_account.cliff_duration = state.cliff_duration;
+        beneficiary_account.vesting_duration = state.vesting_duration;
+        beneficiary_account.min_claim_interval = min_claim_interval;
+        beneficiary_account.last_release_time = 0;
+        beneficiary_account.mint = state.mint;
+        beneficiary_account.accelerated = 0;
+        beneficiary_account.accrued = 0;
+        beneficiary_account.accrued_through = 0;
+        state.commit(&mint, allocation)?;
+
+        emit!(GrantCreated::of(beneficiary_account, mint));
+
+        Ok(())
+    }
+
+    // Release vested tokens to a beneficiary
+    pub fn release(ctx: Context<Release>) -> Result<()> {
+        let beneficiary = &mut ctx.accounts.beneficiary;
+        let clock = Clock::get()?;
+        let current_time = clock.unix_timestamp;
+
+        require!(!ctx.accounts.state.releases_frozen, ErrorCode::ReleasesFrozen);
+
+        // Enforce the claim cadence, if any
+        require!(
+            beneficiary.can_release_at(current_time),
+            ErrorCode::ReleaseTooSoon
+        );
+
+        // Calculate releasable amount
+        let releasable = beneficiary.releasable_amount(current_time)?;
+        require!(releasable > 0, ErrorCode::NoTokensAvailable);
+
+        // Update beneficiary state
+        beneficiary.released = beneficiary.released.checked_add(releasable)
+            .ok_or(CommonError::Overflow)?;
+        beneficiary.last_release_time = current_time;
+        let mint = beneficiary.grant_mint(&ctx.accounts.state);
+        ctx.accounts.state.uncommit(&mint, releasable);
+
+        // Transfer tokens
+        let seeds: &[&[u8]] = &[
+            b"authority", 
+            &[*ctx.bumps.get("authority").unwrap()]
+        ];
+        let signer = &[seeds];
+        
+        token::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.treasury.to_account_info(),
+                    to: ctx.accounts.beneficiary_token_account.to_account_info(),
+                    authority: ctx.accounts.authority.to_account_info(),
+                },
+                signer,
+            ),
+            releasable,
+        )?;
+
+        // Emit event
+        emit!(ReleaseEvent {
+            beneficiary: beneficiary.user,
+            grant: beneficiary.key(),
+            mint: ctx.accounts.treasury.mint,
+            amount: releasable,
+            timestamp: current_time,
+            user_type: beneficiary.user_type,
+        });
+
+        Ok(())
+    }
+
+    // Immediately vest `bps` of a beneficiary's still-unvested allocation (acceleration clauses)
+    pub fn accelerate(ctx: Context<Accelerate>, bps: u64) -> Result<()> {
+        require!(bps > 0 && bps <= 10_000, ErrorCode::InvalidAcceleration);
+
+        let beneficiary = &mut ctx.accounts.beneficiary;
+        let current_time = Clock::get()?.unix_timestamp;
+
+        let mut schedule = beneficiary.schedule();
+        let amount = schedule.accelerate(current_time, bps).ok_or(CommonError::Overflow)?;
+        beneficiary.accelerated = schedule.accelerated;
+
+        emit!(AccelerationEvent {
+            beneficiary: beneficiary.user,
+            grant: beneficiary.key(),
+            bps,
+            amount,
+            timestamp: current_time,
+        });
+
+        Ok(())
+    }
+
+    // Report what a grant vested in its next unreported period; permissionless crank
+    pub fn record_accrual(ctx: Context<RecordAccrual>) -> Result<()> {
+        reporting::record_accrual(ctx)
+    }
+
+    // Emit a `VestingStatement` snapshot of the caller's own grant
+    pub fn request_statement(ctx: Context<RequestStatement>) -> Result<()> {
+        reporting::request_statement(ctx)
+    }
+
+    // Offer new schedule terms for a grant; nothing changes until the beneficiary accepts
+    pub fn propose_amendment(
+        ctx: Context<ProposeAmendment>,
+        allocation: u64,
+        cliff_duration: i64,
+        vesting_duration: i64,
+    ) -> Result<()> {
+        amendments::propose_amendment(ctx, allocation, cliff_duration, vesting_duration)
+    }
+
+    pub fn accept_amendment(ctx: Context<AcceptAmendment>) -> Result<()> {
+        amendments::accept_amendment(ctx)
+    }
+
+    pub fn cancel_amendment(ctx: Context<CancelAmendment>) -> Result<()> {
+        amendments::cancel_amendment(ctx)
+    }
+
+    // Check a treasury holds enough for every unreleased allocation in its mint; fails if not
+    pub fn verify_solvency(ctx: Context<VerifySolvency>) -> Result<()> {
+        let state = &ctx.accounts.state;
+        let treasury = &ctx.accounts.treasury;
+        require!(
+            state.treasury_for(&treasury.mint) == Some(treasury.key()),
+            ErrorCode::UnknownVault
+        );
+
+        let committed = state.committed_for(&treasury.mint);
+        emit!(SolvencyReport {
+            mint: treasury.mint,
+            treasury: treasury.key(),
+            balance: treasury.amount,
+            committed,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        require!(treasury.amount >= committed, ErrorCode::TreasuryUnderfunded);
+        Ok(())
+    }
+
+    // Guardian-only: stop every release until the freeze is lifted
+    pub fn freeze_releases(ctx: Context<GuardianFreeze>) -> Result<()> {
+        freeze::freeze_releases(ctx)
+    }
+
+    // Guardian-only: start the timelock after which releases can be unfrozen
+    pub fn request_unfreeze(ctx: Context<GuardianFreeze>) -> Result<()> {
+        freeze::request_unfreeze(ctx)
+    }
+
+    // Guardian-only: resume releases once the unfreeze timelock has passed
+    pub fn unfreeze_releases(ctx: Context<GuardianFreeze>) -> Result<()> {
+        freeze::unfreeze_releases(ctx)
+    }
+
+    // Register a treasury vault for grants in another mint
+    pub fn add_vault(ctx: Context<AddVault>) -> Result<()> {
+        grants::add_vault(ctx)
+    }
+
+    // Grant a beneficiary tokens of a registered vault's mint
+    pub fn add_grant(
+        ctx: Context<AddGrant>,
+        beneficiary: Pubkey,
+        allocation: u64,
+        user_type: UserType,
+        min_claim_interval: Option<i64>,
+    ) -> Result<()> {
+        grants::add_grant(ctx, beneficiary, allocation, user_type, min_claim_interval)
+    }
+
+    // Escrow part of the treasury for a Merkle airdrop
+    pub fn create_distribution(
+        ctx: Context<CreateDistribution>,
+        id: u64,
+        merkle_root: [u8; 32],
+        total_amount: u64,
+        num_claims: u32,
+        expires_at: i64,
+    ) -> Result<()> {
+        claims::create_distribution(ctx, id, merkle_root, total_amount, num_claims, expires_at)
+    }
+
+    // Claim an airdrop allocation into the caller's associated token account
+    pub fn claim(ctx: Context<Claim>, index: u32, amount: u64, proof: Vec<[u8; 32]>) -> Result<()> {
+        claims::claim(ctx, index, amount, proof)
+    }
+
+    // Return an expired distribution's unclaimed tokens to the treasury
+    pub fn close_distribution(ctx: Context<CloseDistribution>) -> Result<()> {
+        claims::close_distribution(ctx)
+    }
+
+    // Bring a program account up to the current schema version (permissionless, payer covers any extra rent)
+    pub fn migrate_account(ctx: Context<MigrateAccount>) -> Result<()> {
+        let target = ctx.accounts.target.to_account_info();
+        let data = target.try_borrow_data()?.to_vec();
+        require!(data.len() >= 8, CommonError::UnknownAccountType);
+
+        // Distributions are sized by their bitmap, which migration carries over
+        let (migrated, min_len) = match &data[..8] {
+            d if d == VestingState::DISCRIMINATOR => (migrate::<VestingState>(&data), 8 + VestingState::LEN),
+            d if d == Beneficiary::DISCRIMINATOR => (migrate::<Beneficiary>(&data), 8 + Beneficiary::LEN),
+            d if d == Distribution::DISCRIMINATOR => (migrate::<Distribution>(&data), 0),
+            d if d == Amendment::DISCRIMINATOR => (migrate::<Amendment>(&data), 8 + Amendment::LEN),
+            _ => return err!(CommonError::UnknownAccountType),
+        };
+        let migrated = migrated.map_err(|_| CommonError::MigrationFailed)?;
+
+        if let Some(migrated) = migrated {
+            rewrite_account(
+                &target,
+                &ctx.accounts.payer.to_account_info(),
+                &ctx.accounts.system_program.to_account_info(),
+                &migrated,
+                min_len,
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+// Account Structures
+#[account]
+pub struct VestingState {
+    pub version: u8,              // Account schema version
+    pub mint: Pubkey,            // Token mint address
+    pub treasury: Pubkey,         // Treasury token account
+    pub authority: Pubkey,        // Program authority (PDA)
+    pub total_supply: u64,        // Total token supply
+    pub cliff_duration: i64,      // Cliff duration in seconds
+    pub vesting_duration: i64,    // Total vesting duration in seconds
+    pub start_time: i64,          // Program start timestamp
+    pub admin: Pubkey,            // Wallet allowed to add beneficiaries (v2)
+    pub vaults: Vec<Vault>,       // Treasuries for grants in other mints (v3)
+    pub committed: u64,           // Allocated but unreleased in the program's own mint (v4)
+    pub releases_frozen: bool,    // Set by the guardian to stop all releases (v5)
+    pub unfreeze_at: i64,         // When a requested unfreeze may apply, 0 if none pending (v5)
+}
+
+#[account]
+pub struct Beneficiary {
+    pub version: u8,              // Account schema version
+    pub user: Pubkey,             // Beneficiary wallet address
+    pub allocation: u64,          // Total allocated tokens
+    pub released: u64,            // Tokens already released
+    pub user_type: UserType,      // Founder/Advisor/Team
+    pub start_time: i64,          // Vesting start time
+    pub cliff_duration: i64,      // Cliff duration in seconds
+    pub vesting_duration: i64,    // Total vesting duration in seconds
+    pub min_claim_interval: i64,  // Minimum seconds between releases, 0 for none (v2)
+    pub last_release_time: i64,   // Timestamp of the last release, 0 if never (v2)
+    pub mint: Pubkey,             // Grant mint, zero key for the program's own mint (v3)
+    pub accelerated: u64,         // Vested early by `accelerate` (v4)
+    pub accrued: u64,             // Vested through `accrued_through`, as last reported (v5)
+    pub accrued_through: i64,     // End of the last reported accrual period, 0 if none (v5)
+}
+
+// User Type Enum
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub enum UserType {
+    Founder,
+    Advisor,
+    Team,
+}
+
+// Contexts
+#[derive(Accounts)]
+pub struct Initialize<'info> {
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + VestingState::LEN,
+        seeds = [b"state"],
+        bump
+    )]
+    pub state: Account<'info, VestingState>,
+    
+    #[account(
+        init,
+        payer = payer,
+        mint::decimals = 9,
+        mint::authority = authority,
+        mint::freeze_authority = authority
+    )]
+    pub mint: Account<'info, Mint>,
+    
+    #[account(
+        init,
+        payer = payer,
+        token::mint = mint,
+        token::authority = authority
+    )]
+    pub treasury: Account<'info, TokenAccount>,
+    
+    /// PDA authority
+    #[account(
+        seeds = [b"authority"],
+        bump
+    )]
+    pub authority: AccountInfo<'info>,
+    
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+#[derive(Accounts)]
+pub struct AddBeneficiary<'info> {
+    #[account(
+        mut,
+        has_one = admin @ CommonError::Unauthorized,
+        seeds = [b"state"],
+        bump
+    )]
+    pub state: Account<'info, VestingState>,
+
+    pub admin: Signer<'info>,
+    
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + Beneficiary::LEN,
+        seeds = [b"beneficiary", user.key().as_ref()],
+        bump
+    )]
+    pub beneficiary: Account<'info, Beneficiary>,
+    
+    /// CHECK: User wallet address
+    pub user: AccountInfo<'info>,
+    
+    #[account(constraint = state.treasury == treasury.key() @ ErrorCode::UnknownVault)]
+    pub treasury: Account<'info, TokenAccount>,
+    
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct Release<'info> {
+    #[account(
+        mut,
+        has_one = authority @ CommonError::Unauthorized,
+        seeds = [b"state"],
+        bump
+    )]
+    pub state: Account<'info, VestingState>,
+    
+    // Any grant, in the program's own mint or a vault's
+    #[account(mut)]
+    pub beneficiary: Account<'info, Beneficiary>,
+    
+    #[account(
+        mut,
+        associated_token::mint = beneficiary.grant_mint(&state),
+        associated_token::authority = beneficiary.user
+    )]
+    pub beneficiary_token_account: Account<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        constraint = state.treasury_for(&beneficiary.grant_mint(&state)) == Some(treasury.key())
+            @ ErrorCode::UnknownVault,
+        token::authority = authority
+    )]
+    pub treasury: Account<'info, TokenAccount>,
+    
+    /// PDA authority
+    #[account(
+        seeds = [b"authority"],
+        bump
+    )]
+    pub authority: AccountInfo<'info>,
+
+    #[account(
+        seeds = [REGISTRY_SEED],
+        bump = pause_registry.bump,
+        seeds::program = pause_registry::ID,
+        constraint = !pause_registry.is_paused(VESTING) @ CommonError::Paused
+    )]
+    pub pause_registry: Account<'info, PauseRegistry>,
+    
+    pub token_program: Program<'info, Token>,
+    pub clock: Sysvar<'info, Clock>,
+}
+
+#[derive(Accounts)]
+pub struct VerifySolvency<'info> {
+    #[account(seeds = [b"state"], bump)]
+    pub state: Account<'info, VestingState>,
+
+    pub treasury: Account<'info, TokenAccount>,
+}
+
+#[derive(Accounts)]
+pub struct Accelerate<'info> {
+    #[account(
+        has_one = admin @ CommonError::Unauthorized,
+        seeds = [b"state"],
+        bump
+    )]
+    pub state: Account<'info, VestingState>,
+
+    pub admin: Signer<'info>,
+
+    #[account(mut)]
+    pub beneficiary: Account<'info, Beneficiary>,
+}
+
+#[derive(Accounts)]
+pub struct MigrateAccount<'info> {
+    /// CHECK: Owned by this program; type is identified by its discriminator before rewriting
+    #[account(mut, owner = crate::ID)]
+    pub target: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+// Error Codes (shared failures use errors::CommonError)
+#[error_code(offset = 7000)]
+pub enum ErrorCode {
+    #[msg("Invalid cliff duration")]
+    InvalidCliff,
+    #[msg("Cliff must be shorter than vesting duration")]
+    InvalidCliffDuration,
+    #[msg("Invalid token allocation")]
+    InvalidAllocation,
+    #[msg("Insufficient token supply")]
+    InsufficientSupply,
+    #[msg("No tokens available for release")]
+    NoTokensAvailable,
+    #[msg("Claim window has closed")]
+    ClaimExpired,
+    #[msg("Allocation already claimed")]
+    AlreadyClaimed,
+    #[msg("Merkle proof does not match the distribution root")]
+    InvalidProof,
+    #[msg("Claim index is outside the distribution")]
+    InvalidClaimIndex,
+    #[msg("Distribution has not expired yet")]
+    DistributionNotExpired,
+    #[msg("Too many claims for one distribution")]
+    TooManyClaims,
+    #[msg("Minimum interval since the last release has not passed")]
+    ReleaseTooSoon,
+    #[msg("No treasury vault is registered for this mint")]
+    UnknownVault,
+    #[msg("A treasury vault already exists for this mint")]
+    VaultExists,
+    #[msg("Too many treasury vaults")]
+    TooManyVaults,
+    #[msg("Acceleration must be between 1 and 10000 bps")]
+    InvalidAcceleration,
+    #[msg("Treasury holds less than the unreleased allocations")]
+    TreasuryUnderfunded,
+    #[msg("No accrual period is due for this grant")]
+    AccrualNotDue,
+    #[msg("Releases are frozen")]
+    ReleasesFrozen,
+    #[msg("Releases are not frozen")]
+    ReleasesNotFrozen,
+    #[msg("Unfreeze was not requested or its timelock has not passed")]
+    UnfreezeNotReady,
+}
+
+impl ErrorCode {
+    pub const ALL: [ErrorCode; 21] = [
+        ErrorCode::InvalidCliff,
+        ErrorCode::InvalidCliffDuration,
+        ErrorCode::InvalidAllocation,
+        ErrorCode::InsufficientSupply,
+        ErrorCode::NoTokensAvailable,
+        ErrorCode::ClaimExpired,
+        ErrorCode::AlreadyClaimed,
+        ErrorCode::InvalidProof,
+        ErrorCode::InvalidClaimIndex,
+        ErrorCode::DistributionNotExpired,
+        ErrorCode::TooManyClaims,
+        ErrorCode::ReleaseTooSoon,
+        ErrorCode::UnknownVault,
+        ErrorCode::VaultExists,
+        ErrorCode::TooManyVaults,
+        ErrorCode::InvalidAcceleration,
+        ErrorCode::TreasuryUnderfunded,
+        ErrorCode::AccrualNotDue,
+        ErrorCode::ReleasesFrozen,
+        ErrorCode::ReleasesNotFrozen,
+        ErrorCode::UnfreezeNotReady,
+    ];
+}
+
+// Events
+// A grant's starting schedule, the first event an off-chain mirror replays for it
+#[event]
+pub struct GrantCreated {
+    pub beneficiary: Pubkey,
+    pub grant: Pubkey,
+    pub mint: Pubkey,
+    pub allocation: u64,
+    pub start_time: i64,
+    pub cliff_duration: i64,
+    pub vesting_duration: i64,
+}
+
+impl GrantCreated {
+    fn of(grant: &Account<Beneficiary>, mint: Pubkey) -> Self {
+        GrantCreated {
+            beneficiary: grant.user,
+            grant: grant.key(),
+            mint,
+            allocation: grant.allocation,
+            start_time: grant.start_time,
+            cliff_duration: grant.cliff_duration,
+            vesting_duration: grant.vesting_duration,
+        }
+    }
+}
+
+#[event]
+pub struct ReleaseEvent {
+    pub beneficiary: Pubkey,
+    pub grant: Pubkey,
+    pub mint: Pubkey,
+    pub amount: u64,
+    pub timestamp: i64,
+    pub user_type: UserType,
+}
+
+#[event]
+pub struct SolvencyReport {
+    pub mint: Pubkey,
+    pub treasury: Pubkey,
+    pub balance: u64,
+    pub committed: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct AccelerationEvent {
+    pub beneficiary: Pubkey,
+    pub grant: Pubkey,
+    pub bps: u64,
+    pub amount: u64,
+    pub timestamp: i64,
+}
+
+// Implementation for Beneficiary
+impl Beneficiary {
+    const LEN: usize = 1 + 32 + 8 + 8 + 1 + 8 + 8 + 8 + 8 + 8 + 32 + 8 + 8 + 8;
+
+    // Grants from before v3 are all in the program's own mint
+    pub fn grant_mint(&self, state: &VestingState) -> Pubkey {
+        if self.mint == Pubkey::default() {
+            state.mint
+        } else {
+            self.mint
+        }
+    }
+
+    // The first release is never throttled
+    pub fn can_release_at(&self, current_time: i64) -> bool {
+        self.min_claim_interval == 0
+            || self.last_release_time == 0
+            || current_time >= self.last_release_time.saturating_add(self.min_claim_interval)
+    }
+
+    // Calculate releasable tokens (shared with off-chain mirrors via fixed_math::accrual)
+    pub fn releasable_amount(&self, current_time: i64) -> Result<u64> {
+        Ok(self
+            .schedule()
+            .releasable_at(current_time, self.released)
+            .ok_or(CommonError::Overflow)?)
+    }
+
+    pub fn schedule(&self) -> VestingSchedule {
+        VestingSchedule {
+            allocation: self.allocation,
+            start_time: self.start_time,
+            cliff_duration: self.cliff_duration,
+            vesting_duration: self.vesting_duration,
+            accelerated: self.accelerated,
+        }
+    }
+}
+
+// Implementation for VestingState
+impl VestingState {
+    const LEN: usize = 1 + 32 + 32 + 32 + 8 + 8 + 8 + 8 + 32 + 4 + MAX_VAULTS * Vault::LEN + 8 + 1 + 8;
+
+    // Treasury holding `mint`, if the program pays out in it
+    pub fn treasury_for(&self, mint: &Pubkey) -> Option<Pubkey> {
+        if *mint == self.mint {
+            return Some(self.treasury);
+        }
+        self.vaults.iter().find(|vault| vault.mint == *mint).map(|vault| vault.treasury)
+    }
+
+    // Unreleased allocations payable from `mint`'s treasury
+    pub fn committed_for(&self, mint: &Pubkey) -> u64 {
+        if *mint == self.mint {
+            return self.committed;
+        }
+        self.vaults.iter().find(|vault| vault.mint == *mint).map_or(0, |vault| vault.committed)
+    }
+
+    pub fn commit(&mut self, mint: &Pubkey, amount: u64) -> Result<()> {
+        let committed = if *mint == self.mint {
+            &mut self.committed
+        } else {
+            let vault = self.vaults.iter_mut().find(|vault| vault.mint == *mint);
+            &mut vault.ok_or(ErrorCode::UnknownVault)?.committed
+        };
+        *committed = committed.checked_add(amount).ok_or(CommonError::Overflow)?;
+        Ok(())
+    }
+
+    // Saturates: grants made before v4 were never counted
+    pub fn uncommit(&mut self, mint: &Pubkey, amount: u64) {
+        if *mint == self.mint {
+            self.committed = self.committed.saturating_sub(amount);
+        } else if let Some(vault) = self.vaults.iter_mut().find(|vault| vault.mint == *mint) {
+            vault.committed = vault.committed.saturating_sub(amount);
+        }
+    }
+}
+
+impl Versioned for VestingState {
+    const MIGRATIONS: &'static [Migration] = &[
+        legacy::vesting_state_v1_to_v2,
+        legacy::vesting_state_v2_to_v3,
+        legacy::vesting_state_v3_to_v4,
+        legacy::vesting_state_v4_to_v5,
+    ];
+    // Discriminator plus mint, treasury, authority and four 8-byte fields
+    const UNVERSIONED_LEN: Option<usize> = Some(8 + 32 + 32 + 32 + 8 + 8 + 8 + 8);
+
+    fn version(&self) -> u8 {
+        self.version
+    }
+}
+
+impl Versioned for Beneficiary {
+    const MIGRATIONS: &'static [Migration] = &[
+        legacy::beneficiary_v1_to_v2,
+        legacy::beneficiary_v2_to_v3,
+        legacy::beneficiary_v3_to_v4,
+        legacy::beneficiary_v4_to_v5,
+    ];
+    // Discriminator plus the version 1 fields
+    const UNVERSIONED_LEN: Option<usize> = Some(8 + 32 + 8 + 8 + 1 + 8 + 8 + 8);
+
+    fn version(&self) -> u8 {
+        self.version
+    }
+}
+
+// Layouts of earlier account versions, kept for `migrate_account`
+#[allow(dead_code)] // some layouts are only parsed to find where they end
+mod legacy {
+    use super::*;
+
+    #[derive(AnchorDeserialize)]
+    pub struct VestingStateV1 {
+        pub mint: Pubkey,
+        pub treasury: Pubkey,
+        pub authority: Pubkey,
+        pub total_supply: u64,
+        pub cliff_duration: i64,
+        pub vesting_duration: i64,
+        pub start_time: i64,
+    }
+
+    // No admin was recorded before v2; the zero key leaves beneficiary management disabled
+    pub fn vesting_state_v1_to_v2(body: &[u8]) -> Option<Vec<u8>> {
+        insert_after::<VestingStateV1>(body, Pubkey::default().as_ref())
+    }
+
+    #[derive(AnchorDeserialize)]
+    pub struct BeneficiaryV1 {
+        pub user: Pubkey,
+        pub allocation: u64,
+        pub released: u64,
+        pub user_type: UserType,
+        pub start_time: i64,
+        pub cliff_duration: i64,
+        pub vesting_duration: i64,
+    }
+
+    // Existing grants keep releasing without a cadence cap
+    pub fn beneficiary_v1_to_v2(body: &[u8]) -> Option<Vec<u8>> {
+        insert_after::<BeneficiaryV1>(body, &[0u8; 8 + 8])
+    }
+
+    #[derive(AnchorDeserialize)]
+    pub struct VestingStateV2 {
+        pub mint: Pubkey,
+        pub treasury: Pubkey,
+        pub authority: Pubkey,
+        pub total_supply: u64,
+        pub cliff_duration: i64,
+        pub vesting_duration: i64,
+        pub start_time: i64,
+        pub admin: Pubkey,
+    }
+
+    // Empty vault list; `add_vault` grows the account to full size
+    pub fn vesting_state_v2_to_v3(body: &[u8]) -> Option<Vec<u8>> {
+        insert_after::<VestingStateV2>(body, &0u32.to_le_bytes())
+    }
+
+    #[derive(AnchorDeserialize)]
+    pub struct BeneficiaryV2 {
+        pub user: Pubkey,
+        pub allocation: u64,
+        pub released: u64,
+        pub user_type: UserType,
+        pub start_time: i64,
+        pub cliff_duration: i64,
+        pub vesting_duration: i64,
+        pub min_claim_interval: i64,
+        pub last_release_time: i64,
+    }
+
+    // Zero key resolves to the program's own mint (see `Beneficiary::grant_mint`)
+    pub fn beneficiary_v2_to_v3(body: &[u8]) -> Option<Vec<u8>> {
+        insert_after::<BeneficiaryV2>(body, Pubkey::default().as_ref())
+    }
+
+    #[derive(AnchorDeserialize)]
+    pub struct BeneficiaryV3 {
+        pub user: Pubkey,
+        pub allocation: u64,
+        pub released: u64,
+        pub user_type: UserType,
+        pub start_time: i64,
+        pub cliff_duration: i64,
+        pub vesting_duration: i64,
+        pub min_claim_interval: i64,
+        pub last_release_time: i64,
+        pub mint: Pubkey,
+    }
+
+    pub fn beneficiary_v3_to_v4(body: &[u8]) -> Option<Vec<u8>> {
+        insert_after::<BeneficiaryV3>(body, &0u64.to_le_bytes())
+    }
+
+    #[derive(AnchorDeserialize)]
+    pub struct BeneficiaryV4 {
+        pub user: Pubkey,
+        pub allocation: u64,
+        pub released: u64,
+        pub user_type: UserType,
+        pub start_time: i64,
+        pub cliff_duration: i64,
+        pub vesting_duration: i64,
+        pub min_claim_interval: i64,
+        pub last_release_time: i64,
+        pub mint: Pubkey,
+        pub accelerated: u64,
+    }
+
+    // Nothing reported yet; the first crank starts from the grant's start time
+    pub fn beneficiary_v4_to_v5(body: &[u8]) -> Option<Vec<u8>> {
+        insert_after::<BeneficiaryV4>(body, &[0u8; 8 + 8])
+    }
+
+    #[derive(AnchorDeserialize)]
+    pub struct VaultV1 {
+        pub mint: Pubkey,
+        pub treasury: Pubkey,
+    }
+
+    // Committed counters start at zero: existing grants aren't enumerable on-chain, so solvency
+    // checks only cover allocations made after the migration
+    pub fn vesting_state_v3_to_v4(body: &[u8]) -> Option<Vec<u8>> {
+        let mut rest = body;
+        VestingStateV2::deserialize(&mut rest).ok()?;
+        let prefix_len = body.len() - rest.len();
+        let vaults: Vec<Vault> = Vec::<VaultV1>::deserialize(&mut rest)
+            .ok()?
+            .into_iter()
+            .map(|vault| Vault { mint: vault.mint, treasury: vault.treasury, committed: 0 })
+            .collect();
+
+        let mut migrated = body[..prefix_len].to_vec();
+        vaults.serialize(&mut migrated).ok()?;
+        migrated.extend_from_slice(&0u64.to_le_bytes());
+        migrated.extend_from_slice(rest);
+        Some(migrated)
+    }
+
+    #[derive(AnchorDeserialize)]
+    pub struct VestingStateV4 {
+        pub mint: Pubkey,
+        pub treasury: Pubkey,
+        pub authority: Pubkey,
+        pub total_supply: u64,
+        pub cliff_duration: i64,
+        pub vesting_duration: i64,
+        pub start_time: i64,
+        pub admin: Pubkey,
+        pub vaults: Vec<Vault>,
+        pub committed: u64,
+    }
+
+    // Releases stay open, with no unfreeze pending
+    pub fn vesting_state_v4_to_v5(body: &[u8]) -> Option<Vec<u8>> {
+        insert_after::<VestingStateV4>(body, &[0u8; 1 + 8])
+    }
+}
@@ -0,0 +1,140 @@
+// Accrual-basis reporting of vesting expense.
+//
+// Anyone can crank `record_accrual` once per grant and period. Each call closes the next period
+// since the grant's last report and emits a `VestingAccrual` with what vested in it, claimed or
+// not. Grants that fell behind catch up one period per call, so the event stream has no gaps.
+//
+// `request_statement` lets a beneficiary emit a snapshot of their own grant, which support can
+// read from an explorer instead of running scripts against the account.
+use anchor_lang::prelude::*;
+use errors::CommonError;
+
+use crate::{Beneficiary, ErrorCode, VestingState};
+
+// Reporting periods are fixed 30-day months counted from each grant's start
+pub const ACCRUAL_PERIOD: i64 = 30 * 24 * 60 * 60;
+
+pub(crate) fn record_accrual(ctx: Context<RecordAccrual>) -> Result<()> {
+    let beneficiary = &mut ctx.accounts.beneficiary;
+    let current_time = Clock::get()?.unix_timestamp;
+
+    let period_start = if beneficiary.accrued_through == 0 {
+        beneficiary.start_time
+    } else {
+        beneficiary.accrued_through
+    };
+    let period_end = period_start.checked_add(ACCRUAL_PERIOD).ok_or(CommonError::Overflow)?;
+    require!(
+        current_time >= period_end && beneficiary.accrued < beneficiary.allocation,
+        ErrorCode::AccrualNotDue
+    );
+
+    let vested = beneficiary.schedule().vested_at(period_end).ok_or(CommonError::Overflow)?;
+    let amount = vested.saturating_sub(beneficiary.accrued);
+    beneficiary.accrued = beneficiary.accrued.max(vested);
+    beneficiary.accrued_through = period_end;
+
+    emit!(VestingAccrual {
+        beneficiary: beneficiary.user,
+        grant: beneficiary.key(),
+        mint: beneficiary.grant_mint(&ctx.accounts.state),
+        period_start,
+        period_end,
+        amount,
+        cumulative: beneficiary.accrued,
+    });
+
+    Ok(())
+}
+
+pub(crate) fn request_statement(ctx: Context<RequestStatement>) -> Result<()> {
+    let state = &ctx.accounts.state;
+    let beneficiary = &ctx.accounts.beneficiary;
+    let current_time = Clock::get()?.unix_timestamp;
+
+    let vested = beneficiary.schedule().vested_at(current_time).ok_or(CommonError::Overflow)?;
+    let claimable = if beneficiary.can_release_at(current_time) {
+        beneficiary.releasable_amount(current_time)?
+    } else {
+        0
+    };
+
+    emit!(VestingStatement {
+        beneficiary: beneficiary.user,
+        grant: beneficiary.key(),
+        mint: beneficiary.grant_mint(state),
+        allocation: beneficiary.allocation,
+        vested,
+        released: beneficiary.released,
+        claimable,
+        next_unlock: next_unlock(beneficiary, vested, current_time),
+        releases_frozen: state.releases_frozen,
+        timestamp: current_time,
+    });
+
+    Ok(())
+}
+
+// Earliest time from now at which `release` can pay out: the cliff, or the end of the claim
+// cadence's wait. 0 once everything has vested.
+fn next_unlock(beneficiary: &Beneficiary, vested: u64, current_time: i64) -> i64 {
+    if vested >= beneficiary.allocation {
+        return 0;
+    }
+    let cliff_end = beneficiary.start_time.saturating_add(beneficiary.cliff_duration);
+    if current_time < cliff_end {
+        return cliff_end;
+    }
+    if beneficiary.can_release_at(current_time) {
+        current_time
+    } else {
+        beneficiary.last_release_time.saturating_add(beneficiary.min_claim_interval)
+    }
+}
+
+// Contexts
+#[derive(Accounts)]
+pub struct RecordAccrual<'info> {
+    #[account(seeds = [b"state"], bump)]
+    pub state: Account<'info, VestingState>,
+
+    #[account(mut)]
+    pub beneficiary: Account<'info, Beneficiary>,
+}
+
+#[derive(Accounts)]
+pub struct RequestStatement<'info> {
+    #[account(seeds = [b"state"], bump)]
+    pub state: Account<'info, VestingState>,
+
+    pub user: Signer<'info>,
+
+    #[account(constraint = beneficiary.user == user.key() @ CommonError::Unauthorized)]
+    pub beneficiary: Account<'info, Beneficiary>,
+}
+
+// Events
+#[event]
+pub struct VestingAccrual {
+    pub beneficiary: Pubkey,
+    pub grant: Pubkey,
+    pub mint: Pubkey,
+    pub period_start: i64,
+    pub period_end: i64,
+    pub amount: u64,     // vested during the period, released or not
+    pub cumulative: u64, // vested through `period_end`
+}
+
+#[event]
+pub struct VestingStatement {
+    pub beneficiary: Pubkey,
+    pub grant: Pubkey,
+    pub mint: Pubkey,
+    pub allocation: u64,
+    pub vested: u64,    // to date, acceleration included
+    pub released: u64,
+    pub claimable: u64, // what `release` would pay now, 0 while the claim cadence holds it back
+    pub next_unlock: i64,
+    pub releases_frozen: bool,
+    pub timestamp: i64,
+}
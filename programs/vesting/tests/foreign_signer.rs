@@ -0,0 +1,108 @@
+// Admin-only instructions reject a signer other than the admin recorded on the vesting state.
+// Runs the program natively under solana-program-test: `cargo test -p vesting`.
+use anchor_lang::solana_program::account_info::AccountInfo;
+use anchor_lang::solana_program::entrypoint::ProgramResult;
+use anchor_lang::{InstructionData, ToAccountMetas};
+use errors::CommonError;
+use solana_program_test::{processor, BanksClientError, ProgramTest, ProgramTestContext};
+use solana_sdk::instruction::{Instruction, InstructionError};
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::{Keypair, Signer};
+use solana_sdk::transaction::{Transaction, TransactionError};
+use solana_sdk::{system_program, sysvar};
+use vesting::{accounts, instruction, UserType};
+
+fn process(program_id: &Pubkey, accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    // Anchor's entrypoint wants the slice to live as long as the accounts in it
+    let accounts = Box::leak(Box::new(accounts.to_vec()));
+    vesting::entry(program_id, accounts, data)
+}
+
+fn pda(seeds: &[&[u8]]) -> Pubkey {
+    Pubkey::find_program_address(seeds, &vesting::ID).0
+}
+
+// The context's payer pays the fee and signs alongside `signers`
+async fn send(
+    ctx: &mut ProgramTestContext,
+    ix: Instruction,
+    signers: &[&Keypair],
+) -> Result<(), BanksClientError> {
+    let mut all = vec![&ctx.payer];
+    all.extend_from_slice(signers);
+    let blockhash = ctx.banks_client.get_latest_blockhash().await?;
+    let tx = Transaction::new_signed_with_payer(&[ix], Some(&ctx.payer.pubkey()), &all, blockhash);
+    ctx.banks_client.process_transaction(tx).await
+}
+
+// A vesting state whose admin is the context's payer, and its treasury
+async fn start() -> (ProgramTestContext, Pubkey) {
+    let mut ctx = ProgramTest::new("vesting", vesting::ID, processor!(process))
+        .start_with_context()
+        .await;
+    let (mint, treasury) = (Keypair::new(), Keypair::new());
+    let ix = Instruction {
+        program_id: vesting::ID,
+        accounts: accounts::Initialize {
+            state: pda(&[b"state"]),
+            mint: mint.pubkey(),
+            treasury: treasury.pubkey(),
+            authority: pda(&[b"authority"]),
+            payer: ctx.payer.pubkey(),
+            system_program: system_program::ID,
+            token_program: anchor_spl::token::ID,
+            rent: sysvar::rent::ID,
+        }
+        .to_account_metas(None),
+        data: instruction::Initialize {
+            total_supply: 1_000_000,
+            cliff_duration: 0,
+            vesting_duration: 100,
+        }
+        .data(),
+    };
+    send(&mut ctx, ix, &[&mint, &treasury]).await.unwrap();
+    (ctx, treasury.pubkey())
+}
+
+fn add_beneficiary(admin: Pubkey, payer: Pubkey, user: Pubkey, treasury: Pubkey) -> Instruction {
+    Instruction {
+        program_id: vesting::ID,
+        accounts: accounts::AddBeneficiary {
+            state: pda(&[b"state"]),
+            admin,
+            beneficiary: pda(&[b"beneficiary", user.as_ref()]),
+            user,
+            treasury,
+            payer,
+            system_program: system_program::ID,
+        }
+        .to_account_metas(None),
+        data: instruction::AddBeneficiary {
+            beneficiary: user,
+            allocation: 1_000,
+            user_type: UserType::Team,
+            min_claim_interval: None,
+        }
+        .data(),
+    }
+}
+
+#[tokio::test]
+async fn add_beneficiary_rejects_a_non_admin() {
+    let (mut ctx, treasury) = start().await;
+    let (stranger, user) = (Keypair::new(), Pubkey::new_unique());
+    let payer = ctx.payer.pubkey();
+
+    let ix = add_beneficiary(stranger.pubkey(), payer, user, treasury);
+    let result = send(&mut ctx, ix, &[&stranger]).await;
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(u32::from(CommonError::Unauthorized))
+        )
+    );
+
+    send(&mut ctx, add_beneficiary(payer, payer, user, treasury), &[]).await.unwrap();
+}
@@ -1,40 +0,0 @@
-#[derive(Debug, PartialEq, Eq)]
-pub struct Dna(String);
-
-#[derive(Debug, PartialEq, Eq)]
-pub struct Rna(String);
-
-impl Dna {
-    pub fn new(dna: &str) -> Result<Dna, usize> {
-        for (i, c) in dna.chars().enumerate() {
-            if !"GCTA".contains(c) {
-                return Err(i);
-            }
-        }
-        Ok(Dna(dna.to_string()))
-    }
-
-    pub fn into_rna(self) -> Rna {
-        let transcribed = self.0.chars().map(|c| {
-            match c {
-                'G' => 'C',
-                'C' => 'G',
-                'T' => 'A',
-                'A' => 'U',
-                _ => unreachable!(), // Already validated in `new`
-            }
-        }).collect();
-        Rna(transcribed)
-    }
-}
-
-impl Rna {
-    pub fn new(rna: &str) -> Result<Rna, usize> {
-        for (i, c) in rna.chars().enumerate() {
-            if !"CGAU".contains(c) {
-                return Err(i);
-            }
-        }
-        Ok(Rna(rna.to_string()))
-    }
-}
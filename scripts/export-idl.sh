@@ -0,0 +1,10 @@
+#!/usr/bin/env bash
+# Writes the Anchor IDL for every program to idl/<program>.json for the TypeScript frontend.
+set -euo pipefail
+
+cd "$(dirname "$0")/.."
+mkdir -p idl
+
+for program in vesting betting pause-registry; do
+    anchor idl parse -f "programs/$program/src/lib.rs" -o "idl/$program.json"
+done